@@ -0,0 +1,28 @@
+// Writes .br and .gz next to each compressible file in dist/assets, so the
+// server can send them as-is in production mode instead of compressing on
+// every request. Variants that would not be smaller are skipped.
+import { readdirSync, readFileSync, writeFileSync } from "node:fs"
+import { join } from "node:path"
+import { brotliCompressSync, constants, gzipSync } from "node:zlib"
+
+const dir = join(import.meta.dirname, "..", "dist", "assets")
+const compressible = /\.(js|mjs|css|html|json|svg|txt|map)$/
+
+for (const name of readdirSync(dir)) {
+  if (!compressible.test(name)) continue
+
+  const file = join(dir, name)
+  const source = readFileSync(file)
+  const variants = {
+    br: brotliCompressSync(source, {
+      params: { [constants.BROTLI_PARAM_QUALITY]: constants.BROTLI_MAX_QUALITY },
+    }),
+    gz: gzipSync(source, { level: 9 }),
+  }
+
+  for (const [extension, compressed] of Object.entries(variants)) {
+    if (compressed.length < source.length) {
+      writeFileSync(`${file}.${extension}`, compressed)
+    }
+  }
+}
@@ -0,0 +1,5 @@
+// The API reference at /api/docs, bundled with the app instead of loaded from a CDN
+import { createApiReference } from '@scalar/api-reference';
+import '@scalar/api-reference/style.css';
+
+createApiReference('#api-reference', { url: '/api/openapi.json' });
@@ -0,0 +1,259 @@
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+
+/// Unversioned `/api` paths were deprecated on 2026-10-19 (RFC 9745 date)
+const LEGACY_DEPRECATED_AT: &str = "@1792368000";
+/// ...and stop being served six months later (RFC 8594 HTTP-date)
+const LEGACY_SUNSET: &str = "Mon, 19 Apr 2027 00:00:00 GMT";
+
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Version of the JSON API a request was routed through. The same handlers
+/// serve every version; anything that differs between them lives here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    /// The original unversioned `/api` paths, kept for existing clients.
+    /// Responses match v1 and carry deprecation headers.
+    Legacy,
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    pub fn prefix(self) -> &'static str {
+        match self {
+            ApiVersion::Legacy => "/api",
+            ApiVersion::V1 => "/api/v1",
+            ApiVersion::V2 => "/api/v2",
+        }
+    }
+
+    /// The version clients of a deprecated version should move to
+    fn successor(self) -> Option<ApiVersion> {
+        match self {
+            ApiVersion::Legacy => Some(ApiVersion::V1),
+            ApiVersion::V1 | ApiVersion::V2 => None,
+        }
+    }
+
+    /// Representation of a single (non-paginated) response body. v1 returns
+    /// the resource itself; v2 wraps it in `{ "data": ... }` so every body
+    /// has the same envelope as a page.
+    pub fn body<T: Serialize>(self, value: &T) -> Value {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+        match self {
+            ApiVersion::Legacy | ApiVersion::V1 => value,
+            ApiVersion::V2 => json!({ "data": value }),
+        }
+    }
+
+    pub fn json<T: Serialize>(self, value: &T) -> Response {
+        Json(self.body(value)).into_response()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ApiVersion {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<ApiVersion>().copied().unwrap_or(ApiVersion::V1))
+    }
+}
+
+/// Middleware for a version's router tree: tags the request with its version
+/// and marks responses from deprecated versions with `Deprecation`, `Sunset`
+/// and a `Link` to the same resource under the successor version.
+pub async fn version_layer(State(version): State<ApiVersion>, mut request: Request, next: Next) -> Response {
+    request.extensions_mut().insert(version);
+
+    // Inside a nested router the URI no longer carries the version prefix
+    let successor = version.successor().and_then(|successor| {
+        let path = request.uri().path();
+        HeaderValue::from_str(&format!("<{}{}>; rel=\"successor-version\"", successor.prefix(), path)).ok()
+    });
+
+    let mut response = next.run(request).await;
+
+    if version == ApiVersion::Legacy {
+        let headers = response.headers_mut();
+        headers.insert(DEPRECATION, HeaderValue::from_static(LEGACY_DEPRECATED_AT));
+        headers.insert(SUNSET, HeaderValue::from_static(LEGACY_SUNSET));
+        if let Some(link) = successor {
+            // Appended so a pagination `rel="next"` link survives
+            headers.append(header::LINK, link);
+        }
+    }
+
+    response
+}
+
+/// Pins the v1 JSON shapes. The Terraform provider and other clients decode
+/// these fields by name, so a failure here means a breaking change: make it
+/// in a new version's serializer instead of the shared models.
+#[cfg(test)]
+mod compat_tests {
+    use super::*;
+    use db_core::models::incident::Incident;
+    use db_core::models::monitor::Monitor;
+    use db_core::models::status_event::StatusEvent;
+    use time::OffsetDateTime;
+
+    fn noon() -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(1_735_732_800).unwrap()
+    }
+
+    fn monitor() -> Monitor {
+        Monitor {
+            id: 7,
+            name: "api".to_string(),
+            display_name: "Public API".to_string(),
+            description: None,
+            url: Some("https://example.com/health".to_string()),
+            monitor_type: "http".to_string(),
+            check_interval: 60,
+            timeout: 10,
+            is_active: true,
+            metadata: Some(json!({ "service_group": "edge" })),
+            service_group_id: Some(2),
+            weight: 1,
+            created_at: noon(),
+            updated_at: noon(),
+        }
+    }
+
+    #[test]
+    fn monitor_v1_shape() {
+        assert_eq!(
+            ApiVersion::V1.body(&monitor()),
+            json!({
+                "id": 7,
+                "name": "api",
+                "display_name": "Public API",
+                "description": null,
+                "url": "https://example.com/health",
+                "monitor_type": "http",
+                "check_interval": 60,
+                "timeout": 10,
+                "is_active": true,
+                "metadata": { "service_group": "edge" },
+                "service_group_id": 2,
+                "weight": 1,
+                "created_at": "2025-01-01T12:00:00Z",
+                "updated_at": "2025-01-01T12:00:00Z",
+            })
+        );
+    }
+
+    #[test]
+    fn incident_v1_shape() {
+        let incident = Incident {
+            id: 3,
+            title: "Elevated errors".to_string(),
+            message: "Investigating".to_string(),
+            severity: "warning".to_string(),
+            affected_monitors: vec![7],
+            created_at: noon(),
+            updated_at: noon(),
+            started_at: noon(),
+            resolved_at: None,
+            is_resolved: false,
+            metadata: None,
+        };
+
+        assert_eq!(
+            ApiVersion::V1.body(&incident),
+            json!({
+                "id": 3,
+                "title": "Elevated errors",
+                "message": "Investigating",
+                "severity": "warning",
+                "affected_monitors": [7],
+                "created_at": "2025-01-01T12:00:00Z",
+                "updated_at": "2025-01-01T12:00:00Z",
+                "started_at": "2025-01-01T12:00:00Z",
+                "resolved_at": null,
+                "is_resolved": false,
+                "metadata": null,
+            })
+        );
+    }
+
+    #[test]
+    fn status_event_v1_shape() {
+        let event = StatusEvent {
+            id: 41,
+            time: noon(),
+            monitor_id: 7,
+            status: "operational".to_string(),
+            response_time: Some(120),
+            status_code: Some(200),
+            error_message: None,
+            metadata: None,
+            created_at: noon(),
+        };
+
+        assert_eq!(
+            ApiVersion::V1.body(&event),
+            json!({
+                "id": 41,
+                "time": "2025-01-01T12:00:00Z",
+                "monitor_id": 7,
+                "status": "operational",
+                "response_time": 120,
+                "status_code": 200,
+                "error_message": null,
+                "metadata": null,
+                "created_at": "2025-01-01T12:00:00Z",
+            })
+        );
+    }
+
+    #[test]
+    fn legacy_matches_v1_and_v2_wraps_in_data() {
+        let v1 = ApiVersion::V1.body(&monitor());
+
+        assert_eq!(ApiVersion::Legacy.body(&monitor()), v1);
+        assert_eq!(ApiVersion::V2.body(&monitor()), json!({ "data": v1 }));
+    }
+
+    #[tokio::test]
+    async fn legacy_responses_point_at_their_v1_successor() {
+        use axum::{body::Body, middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .nest(
+                "/api/v1",
+                Router::new()
+                    .route("/monitors/{id}", get(|| async { "ok" }))
+                    .layer(middleware::from_fn_with_state(ApiVersion::V1, version_layer)),
+            )
+            .nest(
+                "/api",
+                Router::new()
+                    .route("/monitors/{id}", get(|| async { "ok" }))
+                    .layer(middleware::from_fn_with_state(ApiVersion::Legacy, version_layer)),
+            );
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let legacy = app.clone().oneshot(get("/api/monitors/7")).await.unwrap();
+        assert_eq!(legacy.headers()[DEPRECATION], LEGACY_DEPRECATED_AT);
+        assert_eq!(legacy.headers()[SUNSET], LEGACY_SUNSET);
+        assert_eq!(
+            legacy.headers()[header::LINK],
+            "</api/v1/monitors/7>; rel=\"successor-version\""
+        );
+
+        let v1 = app.oneshot(get("/api/v1/monitors/7")).await.unwrap();
+        assert!(v1.headers().get(DEPRECATION).is_none());
+    }
+}
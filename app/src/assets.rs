@@ -0,0 +1,285 @@
+use axum::{
+    http::{header, HeaderValue, Response},
+    Router,
+};
+use axum_inertia::{vite, InertiaConfig};
+use config_core::{AppMode, ServerConfig};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tower::ServiceBuilder;
+use tower_http::services::ServeDir;
+use tower_http::set_header::SetResponseHeaderLayer;
+
+/// Entry point of the frontend, as named in the Vite manifest
+const MAIN: &str = "src/main.tsx";
+/// Entry point of the API reference page
+pub const API_DOCS: &str = "src/api-docs.ts";
+
+/// Built asset names change with their content, so a cached copy never goes stale
+const CACHE_FOREVER: HeaderValue = HeaderValue::from_static("public, max-age=31536000, immutable");
+
+/// The page layout for the configured mode. In production the Inertia asset
+/// version is a hash of the manifest, so browsers reload after a deploy.
+pub fn inertia_config(server: &ServerConfig) -> Result<InertiaConfig, String> {
+    let config = match server.mode {
+        AppMode::Development => vite::Development::default()
+            .port(server.vite_port)
+            .main(MAIN)
+            .lang("en")
+            .title("RustGenie")
+            .react()
+            .into_config(),
+        AppMode::Production => {
+            let manifest = server.manifest_path();
+            // axum_inertia's production layout only links the entry's own
+            // stylesheets, so it is used for the version alone
+            let version = vite::Production::new(&manifest.to_string_lossy(), MAIN)
+                .map_err(|e| format!("Failed to load {}: {}", manifest.display(), e))?
+                .into_config()
+                .version();
+            let tags = entry_tags(server, MAIN)?;
+            InertiaConfig::new(version, Box::new(move |props| production_layout(&tags, &props)))
+        }
+    };
+    Ok(config)
+}
+
+/// The same page as `vite::Production` renders, with all of the entry's tags
+fn production_layout(tags: &str, props: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html><html lang="en"><head><title>RustGenie</title><meta charset="utf-8"><meta name="viewport" content="width=device-width, initial-scale=1.0">{}</head><body><div id="app" data-page="{}"></div></body></html>"#,
+        tags,
+        escape_attribute(props)
+    )
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Script and stylesheet tags loading a Vite entry on a page outside
+/// Inertia, from the dev server or the production manifest
+pub fn entry_tags(server: &ServerConfig, entry: &str) -> Result<String, String> {
+    match server.mode {
+        AppMode::Development => {
+            let origin = format!("http://localhost:{}", server.vite_port);
+            Ok(format!(
+                r#"<script type="module" src="{origin}/@vite/client"></script><script type="module" src="{origin}/{entry}"></script>"#
+            ))
+        }
+        AppMode::Production => {
+            let manifest = server.manifest_path();
+            let read = || -> Result<String, String> {
+                let json = std::fs::read_to_string(&manifest).map_err(|e| e.to_string())?;
+                let chunks: HashMap<String, ManifestChunk> = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+                production_tags(&chunks, entry)
+            };
+            read().map_err(|e| format!("Failed to load {}: {}", manifest.display(), e))
+        }
+    }
+}
+
+/// Stylesheets of the entry and every chunk it imports, which Vite lists on
+/// the chunk that pulled them in, then preloads of the imported chunks and
+/// the entry script. Dynamic imports are left for the browser to fetch.
+fn production_tags(chunks: &HashMap<String, ManifestChunk>, entry: &str) -> Result<String, String> {
+    let mut order = Vec::new();
+    let mut pending = vec![entry];
+    while let Some(key) = pending.pop() {
+        if order.contains(&key) {
+            continue;
+        }
+        let chunk = chunks.get(key).ok_or_else(|| format!("no entry for {}", key))?;
+        order.push(key);
+        pending.extend(chunk.imports.iter().rev().map(String::as_str));
+    }
+
+    let mut css: Vec<&str> = Vec::new();
+    for key in &order {
+        for file in &chunks[*key].css {
+            if !css.contains(&file.as_str()) {
+                css.push(file);
+            }
+        }
+    }
+
+    let mut tags = String::new();
+    for file in css {
+        tags.push_str(&format!(r#"<link rel="stylesheet" href="/{}">"#, file));
+    }
+    for key in &order[1..] {
+        tags.push_str(&format!(r#"<link rel="modulepreload" href="/{}">"#, chunks[*key].file));
+    }
+    tags.push_str(&format!(r#"<script type="module" src="/{}"></script>"#, chunks[entry].file));
+    Ok(tags)
+}
+
+#[derive(Deserialize)]
+struct ManifestChunk {
+    file: String,
+    #[serde(default)]
+    css: Vec<String>,
+    #[serde(default)]
+    imports: Vec<String>,
+}
+
+/// Serves `/assets` from the build output. In production, found files are
+/// cached for good and the gzip and brotli variants written by `npm run build`
+/// go to clients that accept them.
+pub fn router<S>(server: &ServerConfig) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let dir = ServeDir::new(server.dist_dir.join("assets"));
+
+    match server.mode {
+        AppMode::Development => Router::new().nest_service("/assets", dir),
+        AppMode::Production => Router::new().nest_service(
+            "/assets",
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::overriding(header::CACHE_CONTROL, cache_if_found))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::VARY,
+                    HeaderValue::from_static("accept-encoding"),
+                ))
+                .service(dir.precompressed_br().precompressed_gzip()),
+        ),
+    }
+}
+
+// A 404 may be a file the next deploy adds, so only hits are cached
+fn cache_if_found<B>(response: &Response<B>) -> Option<HeaderValue> {
+    response.status().is_success().then_some(CACHE_FOREVER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use std::fs;
+    use tower::ServiceExt;
+
+    /// What `vite build` writes to `dist/.vite/manifest.json` for this app:
+    /// entries import shared `_` chunks, which carry their own stylesheets,
+    /// and lazily loaded modules and fonts get entries of their own
+    const MANIFEST: &str = r#"{
+  "_preload-helper-D6kgxu3v.js": {
+    "file": "assets/preload-helper-D6kgxu3v.js",
+    "name": "preload-helper"
+  },
+  "_ui-DkF3a9Qe.js": {
+    "file": "assets/ui-DkF3a9Qe.js",
+    "name": "ui",
+    "imports": [
+      "_preload-helper-D6kgxu3v.js"
+    ],
+    "css": [
+      "assets/ui-B8vN3qTe.css"
+    ]
+  },
+  "node_modules/.pnpm/@scalar+api-reference@1.28.8/node_modules/@scalar/api-reference/dist/highlight.js": {
+    "file": "assets/highlight-Bq1vT5mx.js",
+    "name": "highlight",
+    "src": "node_modules/.pnpm/@scalar+api-reference@1.28.8/node_modules/@scalar/api-reference/dist/highlight.js",
+    "isDynamicEntry": true,
+    "imports": [
+      "_preload-helper-D6kgxu3v.js"
+    ],
+    "css": [
+      "assets/highlight-Cw2pZ8dR.css"
+    ]
+  },
+  "node_modules/.pnpm/@fontsource+inter@5.1.0/node_modules/@fontsource/inter/files/inter-latin-400-normal.woff2": {
+    "file": "assets/inter-latin-400-normal-C38fXH4l.woff2",
+    "src": "node_modules/.pnpm/@fontsource+inter@5.1.0/node_modules/@fontsource/inter/files/inter-latin-400-normal.woff2"
+  },
+  "src/api-docs.ts": {
+    "file": "assets/api-docs-C3nJk2Lp.js",
+    "name": "api-docs",
+    "src": "src/api-docs.ts",
+    "isEntry": true,
+    "imports": [
+      "_preload-helper-D6kgxu3v.js"
+    ],
+    "dynamicImports": [
+      "node_modules/.pnpm/@scalar+api-reference@1.28.8/node_modules/@scalar/api-reference/dist/highlight.js"
+    ],
+    "css": [
+      "assets/api-docs-BfGh12kL.css"
+    ]
+  },
+  "src/main.tsx": {
+    "file": "assets/main-Dq7wR0sT.js",
+    "name": "main",
+    "src": "src/main.tsx",
+    "isEntry": true,
+    "imports": [
+      "_ui-DkF3a9Qe.js",
+      "_preload-helper-D6kgxu3v.js"
+    ],
+    "css": [
+      "assets/main-CbXk9m2A.css"
+    ],
+    "assets": [
+      "assets/inter-latin-400-normal-C38fXH4l.woff2"
+    ]
+  }
+}"#;
+
+    #[tokio::test]
+    async fn production_serves_built_assets_precompressed_and_cached() {
+        let dist = std::env::temp_dir().join(format!("rustgenie-dist-{}", std::process::id()));
+        fs::create_dir_all(dist.join(".vite")).unwrap();
+        fs::create_dir_all(dist.join("assets")).unwrap();
+        fs::write(dist.join(".vite/manifest.json"), MANIFEST).unwrap();
+        fs::write(dist.join("assets/main-Dq7wR0sT.js"), "console.log('hi')").unwrap();
+        fs::write(dist.join("assets/main-Dq7wR0sT.js.br"), "brotli bytes").unwrap();
+
+        let server = ServerConfig {
+            mode: AppMode::Production,
+            dist_dir: dist.clone(),
+            ..ServerConfig::default()
+        };
+        let inertia = inertia_config(&server).unwrap();
+        assert!(inertia.version().is_some());
+        let layout = (inertia.layout())(r#"{"props":{"name":"R&D <API>"}}"#.to_string());
+        assert!(layout.contains(concat!(
+            r#"<link rel="stylesheet" href="/assets/main-CbXk9m2A.css">"#,
+            r#"<link rel="stylesheet" href="/assets/ui-B8vN3qTe.css">"#,
+            r#"<link rel="modulepreload" href="/assets/ui-DkF3a9Qe.js">"#,
+            r#"<link rel="modulepreload" href="/assets/preload-helper-D6kgxu3v.js">"#,
+            r#"<script type="module" src="/assets/main-Dq7wR0sT.js"></script>"#,
+        )));
+        assert!(layout.contains(r#"<div id="app" data-page="{&quot;props&quot;:{&quot;name&quot;:&quot;R&amp;D &lt;API&gt;&quot;}}"></div>"#));
+
+        // Shared chunks are linked once, and lazily loaded chunks not at all
+        let docs = entry_tags(&server, API_DOCS).unwrap();
+        assert_eq!(docs, concat!(
+            r#"<link rel="stylesheet" href="/assets/api-docs-BfGh12kL.css">"#,
+            r#"<link rel="modulepreload" href="/assets/preload-helper-D6kgxu3v.js">"#,
+            r#"<script type="module" src="/assets/api-docs-C3nJk2Lp.js"></script>"#,
+        ));
+
+        let app: Router = router(&server);
+        let request = Request::builder()
+            .uri("/assets/main-Dq7wR0sT.js")
+            .header(header::ACCEPT_ENCODING, "gzip, br")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], CACHE_FOREVER);
+
+        let request = Request::builder().uri("/assets/main-old.js").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(header::CACHE_CONTROL).is_none());
+
+        fs::remove_dir_all(dist).unwrap();
+    }
+}
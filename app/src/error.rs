@@ -0,0 +1,197 @@
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use db_core::error::DbError;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::json;
+use thiserror::Error;
+use tracing::error;
+
+use crate::services::worker::job_queue::WorkerError;
+use crate::validation::{FieldError, ValidationErrors};
+
+/// Error returned by the JSON API. Every variant renders as
+/// `{ "error": { "status", "code", "message", "details"? } }`.
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("Authentication required")]
+    Unauthorized,
+
+    #[error("Resource not found")]
+    NotFound,
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("Validation failed")]
+    Validation(Vec<FieldError>),
+
+    /// The message is logged but never sent to the client
+    #[error("Internal server error")]
+    Internal(String),
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ErrorBody<'a> {
+    status: u16,
+    code: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<&'a [FieldError]>,
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::NotFound => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::Unauthorized => "unauthorized",
+            ApiError::NotFound => "not_found",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::Validation(_) => "validation_failed",
+            ApiError::Internal(_) => "internal_error",
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if let ApiError::Internal(message) = &self {
+            error!("Internal API error: {}", message);
+        }
+
+        let status = self.status();
+        let details = match &self {
+            ApiError::Validation(fields) => Some(fields.as_slice()),
+            _ => None,
+        };
+        let body = ErrorBody {
+            status: status.as_u16(),
+            code: self.code(),
+            message: self.to_string(),
+            details,
+        };
+
+        (status, Json(json!({ "error": body }))).into_response()
+    }
+}
+
+impl From<DbError> for ApiError {
+    fn from(err: DbError) -> Self {
+        match err {
+            DbError::NotFound => ApiError::NotFound,
+            DbError::Conflict(constraint) => {
+                ApiError::Conflict(format!("A resource with the same unique value already exists ({})", constraint))
+            }
+            DbError::InvalidInput(message) => ApiError::BadRequest(message),
+            other => ApiError::Internal(other.to_string()),
+        }
+    }
+}
+
+impl From<WorkerError> for ApiError {
+    fn from(err: WorkerError) -> Self {
+        match err {
+            WorkerError::InvalidPage(message) => ApiError::BadRequest(message),
+            other => ApiError::Internal(other.to_string()),
+        }
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        ApiError::Validation(errors.into_inner())
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            // Well-formed JSON with the wrong shape is a validation problem, not a syntax one
+            JsonRejection::JsonDataError(e) => ApiError::Validation(vec![FieldError::new(
+                "body",
+                "invalid",
+                e.body_text(),
+            )]),
+            other => ApiError::BadRequest(other.body_text()),
+        }
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        ApiError::BadRequest(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::BadRequest(rejection.body_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use serde_json::Value;
+
+    async fn render(error: ApiError) -> (StatusCode, Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn database_errors_map_to_http_statuses() {
+        let (status, body) = render(DbError::NotFound.into()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["code"], "not_found");
+        assert_eq!(body["error"]["status"], 404);
+
+        let (status, body) = render(DbError::Conflict("idx_monitors_name".to_string()).into()).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "conflict");
+        assert!(body["error"]["message"].as_str().unwrap().contains("idx_monitors_name"));
+
+        // Query errors carry SQL and connection details, which stay in the log
+        let (status, body) = render(DbError::QueryError("relation \"monitors\" does not exist".to_string()).into()).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"]["message"], "Internal server error");
+    }
+
+    #[tokio::test]
+    async fn validation_errors_list_each_field() {
+        let mut errors = ValidationErrors::default();
+        errors.add("name", "required", "name is required");
+        errors.add("interval_seconds", "out_of_range", "must be at least 10");
+
+        let (status, body) = render(errors.into()).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"]["code"], "validation_failed");
+        assert_eq!(body["error"]["details"][0]["field"], "name");
+        assert_eq!(body["error"]["details"][1]["code"], "out_of_range");
+
+        // Other errors have no details at all
+        let (_, body) = render(ApiError::NotFound).await;
+        assert!(body["error"].get("details").is_none());
+    }
+}
@@ -1,12 +1,26 @@
-use axum::{Router, extract::FromRef};
-use axum_inertia::{vite, InertiaConfig};
+use axum::{Extension, Router, ServiceExt, extract::{FromRef, Request}, middleware};
+use axum_inertia::InertiaConfig;
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
-use tower_http::services::ServeDir;
+use tower::Layer;
 use std::sync::Arc;
+use tracing::info;
 
+use api_version::ApiVersion;
+
+mod api_version;
+mod assets;
+mod error;
+mod metrics;
+mod openapi;
+mod pagination;
 mod routes;
 mod services;
+mod ssr;
+#[cfg(test)]
+mod test_support;
+mod trace;
+mod validation;
 
 // Define a combined application state
 #[derive(Clone)]
@@ -14,6 +28,8 @@ struct AppState {
     db_pool: db_core::DbPool,
     inertia: InertiaConfig,
     worker_service: Arc<services::worker::WorkerService>,
+    status_stream: Arc<services::stream_service::StatusStream>,
+    http_metrics: metrics::HttpMetrics,
 }
 
 // Implement FromRef for DbPool
@@ -30,70 +46,130 @@ impl FromRef<AppState> for InertiaConfig {
     }
 }
 
+// Implement FromRef for WorkerService
+impl FromRef<AppState> for Arc<services::worker::WorkerService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.worker_service.clone()
+    }
+}
+
+// Implement FromRef for the live status stream
+impl FromRef<AppState> for Arc<services::stream_service::StatusStream> {
+    fn from_ref(state: &AppState) -> Self {
+        state.status_stream.clone()
+    }
+}
+
+// Implement FromRef for the request latency metrics
+impl FromRef<AppState> for metrics::HttpMetrics {
+    fn from_ref(state: &AppState) -> Self {
+        state.http_metrics.clone()
+    }
+}
+
 #[tokio::main]
 async fn main() {
-    // Load environment variables
-    dotenv::dotenv().ok();
-    
+    // `rustgenie config check` prints the effective configuration and exits
+    if let Some(code) = config_core::run_command(std::env::args().skip(1)) {
+        std::process::exit(code);
+    }
+
+    // Read config.toml and the environment, refusing to start if invalid
+    let config = config_core::Config::load_or_exit();
+
+    // Log to stdout and, when configured, export traces over OTLP
+    let telemetry = telemetry::init("rustgenie");
+
     // Initialize database connection pool
-    let db_pool = db_core::init_pool().await.expect("Failed to initialize database pool");
+    let db_pool = db_core::init_pool(&config.database)
+        .await
+        .expect("Failed to initialize database pool");
     
     // Initialize worker service
     let worker_service = services::worker::WorkerService::new(db_pool.clone())
         .await
         .expect("Failed to initialize worker service");
     
-    // Configure Inertia for development
-    let inertia = vite::Development::default()
-        .port(5173)
-        .main("src/main.tsx")
-        .lang("en")
-        .title("RustGenie")
-        .react()
-        .into_config();
+    // Load pages from the Vite dev server or the production build
+    let inertia = assets::inertia_config(&config.server).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    // The API reference loads its own bundle, from the same place as pages
+    let api_docs = routes::api_docs::router(&config.server).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
 
     // Create combined app state
     let app_state = AppState {
         db_pool: db_pool.clone(),
         inertia,
         worker_service: Arc::new(worker_service),
+        status_stream: services::stream_service::StatusStream::start(db_pool.clone()),
+        http_metrics: metrics::HttpMetrics::default(),
     };
 
     // Create auth router with all middleware built-in
-    let auth_middleware_router = routes::auth::create_auth_router::<AppState>()
+    let auth_middleware_router = routes::auth::create_auth_router::<AppState>(&config)
         .await
         .expect("Failed to create auth router");
+
+    // Sessions wrap every route so any handler can resolve the current user
+    let session_layer = routes::auth::create_session_layer(&db_pool, &config)
+        .await
+        .expect("Failed to create session layer");
     
     // Create main router with combined state
     let app = Router::new()
-        .nest_service(
-            "/assets",
-            ServeDir::new("dist/assets"),
-        )
+        .merge(assets::router(&config.server))
         .merge(routes::home::router())
+        .merge(routes::dashboard::router())
+        .merge(routes::metrics::router())
+        .nest("/api/v1", routes::api::router(ApiVersion::V1))
+        .nest("/api/v2", routes::api::router(ApiVersion::V2))
+        .nest("/api", routes::api::router(ApiVersion::Legacy))
+        .merge(api_docs)
+        .merge(routes::audit::router())
+        .merge(routes::badges::router())
         .merge(routes::jobs::router())
         .merge(routes::monitors::router())
+        .merge(routes::slos::router())
         .merge(routes::status::router())
+        .merge(routes::status_pages::router())
+        .merge(routes::status_feeds::router(db_pool.clone(), &config.server.app_url))
+        .merge(routes::statuspage_api::router(db_pool.clone(), &config.server.app_url))
         .merge(auth_middleware_router)
-        .with_state(app_state);
+        .layer(middleware::from_fn_with_state(app_state.http_metrics.clone(), metrics::track_requests))
+        .layer(middleware::from_fn(trace::trace_requests))
+        .layer(session_layer)
+        .layer(Extension(services::audit_service::TrustedProxies(config.server.trusted_proxies.as_slice().into())));
+
+    // Render configured pages on the server, falling back to the browser
+    let app = if config.ssr.enabled {
+        let renderer = ssr::SsrRenderer::new(&config.ssr).expect("Failed to create SSR client");
+        info!("Rendering {} on the SSR server at {}", config.ssr.pages.join(", "), config.ssr.url);
+        app.layer(middleware::from_fn_with_state(Arc::new(renderer), ssr::render_pages))
+    } else {
+        app
+    };
+    let app = app.with_state(app_state);
+
+    // Serve status pages on their custom domains. This has to run before
+    // routing, so it wraps the router instead of being one of its layers.
+    let custom_domains = routes::status_pages::CustomDomains::new(db_pool.clone(), &config.server.app_url);
+    let app = middleware::from_fn_with_state(custom_domains, routes::status_pages::serve_custom_domains).layer(app);
 
     // Start server
-    let addr = SocketAddr::from(([127, 0, 0, 1], 8000));
-    println!("Server started at http://{}", addr);
-    println!("Auth routes available:");
-    println!("  - /auth/login (login page)");
-    println!("  - /auth/signin (start OIDC flow - redirects to provider)");
-    println!("  - /auth/protected (requires login - will redirect to OIDC provider)");
-    println!("  - /auth/maybe-protected (optional login)");
-    println!("  - /auth/callback (OIDC callback endpoint)");
-    println!("  - /auth/logout");
-    println!();
-    println!("Make sure to set these environment variables:");
-    println!("  - OIDC_ISSUER_URL");
-    println!("  - OIDC_CLIENT_ID");
-    println!("  - OIDC_CLIENT_SECRET");
-    println!("  - OIDC_REDIRECT_URL (should be: http://localhost:8000/auth/callback)");
-    
+    let addr = config.server.bind;
+    info!("Server started at http://{} ({})", addr, config.server.app_url);
+    info!("Auth routes: /auth/login, /auth/signin, /auth/protected, /auth/maybe-protected, /auth/callback, /auth/logout");
+
     let listener = TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, ServiceExt::<Request>::into_make_service_with_connect_info::<SocketAddr>(app))
+        .await
+        .unwrap();
+
+    telemetry.shutdown();
 }
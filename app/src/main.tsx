@@ -1,4 +1,4 @@
-import { createRoot } from 'react-dom/client';
+import { createRoot, hydrateRoot } from 'react-dom/client';
 import { createInertiaApp } from '@inertiajs/react';
 import './styles.css';
 
@@ -8,6 +8,11 @@ createInertiaApp({
     return pages[`./views/pages/${name}.tsx`];
   },
   setup({ el, App, props }) {
-    createRoot(el).render(<App {...props} />);
+    // Pages rendered by the SSR server only need their event handlers attached
+    if (el.hasAttribute('data-server-rendered')) {
+      hydrateRoot(el, <App {...props} />);
+    } else {
+      createRoot(el).render(<App {...props} />);
+    }
   },
 });
@@ -0,0 +1,102 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::Registry;
+use tokio::time::Instant;
+
+/// Requests that matched no route share one label, so scanners probing random
+/// paths can't grow the series count
+const UNMATCHED_PATH: &str = "unmatched";
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RequestLabels {
+    method: String,
+    /// The route template, e.g. `/api/v1/monitors/{id}`
+    path: String,
+    status: u16,
+}
+
+type LatencyFamily = Family<RequestLabels, Histogram, fn() -> Histogram>;
+
+/// Latency of every request the app serves. Cheap to clone; clones share
+/// the same histograms.
+#[derive(Clone, Debug)]
+pub struct HttpMetrics {
+    latency: LatencyFamily,
+}
+
+impl Default for HttpMetrics {
+    fn default() -> Self {
+        // 5ms up to ~10s
+        let latency: LatencyFamily = Family::new_with_constructor(|| Histogram::new(exponential_buckets(0.005, 2.0, 12)));
+        Self { latency }
+    }
+}
+
+impl HttpMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "http_request_duration_seconds",
+            "Time taken to serve HTTP requests",
+            self.latency.clone(),
+        );
+    }
+}
+
+/// Middleware for the whole app: times each request by method, route and
+/// response status
+pub async fn track_requests(State(metrics): State<HttpMetrics>, request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED_PATH, |path| path.as_str())
+        .to_string();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    let labels = RequestLabels {
+        method,
+        path,
+        status: response.status().as_u16(),
+    };
+    metrics.latency.get_or_create(&labels).observe(start.elapsed().as_secs_f64());
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use prometheus_client::encoding::text::encode;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn requests_are_timed_by_route_template() {
+        let metrics = HttpMetrics::default();
+        let app = Router::new()
+            .route("/monitors/{id}", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(metrics.clone(), track_requests));
+
+        for uri in ["/monitors/1", "/monitors/2", "/nope"] {
+            let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+
+        let mut registry = Registry::default();
+        metrics.register(&mut registry);
+        let mut text = String::new();
+        encode(&mut text, &registry).unwrap();
+
+        assert!(text.contains(r#"http_request_duration_seconds_count{method="GET",path="/monitors/{id}",status="200"} 2"#));
+        assert!(text.contains(r#"http_request_duration_seconds_count{method="GET",path="unmatched",status="404"} 1"#));
+        assert!(!text.contains("/monitors/1"));
+    }
+}
@@ -0,0 +1,615 @@
+//! OpenAPI 3.1 document for v1 of the JSON API.
+//!
+//! Schemas are derived from the request, response and query types with
+//! `schemars`, so they follow serde renames and optional fields. Operations
+//! are listed here by hand; `every_api_route_is_documented` fails when an
+//! `api_router` gains a route that has no entry below.
+
+use db_core::models::badge_token::BadgeToken;
+use db_core::models::document::Document;
+use db_core::models::incident::{CreateIncident, Incident, UpdateIncident};
+use db_core::models::maintenance_window::{CreateMaintenanceWindow, MaintenanceWindow, UpdateMaintenanceWindow};
+use db_core::models::monitor::{CreateMonitor, Monitor, UpdateMonitor};
+use db_core::models::service_group::{CreateServiceGroup, ServiceGroup, UpdateServiceGroup};
+use db_core::models::slo::{CreateSlo, Slo, SloStatus, UpdateSlo};
+use db_core::models::status_event::{CreateStatusEvent, StatusEvent};
+use db_core::models::status_page::{CreateStatusPage, StatusPage, UpdateStatusPage};
+use db_core::pagination::Page;
+use monitor_checks::CheckOutcome;
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+
+use crate::api_version::ApiVersion;
+use crate::error::ErrorBody;
+use crate::routes::audit::AuditLogQuery;
+use crate::routes::badges::{BadgePrivacy, IssuedBadgeToken};
+use crate::routes::documents::DocumentListQuery;
+use crate::routes::jobs::{EmailRequest, JobListQuery, JobResponse};
+use crate::routes::maintenance::MaintenanceListQuery;
+use crate::routes::service_groups::ServiceGroupListQuery;
+use crate::routes::slos::SloListQuery;
+use crate::routes::status::{
+    CheckQuery, EventExportQuery, EventListQuery, HeartbeatRequest, HeartbeatResponse, IncidentListQuery,
+    MonitorListQuery, StatsQuery,
+};
+use crate::routes::status_pages::StatusPageListQuery;
+use crate::routes::stream::StatusStreamQuery;
+use crate::services::audit_service::AuditLogPage;
+use crate::services::check_service::QueuedCheck;
+use crate::services::monitor_service::MonitorStats;
+use crate::services::worker::{JobRecord, RescheduleJob};
+
+const ERROR_SCHEMA: &str = "#/components/schemas/ApiError";
+
+/// Build the complete OpenAPI document
+pub fn document() -> Value {
+    let mut spec = Spec::new();
+
+    // Monitors
+    let op = Operation::new("listMonitors", "List monitors", "monitors")
+        .query::<MonitorListQuery>()
+        .json_response(200, "A page of monitors", spec.schema::<Page<Monitor>>());
+    spec.add("get", "/monitors", op);
+
+    let op = Operation::new("createMonitor", "Create a monitor", "monitors")
+        .json_body(spec.schema::<CreateMonitor>())
+        .json_response(201, "The created monitor", spec.schema::<Monitor>());
+    spec.add("post", "/monitors", op);
+
+    let op = Operation::new("getMonitor", "Get a monitor", "monitors")
+        .path_param("id", "Monitor ID")
+        .json_response(200, "The monitor", spec.schema::<Monitor>());
+    spec.add("get", "/monitors/{id}", op);
+
+    let op = Operation::new("updateMonitor", "Update a monitor", "monitors")
+        .path_param("id", "Monitor ID")
+        .json_body(spec.schema::<UpdateMonitor>())
+        .json_response(200, "The updated monitor", spec.schema::<Monitor>());
+    spec.add("put", "/monitors/{id}", op);
+
+    let op = Operation::new("deleteMonitor", "Delete a monitor", "monitors")
+        .path_param("id", "Monitor ID")
+        .empty_response(204, "The monitor was deleted");
+    spec.add("delete", "/monitors/{id}", op);
+
+    let op = Operation::new("checkMonitor", "Check a monitor now", "monitors")
+        .path_param("id", "Monitor ID")
+        .query::<CheckQuery>()
+        .json_response(200, "The status event recorded by the check", spec.schema::<StatusEvent>())
+        .json_response(202, "The check was queued and has not finished yet", spec.schema::<QueuedCheck>());
+    spec.add("post", "/monitors/{id}/check", op);
+
+    let op = Operation::new("dryRunMonitorCheck", "Check an unsaved monitor config", "monitors")
+        .signed_in()
+        .json_body(spec.schema::<CreateMonitor>())
+        .json_response(200, "Result of the check; nothing is recorded", spec.schema::<CheckOutcome>());
+    spec.add("post", "/monitors/check", op);
+
+    // Service groups
+    let op = Operation::new("listServiceGroups", "List service groups in display order", "service-groups")
+        .query::<ServiceGroupListQuery>()
+        .json_response(200, "A page of service groups", spec.schema::<Page<ServiceGroup>>());
+    spec.add("get", "/service-groups", op);
+
+    let op = Operation::new("createServiceGroup", "Create a service group", "service-groups")
+        .signed_in()
+        .json_body(spec.schema::<CreateServiceGroup>())
+        .json_response(201, "The created service group", spec.schema::<ServiceGroup>());
+    spec.add("post", "/service-groups", op);
+
+    let op = Operation::new("getServiceGroup", "Get a service group", "service-groups")
+        .path_param("id", "Service group ID")
+        .json_response(200, "The service group", spec.schema::<ServiceGroup>());
+    spec.add("get", "/service-groups/{id}", op);
+
+    let op = Operation::new("updateServiceGroup", "Rename, reorder or move a service group", "service-groups")
+        .signed_in()
+        .path_param("id", "Service group ID")
+        .json_body(spec.schema::<UpdateServiceGroup>())
+        .json_response(200, "The updated service group", spec.schema::<ServiceGroup>());
+    spec.add("put", "/service-groups/{id}", op);
+
+    let op = Operation::new("deleteServiceGroup", "Delete a service group; its monitors and subgroups are kept", "service-groups")
+        .signed_in()
+        .path_param("id", "Service group ID")
+        .empty_response(204, "The service group was deleted");
+    spec.add("delete", "/service-groups/{id}", op);
+
+    // SLOs
+    let op = Operation::new("listSlos", "List SLOs by name", "slos")
+        .query::<SloListQuery>()
+        .json_response(200, "A page of SLOs", spec.schema::<Page<Slo>>());
+    spec.add("get", "/slos", op);
+
+    let op = Operation::new("createSlo", "Create an SLO for a monitor or a service group", "slos")
+        .signed_in()
+        .json_body(spec.schema::<CreateSlo>())
+        .json_response(201, "The created SLO", spec.schema::<Slo>());
+    spec.add("post", "/slos", op);
+
+    let op = Operation::new("getSlo", "Get an SLO", "slos")
+        .path_param("id", "SLO ID")
+        .json_response(200, "The SLO", spec.schema::<Slo>());
+    spec.add("get", "/slos/{id}", op);
+
+    let op = Operation::new("updateSlo", "Change an SLO's target, window, latency objective or alerts", "slos")
+        .signed_in()
+        .path_param("id", "SLO ID")
+        .json_body(spec.schema::<UpdateSlo>())
+        .json_response(200, "The updated SLO", spec.schema::<Slo>());
+    spec.add("put", "/slos/{id}", op);
+
+    let op = Operation::new("deleteSlo", "Delete an SLO", "slos")
+        .signed_in()
+        .path_param("id", "SLO ID")
+        .empty_response(204, "The SLO was deleted");
+    spec.add("delete", "/slos/{id}", op);
+
+    let op = Operation::new("getSloStatus", "Get an SLO's error budget and burn rates", "slos")
+        .path_param("id", "SLO ID")
+        .json_response(200, "Where the SLO stands over its window", spec.schema::<SloStatus>());
+    spec.add("get", "/slos/{id}/status", op);
+
+    // Badge tokens
+    let op = Operation::new("setMonitorBadgePrivacy", "Make a monitor's badge private or public", "badges")
+        .signed_in()
+        .path_param("id", "Monitor ID")
+        .json_body(spec.schema::<BadgePrivacy>())
+        .json_response(200, "Whether the badge is private", spec.schema::<BadgeToken>());
+    spec.add("put", "/monitors/{id}/badge", op);
+
+    let op = Operation::new("issueMonitorBadgeToken", "Make a monitor's badge private with a new token", "badges")
+        .signed_in()
+        .path_param("id", "Monitor ID")
+        .json_response(201, "The token and the badge URL that carries it", spec.schema::<IssuedBadgeToken>());
+    spec.add("post", "/monitors/{id}/badge-token", op);
+
+    let op = Operation::new("revokeMonitorBadgeToken", "Stop a monitor's badge token working; the badge stays private", "badges")
+        .signed_in()
+        .path_param("id", "Monitor ID")
+        .empty_response(204, "The token was revoked");
+    spec.add("delete", "/monitors/{id}/badge-token", op);
+
+    let op = Operation::new("setServiceGroupBadgePrivacy", "Make a service group's badge private or public", "badges")
+        .signed_in()
+        .path_param("id", "Service group ID")
+        .json_body(spec.schema::<BadgePrivacy>())
+        .json_response(200, "Whether the badge is private", spec.schema::<BadgeToken>());
+    spec.add("put", "/service-groups/{id}/badge", op);
+
+    let op = Operation::new("issueServiceGroupBadgeToken", "Make a service group's badge private with a new token", "badges")
+        .signed_in()
+        .path_param("id", "Service group ID")
+        .json_response(201, "The token and the badge URL that carries it", spec.schema::<IssuedBadgeToken>());
+    spec.add("post", "/service-groups/{id}/badge-token", op);
+
+    let op = Operation::new("revokeServiceGroupBadgeToken", "Stop a service group's badge token working; the badge stays private", "badges")
+        .signed_in()
+        .path_param("id", "Service group ID")
+        .empty_response(204, "The token was revoked");
+    spec.add("delete", "/service-groups/{id}/badge-token", op);
+
+    // Status events
+    let op = Operation::new("listMonitorEvents", "List status events for a monitor", "events")
+        .path_param("id", "Monitor ID")
+        .query::<EventListQuery>()
+        .json_response(200, "A page of status events, newest first by default", spec.schema::<Page<StatusEvent>>());
+    spec.add("get", "/monitors/{id}/events", op);
+
+    let op = Operation::new("recordMonitorEvent", "Record a status event", "events")
+        .path_param("id", "Monitor ID")
+        .json_body(spec.schema::<CreateStatusEvent>())
+        .json_response(201, "The recorded event", spec.schema::<StatusEvent>());
+    spec.add("post", "/monitors/{id}/events", op);
+
+    let op = Operation::new("exportMonitorEvents", "Export status events as CSV or NDJSON", "events")
+        .path_param("id", "Monitor ID")
+        .query::<EventExportQuery>()
+        .response(200, "Streamed export file", json!({
+            "text/csv": { "schema": { "type": "string" } },
+            "application/x-ndjson": { "schema": { "type": "string" } },
+        }));
+    spec.add("get", "/monitors/{id}/events/export", op);
+
+    let op = Operation::new("getMonitorStats", "Aggregated uptime and latency for a monitor", "events")
+        .path_param("id", "Monitor ID")
+        .query::<StatsQuery>()
+        .json_response(200, "Hourly or daily buckets", spec.schema::<MonitorStats>());
+    spec.add("get", "/monitors/{id}/stats", op);
+
+    let op = Operation::new("receiveHeartbeat", "Record a heartbeat from a push monitor", "events")
+        .path_param("monitor_id", "Monitor ID")
+        .json_body(spec.schema::<HeartbeatRequest>())
+        .json_response(200, "The heartbeat was recorded", spec.schema::<HeartbeatResponse>());
+    spec.add("post", "/heartbeat/{monitor_id}", op);
+
+    // Incidents
+    let op = Operation::new("listIncidents", "List incidents", "incidents")
+        .query::<IncidentListQuery>()
+        .json_response(200, "A page of incidents", spec.schema::<Page<Incident>>());
+    spec.add("get", "/incidents", op);
+
+    let op = Operation::new("createIncident", "Open an incident", "incidents")
+        .json_body(spec.schema::<CreateIncident>())
+        .json_response(201, "The created incident", spec.schema::<Incident>());
+    spec.add("post", "/incidents", op);
+
+    let op = Operation::new("updateIncident", "Update or resolve an incident", "incidents")
+        .path_param("id", "Incident ID")
+        .json_body(spec.schema::<UpdateIncident>())
+        .json_response(200, "The updated incident", spec.schema::<Incident>());
+    spec.add("put", "/incidents/{id}", op);
+
+    let op = Operation::new("deleteIncident", "Delete an incident", "incidents")
+        .path_param("id", "Incident ID")
+        .empty_response(204, "The incident was deleted");
+    spec.add("delete", "/incidents/{id}", op);
+
+    // Maintenance
+    let op = Operation::new("listMaintenance", "List maintenance windows, latest first", "maintenance")
+        .query::<MaintenanceListQuery>()
+        .json_response(200, "A page of maintenance windows", spec.schema::<Page<MaintenanceWindow>>());
+    spec.add("get", "/maintenance", op);
+
+    let op = Operation::new("createMaintenance", "Schedule a maintenance window", "maintenance")
+        .signed_in()
+        .json_body(spec.schema::<CreateMaintenanceWindow>())
+        .json_response(201, "The created maintenance window", spec.schema::<MaintenanceWindow>());
+    spec.add("post", "/maintenance", op);
+
+    let op = Operation::new("getMaintenance", "Get a maintenance window", "maintenance")
+        .path_param("id", "Maintenance window ID")
+        .json_response(200, "The maintenance window", spec.schema::<MaintenanceWindow>());
+    spec.add("get", "/maintenance/{id}", op);
+
+    let op = Operation::new("updateMaintenance", "Reschedule or edit a maintenance window", "maintenance")
+        .signed_in()
+        .path_param("id", "Maintenance window ID")
+        .json_body(spec.schema::<UpdateMaintenanceWindow>())
+        .json_response(200, "The updated maintenance window", spec.schema::<MaintenanceWindow>());
+    spec.add("put", "/maintenance/{id}", op);
+
+    let op = Operation::new("deleteMaintenance", "Delete a maintenance window", "maintenance")
+        .signed_in()
+        .path_param("id", "Maintenance window ID")
+        .empty_response(204, "The maintenance window was deleted");
+    spec.add("delete", "/maintenance/{id}", op);
+
+    let op = Operation::new("streamStatus", "Live status events, monitor state changes and incident and maintenance changes", "events")
+        .query::<StatusStreamQuery>()
+        .response(200, "Server-Sent Events stream; resume with the Last-Event-ID header on the same app instance", json!({
+            "text/event-stream": { "schema": { "type": "string" } },
+        }));
+    spec.add("get", "/stream/status", op);
+
+    // Status pages
+    let op = Operation::new("listStatusPages", "List status pages by slug", "status-pages")
+        .query::<StatusPageListQuery>()
+        .json_response(200, "A page of status pages", spec.schema::<Page<StatusPage>>());
+    spec.add("get", "/status-pages", op);
+
+    let op = Operation::new("createStatusPage", "Create a status page", "status-pages")
+        .signed_in()
+        .json_body(spec.schema::<CreateStatusPage>())
+        .json_response(201, "The created status page", spec.schema::<StatusPage>());
+    spec.add("post", "/status-pages", op);
+
+    let op = Operation::new("getStatusPage", "Get a status page", "status-pages")
+        .path_param("id", "Status page ID")
+        .json_response(200, "The status page", spec.schema::<StatusPage>());
+    spec.add("get", "/status-pages/{id}", op);
+
+    let op = Operation::new("updateStatusPage", "Update a status page", "status-pages")
+        .signed_in()
+        .path_param("id", "Status page ID")
+        .json_body(spec.schema::<UpdateStatusPage>())
+        .json_response(200, "The updated status page", spec.schema::<StatusPage>());
+    spec.add("put", "/status-pages/{id}", op);
+
+    let op = Operation::new("deleteStatusPage", "Delete a status page", "status-pages")
+        .signed_in()
+        .path_param("id", "Status page ID")
+        .empty_response(204, "The status page was deleted");
+    spec.add("delete", "/status-pages/{id}", op);
+
+    // Everything else
+    let op = Operation::new("listAuditLog", "Search the audit log", "audit")
+        .signed_in()
+        .query::<AuditLogQuery>()
+        .json_response(200, "A page of audit log entries", spec.schema::<AuditLogPage>());
+    spec.add("get", "/audit-log", op);
+
+    let op = Operation::new("listDocuments", "List documents", "documents")
+        .query::<DocumentListQuery>()
+        .json_response(200, "A page of documents", spec.schema::<Page<Document>>());
+    spec.add("get", "/documents", op);
+
+    let op = Operation::new("queueEmail", "Queue an email job", "jobs")
+        .json_body(spec.schema::<EmailRequest>())
+        .json_response(200, "The queued job", spec.schema::<JobResponse>());
+    spec.add("post", "/jobs/email", op);
+
+    let op = Operation::new("listJobs", "List pending background jobs", "jobs")
+        .signed_in()
+        .query::<JobListQuery>()
+        .json_response(200, "A page of jobs, newest first by default", spec.schema::<Page<JobRecord>>());
+    spec.add("get", "/jobs", op);
+
+    let op = Operation::new("getJob", "Get a pending background job", "jobs")
+        .signed_in()
+        .big_path_param("id", "Job ID")
+        .json_response(200, "The job", spec.schema::<JobRecord>());
+    spec.add("get", "/jobs/{id}", op);
+
+    let op = Operation::new("retryJob", "Run a failed job again now with its attempts reset", "jobs")
+        .signed_in()
+        .big_path_param("id", "Job ID")
+        .json_response(200, "The rescheduled job", spec.schema::<JobRecord>());
+    spec.add("post", "/jobs/{id}/retry", op);
+
+    let op = Operation::new("rescheduleJob", "Change when and how a job runs", "jobs")
+        .signed_in()
+        .big_path_param("id", "Job ID")
+        .json_body(spec.schema::<RescheduleJob>())
+        .json_response(200, "The rescheduled job", spec.schema::<JobRecord>());
+    spec.add("post", "/jobs/{id}/reschedule", op);
+
+    let op = Operation::new("cancelJob", "Remove a failed job so it is never attempted again", "jobs")
+        .signed_in()
+        .big_path_param("id", "Job ID")
+        .empty_response(204, "The job was removed");
+    spec.add("post", "/jobs/{id}/cancel", op);
+
+    spec.finish()
+}
+
+struct Spec {
+    generator: SchemaGenerator,
+    paths: Map<String, Value>,
+}
+
+impl Spec {
+    fn new() -> Self {
+        let generator = SchemaSettings::draft2020_12()
+            .with(|s| s.definitions_path = "/components/schemas".into())
+            .into_generator();
+
+        Self {
+            generator,
+            paths: Map::new(),
+        }
+    }
+
+    /// Schema for `T`, registering it under `components.schemas` where possible
+    fn schema<T: JsonSchema>(&mut self) -> Value {
+        self.generator.subschema_for::<T>().to_value()
+    }
+
+    fn add(&mut self, method: &str, path: &str, operation: Operation) {
+        let item = self
+            .paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        item[method] = operation.finish();
+    }
+
+    fn finish(mut self) -> Value {
+        let error_body = self.generator.subschema_for::<ErrorBody>().to_value();
+        let mut schemas = self.generator.take_definitions(true);
+        schemas.insert(
+            "ApiError".to_string(),
+            json!({
+                "type": "object",
+                "required": ["error"],
+                "properties": { "error": error_body },
+            }),
+        );
+
+        json!({
+            "openapi": "3.1.0",
+            "info": {
+                "title": "RustGenie API",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "servers": [{ "url": ApiVersion::V1.prefix() }],
+            "paths": self.paths,
+            "components": { "schemas": schemas },
+        })
+    }
+}
+
+struct Operation {
+    value: Map<String, Value>,
+    parameters: Vec<Value>,
+    responses: Map<String, Value>,
+}
+
+impl Operation {
+    fn new(id: &str, summary: &str, tag: &str) -> Self {
+        let mut value = Map::new();
+        value.insert("operationId".to_string(), json!(id));
+        value.insert("summary".to_string(), json!(summary));
+        value.insert("tags".to_string(), json!([tag]));
+
+        Self {
+            value,
+            parameters: Vec::new(),
+            responses: Map::new(),
+        }
+    }
+
+    /// Only a signed-in session may call the operation; others get a 401
+    fn signed_in(mut self) -> Self {
+        self.value.insert(
+            "description".to_string(),
+            json!("Requires a signed-in session; other requests get a 401 `unauthorized` error."),
+        );
+        self
+    }
+
+    fn path_param(mut self, name: &str, description: &str) -> Self {
+        self.parameters.push(json!({
+            "name": name,
+            "in": "path",
+            "required": true,
+            "description": description,
+            "schema": { "type": "integer", "format": "int32" },
+        }));
+        self
+    }
+
+    /// A path parameter for a `bigint` key, such as a job ID
+    fn big_path_param(mut self, name: &str, description: &str) -> Self {
+        self.parameters.push(json!({
+            "name": name,
+            "in": "path",
+            "required": true,
+            "description": description,
+            "schema": { "type": "integer", "format": "int64" },
+        }));
+        self
+    }
+
+    /// One query parameter per field of the deserialized query struct. The
+    /// struct is inlined rather than added to `components.schemas`.
+    fn query<Q: JsonSchema>(mut self) -> Self {
+        let schema = SchemaSettings::draft2020_12()
+            .with(|s| s.inline_subschemas = true)
+            .into_generator()
+            .into_root_schema_for::<Q>()
+            .to_value();
+        let required = schema["required"].as_array().cloned().unwrap_or_default();
+
+        if let Some(properties) = schema["properties"].as_object() {
+            for (name, property) in properties {
+                self.parameters.push(json!({
+                    "name": name,
+                    "in": "query",
+                    "required": required.contains(&json!(name)),
+                    "schema": property,
+                }));
+            }
+        }
+        self
+    }
+
+    fn json_body(mut self, schema: Value) -> Self {
+        self.value.insert(
+            "requestBody".to_string(),
+            json!({
+                "required": true,
+                "content": { "application/json": { "schema": schema } },
+            }),
+        );
+        self
+    }
+
+    fn json_response(self, status: u16, description: &str, schema: Value) -> Self {
+        self.response(status, description, json!({ "application/json": { "schema": schema } }))
+    }
+
+    fn empty_response(mut self, status: u16, description: &str) -> Self {
+        self.responses
+            .insert(status.to_string(), json!({ "description": description }));
+        self
+    }
+
+    fn response(mut self, status: u16, description: &str, content: Value) -> Self {
+        self.responses.insert(
+            status.to_string(),
+            json!({ "description": description, "content": content }),
+        );
+        self
+    }
+
+    fn finish(mut self) -> Value {
+        // Every handler reports failures through ApiError
+        self.responses.insert(
+            "default".to_string(),
+            json!({
+                "description": "Error",
+                "content": { "application/json": { "schema": { "$ref": ERROR_SCHEMA } } },
+            }),
+        );
+
+        if !self.parameters.is_empty() {
+            self.value
+                .insert("parameters".to_string(), Value::Array(self.parameters));
+        }
+        self.value
+            .insert("responses".to_string(), Value::Object(self.responses));
+        Value::Object(self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// `(path, method)` for every route the API router registers. axum has
+    /// no public route listing, so this reads the router's `Debug` output:
+    /// the path each route is mounted at and its methods' `Allow` header.
+    fn registered_routes() -> Vec<(String, String)> {
+        let router = format!("{:?}", crate::routes::api::router(ApiVersion::V1));
+        let (endpoints, rest) = router.split_once("node: Node { paths: {").expect("router Debug output changed");
+        let (paths, _) = rest.split_once("fallback_router").expect("router Debug output changed");
+
+        // `RouteId(3): MethodRouter(MethodRouter { ..., allow_header: Bytes(b"POST,DELETE") })`
+        let mut methods = HashMap::new();
+        for entry in endpoints.split("RouteId(").skip(1) {
+            let Some((id, endpoint)) = entry.split_once(')') else { continue };
+            if let Some((_, allow)) = endpoint.split_once("allow_header: Bytes(b\"") {
+                methods.insert(id.to_string(), allow.split('"').next().unwrap_or_default().to_string());
+            }
+        }
+
+        // `RouteId(3): "/monitors/{id}"`
+        let mut routes = Vec::new();
+        for entry in paths.split("RouteId(").skip(1) {
+            let Some((id, path)) = entry.split_once(')') else { continue };
+            let path = path.split('"').nth(1).unwrap_or_default();
+            for method in methods.get(id).map(String::as_str).unwrap_or_default().split(',') {
+                // HEAD comes with every GET
+                if !method.is_empty() && method != "HEAD" {
+                    routes.push((path.to_string(), method.to_lowercase()));
+                }
+            }
+        }
+
+        routes
+    }
+
+    #[test]
+    fn every_api_route_is_documented() {
+        let spec = document();
+        let routes = registered_routes();
+        let known = ("/monitors/{id}".to_string(), "put".to_string());
+        assert!(routes.contains(&known), "could not read the API router's routes: {:?}", routes);
+
+        let missing: Vec<String> = routes
+            .iter()
+            .filter(|(path, method)| spec["paths"][path][method].is_null())
+            .map(|(path, method)| format!("{} {}", method.to_uppercase(), path))
+            .collect();
+
+        assert!(
+            missing.is_empty(),
+            "routes without an OpenAPI operation (add them to openapi::document): {:?}",
+            missing
+        );
+    }
+
+    #[test]
+    fn every_schema_reference_resolves() {
+        let spec = document();
+        let text = spec.to_string();
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+
+        for reference in text.split("\"$ref\":\"#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.contains_key(name), "dangling reference to {}", name);
+        }
+    }
+}
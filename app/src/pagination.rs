@@ -0,0 +1,74 @@
+use axum::{
+    http::{header, HeaderValue, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+use db_core::pagination::Page;
+use reqwest::Url;
+use serde::Serialize;
+
+/// A page of results rendered as `{ "data", "next_cursor", "has_more" }`,
+/// with a `Link: <...>; rel="next"` header when another page exists.
+/// The URI is the one the client requested, so filters and sort carry over.
+pub struct Paginated<T> {
+    pub page: Page<T>,
+    pub uri: Uri,
+}
+
+impl<T> Paginated<T> {
+    pub fn new(page: Page<T>, uri: Uri) -> Self {
+        Self { page, uri }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Paginated<T> {
+    fn into_response(self) -> Response {
+        let link = self
+            .page
+            .next_cursor
+            .as_deref()
+            .and_then(|cursor| next_link(&self.uri, cursor));
+
+        let mut response = Json(self.page).into_response();
+        if let Some(link) = link {
+            response.headers_mut().insert(header::LINK, link);
+        }
+        response
+    }
+}
+
+fn next_link(uri: &Uri, cursor: &str) -> Option<HeaderValue> {
+    // Url needs an absolute base; only the path and query are emitted
+    let mut url = Url::parse("http://localhost").ok()?.join(&uri.to_string()).ok()?;
+
+    let params: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "cursor")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(params)
+        .append_pair("cursor", cursor);
+
+    let target = format!("{}?{}", url.path(), url.query().unwrap_or_default());
+    HeaderValue::from_str(&format!("<{}>; rel=\"next\"", target)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_link_replaces_the_cursor_and_keeps_filters() {
+        let uri: Uri = "/api/monitors?monitor_type=http&cursor=old&limit=10".parse().unwrap();
+
+        let link = next_link(&uri, "abc").unwrap();
+
+        assert_eq!(
+            link.to_str().unwrap(),
+            "</api/monitors?monitor_type=http&limit=10&cursor=abc>; rel=\"next\""
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use axum::{middleware, Router};
+
+use crate::api_version::{self, ApiVersion};
+use crate::routes::{audit, badges, documents, jobs, maintenance, service_groups, slos, status, status_pages, stream};
+use crate::AppState;
+
+/// Every JSON API route, relative to a version prefix. `main` nests one of
+/// these per version, so adding a route here exposes it under all of them.
+pub fn router(version: ApiVersion) -> Router<AppState> {
+    Router::new()
+        .merge(audit::api_router())
+        .merge(badges::api_router())
+        .merge(documents::api_router())
+        .merge(jobs::api_router())
+        .merge(maintenance::api_router())
+        .merge(service_groups::api_router())
+        .merge(slos::api_router())
+        .merge(status::api_router())
+        .merge(status_pages::api_router())
+        .merge(stream::api_router())
+        .layer(middleware::from_fn_with_state(version, api_version::version_layer))
+}
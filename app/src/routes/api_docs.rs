@@ -0,0 +1,45 @@
+use axum::{
+    response::{Html, IntoResponse},
+    routing::get,
+    Json, Router,
+};
+
+use config_core::ServerConfig;
+
+use crate::{assets, openapi};
+
+/// Scalar's API reference UI, bundled by Vite from `src/api-docs.ts` and
+/// pointed at our document
+fn docs_page(tags: &str) -> String {
+    format!(
+        r#"<!doctype html>
+<html>
+  <head>
+    <title>RustGenie API</title>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+  </head>
+  <body>
+    <div id="api-reference"></div>
+    {}
+  </body>
+</html>
+"#,
+        tags
+    )
+}
+
+pub fn router<S>(server: &ServerConfig) -> Result<Router<S>, String>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let page = docs_page(&assets::entry_tags(server, assets::API_DOCS)?);
+
+    Ok(Router::new()
+        .route("/api/openapi.json", get(openapi_json))
+        .route("/api/docs", get(move || async move { Html(page) })))
+}
+
+async fn openapi_json() -> impl IntoResponse {
+    Json(openapi::document())
+}
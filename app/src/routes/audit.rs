@@ -0,0 +1,147 @@
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Redirect},
+    routing::get,
+    Router,
+};
+use axum_extra::extract::WithRejection;
+use axum_inertia::Inertia;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use time::OffsetDateTime;
+use db_core::DbPool;
+use db_core::models::audit_log::AuditLogFilter;
+use crate::api_version::ApiVersion;
+use crate::error::ApiError;
+use crate::services::audit_service::{AuditContext, AuditService};
+
+const DEFAULT_PER_PAGE: i64 = 50;
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub(crate) struct AuditLogQuery {
+    actor_type: Option<String>,
+    actor_id: Option<String>,
+    action: Option<String>,
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    from: Option<OffsetDateTime>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    to: Option<OffsetDateTime>,
+    page: Option<i64>,
+    per_page: Option<i64>,
+}
+
+impl AuditLogQuery {
+    fn filter(&self) -> AuditLogFilter {
+        AuditLogFilter {
+            actor_type: non_empty(&self.actor_type),
+            actor_id: non_empty(&self.actor_id),
+            action: non_empty(&self.action),
+            entity_type: non_empty(&self.entity_type),
+            entity_id: non_empty(&self.entity_id),
+            from: self.from,
+            to: self.to,
+        }
+    }
+}
+
+// HTML forms submit empty strings for untouched filters
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_ref().filter(|v| !v.is_empty()).cloned()
+}
+
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+    axum_inertia::InertiaConfig: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/audit-log", get(audit_log_page))
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/audit-log", get(list_audit_log))
+}
+
+async fn list_audit_log(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Query(query), _): WithRejection<Query<AuditLogQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Entries carry client addresses and whole before and after states
+    audit.require_user()?;
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+
+    let page = AuditService::list(&pool, &query.filter(), page, per_page).await?;
+    Ok(version.json(&page))
+}
+
+async fn audit_log_page(
+    State(pool): State<DbPool>,
+    audit: AuditContext,
+    Query(query): Query<AuditLogQuery>,
+    inertia: Inertia,
+) -> Result<impl IntoResponse, Redirect> {
+    if audit.require_user().is_err() {
+        return Err(Redirect::to("/auth/login"));
+    }
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+
+    Ok(match AuditService::list(&pool, &query.filter(), page, per_page).await {
+        Ok(data) => inertia.render("AuditLog", json!({
+            "entries": data.entries,
+            "total": data.total,
+            "page": data.page,
+            "perPage": data.per_page,
+            "filters": query,
+        })),
+        Err(_) => inertia.render("AuditLog", json!({
+            "entries": [],
+            "total": 0,
+            "page": 1,
+            "perPage": per_page,
+            "filters": query,
+            "error": "Failed to load audit log",
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{with_sign_in, TestState};
+
+    #[tokio::test]
+    async fn reading_the_audit_log_needs_a_session() {
+        let routes = Router::new().nest("/api", api_router()).merge(router());
+        let app = with_sign_in(routes.with_state(TestState::offline()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+
+        let response = client.get(format!("{}/api/audit-log", base)).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client.get(format!("{}/audit-log", base)).send().await.unwrap();
+        assert!(response.status().is_redirection());
+        assert_eq!(response.headers()["location"], "/auth/login");
+    }
+}
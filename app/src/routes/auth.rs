@@ -1,6 +1,6 @@
 use axum::{
     error_handling::HandleErrorLayer,
-    extract::{Query, State},
+    extract::{Extension, Query, State},
     http::Uri,
     response::{Html, IntoResponse, Redirect},
     routing::get,
@@ -26,7 +26,9 @@ use tower_sessions::{
 use tower_sessions_sqlx_store::PostgresStore;
 use axum_inertia::Inertia;
 use serde_json::json;
+use config_core::Config;
 use db_core::{DbPool, repositories::UserRepository, models::user::User};
+use tracing::{debug, error, warn};
 
 // Follow the same pattern as other routes with generic state
 pub fn router<S>() -> Router<S>
@@ -42,48 +44,25 @@ where
         .route("/auth/user", get(get_current_user))
 }
 
-pub async fn create_auth_router<S>() -> Result<Router<S>, Box<dyn std::error::Error + Send + Sync>>
+/// Where the browser lands after logging out
+#[derive(Clone)]
+struct PostLogoutRedirect(Uri);
+
+pub async fn create_auth_router<S>(config: &Config) -> Result<Router<S>, Box<dyn std::error::Error + Send + Sync>>
 where
     S: Clone + Send + Sync + 'static,
     DbPool: axum::extract::FromRef<S>,
     axum_inertia::InertiaConfig: axum::extract::FromRef<S>,
 {
-    // Get environment variables
-    let app_url = std::env::var("OIDC_REDIRECT_URL")
-        .or_else(|_| std::env::var("APP_URL"))
-        .map(|url| {
-            if url.ends_with("/auth/callback") {
-                url.trim_end_matches("/auth/callback").to_string()
-            } else {
-                url
-            }
-        })
-        .unwrap_or_else(|_| "http://localhost:8000".to_string());
-    
-    let issuer = std::env::var("OIDC_ISSUER_URL")
-        .unwrap_or_else(|_| "https://accounts.google.com".to_string());
-    
-    let client_id = std::env::var("OIDC_CLIENT_ID")
-        .unwrap_or_else(|_| "dummy_client_id".to_string());
-    
-    let client_secret = std::env::var("OIDC_CLIENT_SECRET").ok();
+    let app_url = config.server.app_url.clone();
+    let issuer = config.auth.issuer_url.clone();
+    let client_id = config.auth.client_id.clone();
+    let client_secret = config.auth.client_secret.clone();
 
-    println!("=== OIDC Configuration Debug ===");
-    println!("App URL: {}", app_url);
-    println!("Issuer: {}", issuer);
-    println!("Client ID: {}", client_id);
-    println!("Client Secret: {:?}", client_secret.as_ref().map(|_| "***"));
-    println!("================================");
-
-    // Initialize database pool and session store
-    let db_pool = db_core::init_pool().await?;
-    let session_store = PostgresStore::new((*db_pool).clone());
-    session_store.migrate().await?;
-
-    let session_layer = SessionManagerLayer::new(session_store)
-        .with_secure(false) // Set to true in production with HTTPS
-        .with_same_site(SameSite::Lax)
-        .with_expiry(Expiry::OnInactivity(Duration::seconds(3600))); // 1 hour
+    debug!("App URL: {}", app_url);
+    debug!("Issuer: {}", issuer);
+    debug!("Client ID: {}", client_id);
+    debug!("Client Secret: {:?}", client_secret.as_ref().map(|_| "***"));
 
     // Create OIDC login service (requires authentication)
     let oidc_login_service = ServiceBuilder::new()
@@ -121,11 +100,30 @@ where
         .route("/auth/maybe-protected", get(maybe_authenticated))
         .route("/auth/user", get(get_current_user))
         .layer(oidc_auth_service)
-        .layer(session_layer);
+        .layer(Extension(PostLogoutRedirect(Uri::from_maybe_shared(app_url)?)));
 
     Ok(app)
 }
 
+/// Session layer shared by every route so handlers outside `/auth` can
+/// resolve the logged-in user. The OIDC layers above depend on it, so it
+/// must wrap the whole application.
+pub async fn create_session_layer(
+    db_pool: &DbPool,
+    config: &Config,
+) -> Result<SessionManagerLayer<PostgresStore>, Box<dyn std::error::Error + Send + Sync>> {
+    let session_store = PostgresStore::new((**db_pool).clone());
+    session_store.migrate().await?;
+
+    let expiry = Duration::seconds(i64::try_from(config.auth.session_expiry_secs)?);
+    let session_layer = SessionManagerLayer::new(session_store)
+        .with_secure(config.server.app_url.starts_with("https://")) // Cookies only over HTTPS when served that way
+        .with_same_site(SameSite::Lax)
+        .with_expiry(Expiry::OnInactivity(expiry));
+
+    Ok(session_layer)
+}
+
 // Login page handler
 async fn login_page(inertia: Inertia) -> impl IntoResponse {
     inertia.render("Login", json!({
@@ -145,21 +143,19 @@ async fn authenticated(
     State(db_pool): State<DbPool>,
 ) -> impl IntoResponse {
     // Log all available claims for debugging
-    println!("=== OIDC Claims Debug ===");
-    println!("Subject: {}", claims.subject().as_str());
-    println!("Email: {:?}", claims.email());
-    println!("Name: {:?}", claims.name());
-    println!("Given name: {:?}", claims.given_name());
-    println!("Family name: {:?}", claims.family_name());
-    println!("Preferred username: {:?}", claims.preferred_username());
-    println!("Picture: {:?}", claims.picture());
-    println!("========================");
+    debug!("Subject: {}", claims.subject().as_str());
+    debug!("Email: {:?}", claims.email());
+    debug!("Name: {:?}", claims.name());
+    debug!("Given name: {:?}", claims.given_name());
+    debug!("Family name: {:?}", claims.family_name());
+    debug!("Preferred username: {:?}", claims.preferred_username());
+    debug!("Picture: {:?}", claims.picture());
     
     let user_id = claims.subject().as_str();
     let email = claims.email().map(|e| e.as_str());
     let name = claims.name().and_then(|n| n.get(None)).map(|s| s.as_str());
     
-    println!("Extracted values - user_id: {}, email: {:?}, name: {:?}", user_id, email, name);
+    debug!("Extracted values - user_id: {}, email: {:?}, name: {:?}", user_id, email, name);
     
     // Use UserRepository to find or create user
     let user_repo = UserRepository::new(&db_pool);
@@ -167,13 +163,13 @@ async fn authenticated(
         Ok(user) => {
             // Store user ID in session
             if let Err(e) = session.insert("user_id", user.id).await {
-                eprintln!("Failed to store user ID in session: {}", e);
+                error!("Failed to store user ID in session: {}", e);
             }
             
             format!("Hello {}! You are authenticated. Database ID: {}", user_id, user.id)
         }
         Err(e) => {
-            eprintln!("Failed to find or create user: {}", e);
+            error!("Failed to find or create user: {}", e);
             format!("Hello {}! You are authenticated but there was a database error.", user_id)
         }
     }
@@ -197,13 +193,13 @@ async fn maybe_authenticated(
             Ok(user) => {
                 // Store user ID in session
                 if let Err(e) = session.insert("user_id", user.id).await {
-                    eprintln!("Failed to store user ID in session: {}", e);
+                    error!("Failed to store user ID in session: {}", e);
                 }
                 
                 format!("Hello {}! You are logged in via OIDC. Database ID: {}", user_id, user.id)
             }
             Err(e) => {
-                eprintln!("Failed to find or create user: {}", e);
+                error!("Failed to find or create user: {}", e);
                 format!("Hello {}! You are authenticated but there was a database error.", user_id)
             }
         }
@@ -223,14 +219,14 @@ async fn maybe_authenticated(
                         "Hello anonymous user! You can log in at /auth/login".to_string()
                     }
                     Err(e) => {
-                        eprintln!("Failed to load user from database: {}", e);
+                        error!("Failed to load user from database: {}", e);
                         "Hello anonymous user! You can log in at /auth/login".to_string()
                     }
                 }
             }
             Ok(None) => "Hello anonymous user! You can log in at /auth/login".to_string(),
             Err(e) => {
-                eprintln!("Failed to get user ID from session: {}", e);
+                error!("Failed to get user ID from session: {}", e);
                 "Hello anonymous user! You can log in at /auth/login".to_string()
             }
         }
@@ -244,13 +240,12 @@ async fn get_current_user(
 ) -> impl IntoResponse {
     let user_repo = UserRepository::new(&db_pool);
     
-    println!("=== Get Current User Debug ===");
-    println!("Session ID: {:?}", session.id());
+    debug!("Session ID: {:?}", session.id());
     
     // Try to get all session data for debugging
     match session.get::<i32>("user_id").await {
         Ok(Some(user_id)) => {
-            println!("Found user_id in session: {}", user_id);
+            debug!("Found user_id in session: {}", user_id);
             // Load user from database using repository
             match user_repo.find_by_id(user_id).await {
                 Ok(Some(user)) => {
@@ -268,7 +263,7 @@ async fn get_current_user(
                     }))
                 }
                 Err(e) => {
-                    eprintln!("Failed to load user from database: {}", e);
+                    error!("Failed to load user from database: {}", e);
                     axum::Json(json!({
                         "authenticated": false,
                         "user": null,
@@ -278,14 +273,14 @@ async fn get_current_user(
             }
         }
         Ok(None) => {
-            println!("No user_id found in session");
+            debug!("No user_id found in session");
             axum::Json(json!({
                 "authenticated": false,
                 "user": null
             }))
         }
         Err(e) => {
-            eprintln!("Failed to get user ID from session: {}", e);
+            error!("Failed to get user ID from session: {}", e);
             axum::Json(json!({
                 "authenticated": false,
                 "user": null,
@@ -307,47 +302,45 @@ async fn auth_callback(
     State(db_pool): State<DbPool>,
 ) -> impl IntoResponse {
     // Log all available claims for debugging
-    println!("=== OIDC Callback Claims Debug ===");
-    println!("Subject: {}", oidc_claims.subject().as_str());
-    println!("Email: {:?}", oidc_claims.email());
-    println!("Name: {:?}", oidc_claims.name());
-    println!("Given name: {:?}", oidc_claims.given_name());
-    println!("Family name: {:?}", oidc_claims.family_name());
-    println!("Preferred username: {:?}", oidc_claims.preferred_username());
-    println!("Picture: {:?}", oidc_claims.picture());
-    println!("==================================");
+    debug!("Subject: {}", oidc_claims.subject().as_str());
+    debug!("Email: {:?}", oidc_claims.email());
+    debug!("Name: {:?}", oidc_claims.name());
+    debug!("Given name: {:?}", oidc_claims.given_name());
+    debug!("Family name: {:?}", oidc_claims.family_name());
+    debug!("Preferred username: {:?}", oidc_claims.preferred_username());
+    debug!("Picture: {:?}", oidc_claims.picture());
     
     let user_id = oidc_claims.subject().as_str();
     let email = oidc_claims.email().map(|e| e.as_str());
     let name = oidc_claims.name().and_then(|n| n.get(None)).map(|s| s.as_str());
     
-    println!("Extracted values - user_id: {}, email: {:?}, name: {:?}", user_id, email, name);
+    debug!("Extracted values - user_id: {}, email: {:?}, name: {:?}", user_id, email, name);
     
     // Use UserRepository to find or create user
     let user_repo = UserRepository::new(&db_pool);
     match user_repo.find_or_create_by_oidc_subject(user_id, email, name).await {
         Ok(user) => {
             // Store user ID in session
-            println!("Storing user ID {} in session", user.id);
-            println!("Session ID during callback: {:?}", session.id());
+            debug!("Storing user ID {} in session", user.id);
+            debug!("Session ID during callback: {:?}", session.id());
             if let Err(e) = session.insert("user_id", user.id).await {
-                eprintln!("Failed to store user ID in session: {}", e);
+                error!("Failed to store user ID in session: {}", e);
                 return Redirect::to("/auth/login?error=session_error");
             }
-            println!("Successfully stored user ID in session");
+            debug!("Successfully stored user ID in session");
             
             // Verify the data was stored
             match session.get::<i32>("user_id").await {
-                Ok(Some(stored_id)) => println!("Verified: user_id {} is stored in session", stored_id),
-                Ok(None) => println!("WARNING: user_id not found immediately after storing!"),
-                Err(e) => println!("ERROR: Failed to verify stored user_id: {}", e),
+                Ok(Some(stored_id)) => debug!("Verified: user_id {} is stored in session", stored_id),
+                Ok(None) => warn!("user_id not found immediately after storing!"),
+                Err(e) => error!("Failed to verify stored user_id: {}", e),
             }
             
             let redirect_url = query.origin.unwrap_or_else(|| "/".to_string());
             Redirect::to(&redirect_url)
         }
         Err(e) => {
-            eprintln!("Failed to find or create user: {}", e);
+            error!("Failed to find or create user: {}", e);
             Redirect::to("/auth/login?error=database_error")
         }
     }
@@ -357,17 +350,12 @@ async fn auth_callback(
 async fn logout(
     logout: OidcRpInitiatedLogout,
     session: Session,
+    Extension(PostLogoutRedirect(redirect)): Extension<PostLogoutRedirect>,
 ) -> impl IntoResponse {
     // Clear user ID from session
     if let Err(e) = session.remove::<i32>("user_id").await {
-        eprintln!("Failed to remove user ID from session: {}", e);
+        error!("Failed to remove user ID from session: {}", e);
     }
-    
-    let redirect_url = std::env::var("OIDC_REDIRECT_URL")
-        .unwrap_or_else(|_| "http://localhost:8000".to_string());
-    
-    logout.with_post_logout_redirect(
-        Uri::from_maybe_shared(redirect_url)
-            .unwrap_or_else(|_| Uri::from_static("http://localhost:8000"))
-    )
+
+    logout.with_post_logout_redirect(redirect)
 } 
\ No newline at end of file
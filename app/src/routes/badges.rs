@@ -0,0 +1,279 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Json, Router,
+};
+use axum_extra::extract::WithRejection;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use status_page::badge::{self, Badge};
+use db_core::DbPool;
+use db_core::models::audit_log::AuditAction;
+use db_core::models::badge_token::BadgeSubject;
+use db_core::repositories::BadgeTokenRepository;
+use crate::api_version::ApiVersion;
+use crate::error::ApiError;
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::services::badge_service::{BadgeService, UptimeWindow};
+
+/// Longer labels are cut short
+const MAX_LABEL: usize = 64;
+
+#[derive(Debug, Deserialize)]
+struct BadgeQuery {
+    /// Replaces the monitor's or group's name
+    label: Option<String>,
+    /// Show uptime over this window instead of the status
+    uptime: Option<UptimeWindow>,
+    color: Option<String>,
+    label_color: Option<String>,
+    /// Required for private badges
+    token: Option<String>,
+}
+
+/// A new badge token, with the badge URL that carries it
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct IssuedBadgeToken {
+    token: String,
+    badge_url: String,
+}
+
+/// Whether a badge is private
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct BadgePrivacy {
+    /// Private badges are only served with their token; public ones to
+    /// everyone, even for monitors no public page shows
+    private: bool,
+}
+
+/// SVG badges, public or unlocked by a token
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/badge/{file}", get(monitor_badge))
+        .route("/badge/group/{file}", get(group_badge))
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/monitors/{id}/badge", put(set_monitor_privacy))
+        .route("/monitors/{id}/badge-token", post(issue_monitor_token).delete(revoke_monitor_token))
+        .route("/service-groups/{id}/badge", put(set_group_privacy))
+        .route("/service-groups/{id}/badge-token", post(issue_group_token).delete(revoke_group_token))
+}
+
+async fn monitor_badge(
+    State(pool): State<DbPool>,
+    Path(file): Path<String>,
+    WithRejection(Query(query), _): WithRejection<Query<BadgeQuery>, ApiError>,
+) -> Result<Response, ApiError> {
+    render(&pool, BadgeSubject::Monitor(badge_id(&file)?), query).await
+}
+
+async fn group_badge(
+    State(pool): State<DbPool>,
+    Path(file): Path<String>,
+    WithRejection(Query(query), _): WithRejection<Query<BadgeQuery>, ApiError>,
+) -> Result<Response, ApiError> {
+    render(&pool, BadgeSubject::ServiceGroup(badge_id(&file)?), query).await
+}
+
+/// The ID in `{id}.svg`
+fn badge_id(file: &str) -> Result<i32, ApiError> {
+    file.strip_suffix(".svg")
+        .and_then(|id| id.parse().ok())
+        .ok_or(ApiError::NotFound)
+}
+
+async fn render(pool: &DbPool, subject: BadgeSubject, query: BadgeQuery) -> Result<Response, ApiError> {
+    let color = |name: &Option<String>| {
+        name.as_deref()
+            .map(|name| badge::color(name).ok_or_else(|| ApiError::BadRequest(format!("Invalid color: {}", name))))
+            .transpose()
+    };
+    let custom_color = color(&query.color)?;
+    let label_color = color(&query.label_color)?.unwrap_or_else(|| badge::LABEL_COLOR.to_string());
+
+    // Private badges look the same as missing ones without their token
+    let state = BadgeService::load(pool, subject, query.token.as_deref(), query.uptime)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let (message, status_color) = match (query.uptime, state.uptime) {
+        (None, _) => (badge::status_message(&state.status).to_string(), badge::status_color(&state.status)),
+        (Some(_), Some(uptime)) => (badge::uptime_message(uptime), badge::uptime_color(uptime)),
+        (Some(_), None) => ("no data".to_string(), badge::status_color("unknown")),
+    };
+    let label = query.label.unwrap_or_else(|| match query.uptime {
+        Some(window) => format!("{} uptime {}", state.name, window.as_str()),
+        None => state.name.clone(),
+    });
+    let label: String = label.chars().take(MAX_LABEL).collect();
+
+    let svg = badge::render(&Badge {
+        label: &label,
+        message: &message,
+        label_color: &label_color,
+        color: custom_color.as_deref().unwrap_or(status_color),
+    });
+    // Short, so a badge in a README catches up quickly
+    let cache_control = if state.private { "private, max-age=60" } else { "public, max-age=60" };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml; charset=utf-8"),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        svg,
+    )
+        .into_response())
+}
+
+async fn set_monitor_privacy(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+    WithRejection(Json(input), _): WithRejection<Json<BadgePrivacy>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    set_privacy(&pool, version, audit, BadgeSubject::Monitor(id), input).await
+}
+
+async fn set_group_privacy(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+    WithRejection(Json(input), _): WithRejection<Json<BadgePrivacy>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    set_privacy(&pool, version, audit, BadgeSubject::ServiceGroup(id), input).await
+}
+
+async fn issue_monitor_token(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    issue(&pool, version, audit, BadgeSubject::Monitor(id)).await
+}
+
+async fn issue_group_token(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    issue(&pool, version, audit, BadgeSubject::ServiceGroup(id)).await
+}
+
+async fn revoke_monitor_token(
+    State(pool): State<DbPool>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    revoke(&pool, audit, BadgeSubject::Monitor(id)).await
+}
+
+async fn revoke_group_token(
+    State(pool): State<DbPool>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    revoke(&pool, audit, BadgeSubject::ServiceGroup(id)).await
+}
+
+/// Making a badge public drops its token, so making it private again needs
+/// a new one
+async fn set_privacy(
+    pool: &DbPool,
+    version: ApiVersion,
+    audit: AuditContext,
+    subject: BadgeSubject,
+    input: BadgePrivacy,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = BadgeTokenRepository::find(pool, subject).await?;
+
+    let setting = BadgeService::set_private(pool, subject, input.private).await?;
+    let action = if before.is_some() { AuditAction::Update } else { AuditAction::Create };
+    AuditService::record(pool, &audit, action, "badge_token", setting.id, before.as_ref(), Some(&setting)).await;
+    Ok(version.json(&setting))
+}
+
+/// Issuing again replaces the token, so old badge URLs stop working
+async fn issue(
+    pool: &DbPool,
+    version: ApiVersion,
+    audit: AuditContext,
+    subject: BadgeSubject,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = BadgeTokenRepository::find(pool, subject).await?;
+
+    let token = BadgeService::issue_token(pool, subject).await?;
+    let action = if before.is_some() { AuditAction::Update } else { AuditAction::Create };
+    AuditService::record(pool, &audit, action, "badge_token", token.id, before.as_ref(), Some(&token)).await;
+
+    // Always set on a token just issued
+    let secret = token.token.unwrap_or_default();
+    let badge_url = match subject {
+        BadgeSubject::Monitor(id) => format!("/badge/{}.svg?token={}", id, secret),
+        BadgeSubject::ServiceGroup(id) => format!("/badge/group/{}.svg?token={}", id, secret),
+    };
+    let issued = IssuedBadgeToken {
+        token: secret,
+        badge_url,
+    };
+    Ok((StatusCode::CREATED, version.json(&issued)))
+}
+
+/// Stop the token working. The badge stays private, so it is served to
+/// nobody until a new token is issued or it is made public.
+async fn revoke(pool: &DbPool, audit: AuditContext, subject: BadgeSubject) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = BadgeTokenRepository::find(pool, subject).await?.ok_or(ApiError::NotFound)?;
+
+    let after = BadgeTokenRepository::revoke(pool, subject).await?;
+    AuditService::record(pool, &audit, AuditAction::Update, "badge_token", after.id, Some(&before), Some(&after)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{with_sign_in, TestState};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn managing_badges_needs_a_session() {
+        let app = with_sign_in(api_router::<TestState>().with_state(TestState::offline()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = reqwest::Client::new();
+
+        let requests = [
+            client.put(format!("{}/monitors/1/badge", base)).json(&json!({ "private": false })),
+            client.post(format!("{}/monitors/1/badge-token", base)),
+            client.delete(format!("{}/monitors/1/badge-token", base)),
+            client.put(format!("{}/service-groups/1/badge", base)).json(&json!({ "private": false })),
+            client.post(format!("{}/service-groups/1/badge-token", base)),
+            client.delete(format!("{}/service-groups/1/badge-token", base)),
+        ];
+        for request in requests {
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status(), 401, "{}", response.url());
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+    routing::get,
+    Router,
+};
+use db_core::DbPool;
+use futures::stream::StreamExt;
+use std::sync::Arc;
+use tracing::warn;
+use crate::error::ApiError;
+use crate::services::audit_service::AuditContext;
+use crate::services::dashboard_service::{ClientMessage, DashboardCommand, DashboardService, ServerMessage};
+use crate::services::stream_service::StatusStream;
+use crate::services::worker::WorkerService;
+
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+    Arc<StatusStream>: axum::extract::FromRef<S>,
+    Arc<WorkerService>: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/ws/dashboard", get(dashboard_socket))
+}
+
+/// Upgrade to the dashboard WebSocket. Only signed-in users may connect,
+/// since the socket can change monitors and incidents.
+async fn dashboard_socket(
+    State(pool): State<DbPool>,
+    State(stream): State<Arc<StatusStream>>,
+    State(worker): State<Arc<WorkerService>>,
+    audit: AuditContext,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    audit.require_user()?;
+
+    Ok(upgrade.on_upgrade(move |socket| handle_socket(socket, pool, stream, worker, audit)))
+}
+
+async fn handle_socket(
+    mut socket: WebSocket,
+    pool: DbPool,
+    stream: Arc<StatusStream>,
+    worker: Arc<WorkerService>,
+    audit: AuditContext,
+) {
+    // Subscribe up front and filter here, so changing the monitor selection
+    // never drops events in between
+    let mut events = Box::pin(stream.subscribe(Vec::new(), None));
+    let mut monitor_ids: Vec<i32> = Vec::new();
+
+    loop {
+        let reply = tokio::select! {
+            Some(event) = events.next() => {
+                if !event.matches(&monitor_ids) {
+                    continue;
+                }
+                ServerMessage::event(&event)
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage { id, command }) => {
+                        if let DashboardCommand::Subscribe { monitor_ids: ids } = &command {
+                            monitor_ids = ids.clone();
+                        }
+                        match DashboardService::execute(&pool, &worker, &stream, &audit, command).await {
+                            Ok(data) => ServerMessage::Result { id, data },
+                            Err(e) => ServerMessage::error(id, &e),
+                        }
+                    }
+                    Err(e) => ServerMessage::error(None, &ApiError::BadRequest(e.to_string())),
+                },
+                // Pings are answered by axum; other frames carry nothing for us
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+
+        let text = match serde_json::to_string(&reply) {
+            Ok(text) => text,
+            Err(e) => {
+                warn!("Failed to serialize dashboard message: {}", e);
+                continue;
+            }
+        };
+        if socket.send(Message::Text(text.into())).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{session_cookie, with_sign_in, TestState};
+    use db_core::notifications::status_change;
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest};
+
+    async fn next_json<S>(socket: &mut S) -> serde_json::Value
+    where
+        S: futures::Stream<Item = Result<tungstenite::Message, tungstenite::Error>> + Unpin,
+    {
+        let message = socket.next().await.unwrap().unwrap();
+        serde_json::from_str(message.to_text().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn signed_in_clients_get_filtered_events_and_command_errors() {
+        let state = TestState::offline();
+        let app = with_sign_in(router::<TestState>().with_state(state.clone()));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let url = format!("ws://{}/ws/dashboard", addr);
+
+        match tokio_tungstenite::connect_async(url.as_str()).await {
+            Err(tungstenite::Error::Http(response)) => assert_eq!(response.status(), 401),
+            other => panic!("expected 401, got {:?}", other.map(|(_, response)| response.status())),
+        }
+
+        let login = reqwest::get(format!("http://{}/login", addr)).await.unwrap();
+        let cookie = session_cookie(&login);
+        let mut request = url.into_client_request().unwrap();
+        request.headers_mut().insert("cookie", cookie.parse().unwrap());
+        let (mut socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+        socket
+            .send(tungstenite::Message::text(r#"{"id":"1","type":"subscribe","monitor_ids":[2]}"#))
+            .await
+            .unwrap();
+        let subscribed = next_json(&mut socket).await;
+        assert_eq!(subscribed["type"], "result");
+        assert_eq!(subscribed["id"], "1");
+
+        state.stream.publish(status_change(1, "operational", Some("operational")));
+        state.stream.publish(status_change(2, "operational", Some("operational")));
+        let event = next_json(&mut socket).await;
+        assert_eq!(event["type"], "event");
+        assert_eq!(event["event"], "status_event");
+        assert_eq!(event["data"]["monitor_id"], 2);
+
+        socket.send(tungstenite::Message::text("{not json")).await.unwrap();
+        let error = next_json(&mut socket).await;
+        assert_eq!(error["type"], "error");
+        assert_eq!(error["code"], "bad_request");
+    }
+}
@@ -0,0 +1,59 @@
+use axum::{
+    extract::{OriginalUri, Query, State},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use axum_extra::extract::WithRejection;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use db_core::DbPool;
+use db_core::models::document::{DocumentFilter, DocumentSort};
+use db_core::pagination::{PageRequest, SortDirection};
+use crate::error::ApiError;
+use crate::pagination::Paginated;
+use crate::services::document_service::DocumentService;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct DocumentListQuery {
+    status: Option<String>,
+    type_name: Option<String>,
+    reviewer: Option<String>,
+    q: Option<String>,
+    #[serde(default)]
+    sort: DocumentSort,
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/documents", get(list_documents))
+}
+
+async fn list_documents(
+    State(pool): State<DbPool>,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<DocumentListQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let filter = DocumentFilter {
+        status: query.status,
+        type_name: query.type_name,
+        reviewer: query.reviewer,
+        q: query.q,
+    };
+    let page = PageRequest {
+        limit: query.limit,
+        cursor: query.cursor,
+        direction: query.order,
+    };
+
+    let documents = DocumentService::new(pool).list(&filter, query.sort, &page).await?;
+    Ok(Paginated::new(documents, uri))
+}
@@ -35,7 +35,7 @@ async fn index(
     };
 
     // Get monitor status data for the dashboard
-    let status_data = match crate::services::monitor_service::MonitorService::get_status_page_data(&pool).await {
+    let status_data = match crate::services::monitor_service::MonitorService::get_status_page_data(&pool, &status_page::StatusPageFilter::default()).await {
         Ok(data) => Some(data),
         Err(_) => None
     };
@@ -1,44 +1,274 @@
 use axum::{
-    extract::State,
+    extract::{OriginalUri, Path, Query, State},
     http::StatusCode,
-    routing::post,
+    response::{IntoResponse, Response},
+    routing::{get, post},
     Json, Router,
 };
+use axum_extra::extract::WithRejection;
+use axum_inertia::Inertia;
+use db_core::models::audit_log::AuditAction;
+use db_core::pagination::{PageRequest, SortDirection};
+use db_core::DbPool;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
 
-use crate::AppState;
+use crate::api_version::ApiVersion;
+use crate::error::ApiError;
+use crate::pagination::Paginated;
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::services::job_service::JobService;
+use crate::services::worker::job_queue::JOB_STATES;
+use crate::services::worker::{JobFilter, RescheduleJob, WorkerService};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct EmailRequest {
     to: String,
     subject: String,
     body: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct JobResponse {
     job_id: String,
     status: String,
 }
 
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub(crate) struct JobListQuery {
+    /// One of `queued`, `running`, `retrying` or `failed`
+    state: Option<String>,
+    /// Task identifier, e.g. `send_email`
+    task: Option<String>,
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+impl JobListQuery {
+    fn into_parts(self) -> (JobFilter, PageRequest) {
+        let filter = JobFilter {
+            state: non_empty(self.state),
+            task: non_empty(self.task),
+        };
+        let page = PageRequest {
+            limit: self.limit,
+            cursor: self.cursor,
+            direction: self.order,
+        };
+        (filter, page)
+    }
+}
+
+// HTML forms submit empty strings for untouched filters
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
+
 async fn queue_email(
-    State(state): State<AppState>,
-    Json(request): Json<EmailRequest>,
-) -> Result<Json<JobResponse>, StatusCode> {
-    let worker_service = &state.worker_service;
-    
-    let job_id = worker_service
+    State(worker): State<Arc<WorkerService>>,
+    version: ApiVersion,
+    WithRejection(Json(request), _): WithRejection<Json<EmailRequest>, ApiError>,
+) -> Result<Response, ApiError> {
+    let job_id = worker
         .queue_email(&request.to, &request.subject, &request.body)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
-    Ok(Json(JobResponse {
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    Ok(version.json(&JobResponse {
         job_id,
         status: "queued".to_string(),
     }))
 }
 
-pub fn router() -> Router<AppState> {
+async fn list_jobs(
+    State(worker): State<Arc<WorkerService>>,
+    audit: AuditContext,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<JobListQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let (filter, page) = query.into_parts();
+    let jobs = JobService::list_jobs(&worker, &filter, &page).await?;
+    Ok(Paginated::new(jobs, uri))
+}
+
+async fn get_job(
+    State(worker): State<Arc<WorkerService>>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Path(id), _): WithRejection<Path<i64>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let job = JobService::get_job(&worker, id).await?;
+    Ok(version.json(&job))
+}
+
+async fn retry_job(
+    State(pool): State<DbPool>,
+    State(worker): State<Arc<WorkerService>>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Path(id), _): WithRejection<Path<i64>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = JobService::get_job(&worker, id).await?;
+
+    let job = JobService::retry_job(&worker, &before).await?;
+    AuditService::record(&pool, &audit, AuditAction::Update, "job", id, Some(&before), Some(&job)).await;
+    Ok(version.json(&job))
+}
+
+async fn reschedule_job(
+    State(pool): State<DbPool>,
+    State(worker): State<Arc<WorkerService>>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Path(id), _): WithRejection<Path<i64>, ApiError>,
+    WithRejection(Json(changes), _): WithRejection<Json<RescheduleJob>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = JobService::get_job(&worker, id).await?;
+
+    let job = JobService::reschedule_job(&worker, &before, &changes).await?;
+    AuditService::record(&pool, &audit, AuditAction::Update, "job", id, Some(&before), Some(&job)).await;
+    Ok(version.json(&job))
+}
+
+async fn cancel_job(
+    State(pool): State<DbPool>,
+    State(worker): State<Arc<WorkerService>>,
+    audit: AuditContext,
+    WithRejection(Path(id), _): WithRejection<Path<i64>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = JobService::get_job(&worker, id).await?;
+
+    JobService::cancel_job(&worker, &before).await?;
+    AuditService::record(&pool, &audit, AuditAction::Delete, "job", id, Some(&before), None).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    Arc<WorkerService>: axum::extract::FromRef<S>,
+    axum_inertia::InertiaConfig: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/jobs", get(jobs_page))
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`.
+/// Everything but queueing an email needs a signed-in session, since jobs
+/// carry their payloads.
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+    Arc<WorkerService>: axum::extract::FromRef<S>,
+{
     Router::new()
-        .route("/api/jobs/email", post(queue_email))
+        .route("/jobs", get(list_jobs))
+        .route("/jobs/email", post(queue_email))
+        .route("/jobs/{id}", get(get_job))
+        .route("/jobs/{id}/retry", post(retry_job))
+        .route("/jobs/{id}/reschedule", post(reschedule_job))
+        .route("/jobs/{id}/cancel", post(cancel_job))
+}
+
+async fn jobs_page(
+    State(worker): State<Arc<WorkerService>>,
+    audit: AuditContext,
+    Query(query): Query<JobListQuery>,
+    inertia: Inertia,
+) -> impl IntoResponse {
+    let filters = json!({ "state": query.state, "task": query.task });
+    if audit.require_user().is_err() {
+        return inertia.render("Jobs", json!({
+            "jobs": { "data": [], "next_cursor": null, "has_more": false },
+            "states": JOB_STATES,
+            "filters": filters,
+            "error": "Sign in to see jobs",
+        }));
+    }
+    let (filter, page) = query.into_parts();
+
+    match JobService::list_jobs(&worker, &filter, &page).await {
+        Ok(jobs) => inertia.render("Jobs", json!({
+            "jobs": jobs,
+            "states": JOB_STATES,
+            "filters": filters,
+        })),
+        Err(_) => inertia.render("Jobs", json!({
+            "jobs": { "data": [], "next_cursor": null, "has_more": false },
+            "states": JOB_STATES,
+            "filters": filters,
+            "error": "Failed to load jobs",
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{session_cookie, with_sign_in, TestState};
+
+    async fn serve(state: TestState) -> String {
+        let app = with_sign_in(api_router::<TestState>().with_state(state));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn managing_jobs_needs_a_session() {
+        let base = serve(TestState::offline()).await;
+        let client = reqwest::Client::new();
+
+        let requests = [
+            client.get(format!("{}/jobs", base)),
+            client.get(format!("{}/jobs/1", base)),
+            client.post(format!("{}/jobs/1/retry", base)),
+            client.post(format!("{}/jobs/1/reschedule", base)).json(&json!({})),
+            client.post(format!("{}/jobs/1/cancel", base)),
+        ];
+        for request in requests {
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status(), 401, "{}", response.url());
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn only_failed_jobs_can_be_cancelled() {
+        let state = TestState::connect().await;
+        let pool = state.pool.clone();
+        let base = serve(state).await;
+        let client = reqwest::Client::new();
+        let cookie = session_cookie(&client.get(format!("{}/login", base)).send().await.unwrap());
+        let add_job = || sqlx::query_scalar::<_, i64>("SELECT id FROM graphile_worker.add_job('job-cancel-test', '{}'::json)").fetch_one(pool.as_ref());
+        let cancel = |id: i64| client.post(format!("{}/jobs/{}/cancel", base, id)).header("cookie", &cookie).send();
+
+        let queued = add_job().await.unwrap();
+        assert_eq!(cancel(queued).await.unwrap().status(), 409);
+
+        let failed = add_job().await.unwrap();
+        sqlx::query("UPDATE graphile_worker._private_jobs SET attempts = max_attempts WHERE id = $1")
+            .bind(failed)
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(cancel(failed).await.unwrap().status(), 204);
+        assert_eq!(cancel(failed).await.unwrap().status(), 404);
+
+        sqlx::query("DELETE FROM graphile_worker._private_jobs WHERE id = $1")
+            .bind(queued)
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+    }
 }
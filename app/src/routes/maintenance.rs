@@ -0,0 +1,131 @@
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use axum_extra::extract::WithRejection;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use db_core::DbPool;
+use db_core::models::audit_log::AuditAction;
+use db_core::models::maintenance_window::{CreateMaintenanceWindow, UpdateMaintenanceWindow};
+use db_core::pagination::{PageRequest, SortDirection};
+use db_core::repositories::MaintenanceWindowRepository;
+use crate::api_version::ApiVersion;
+use crate::error::ApiError;
+use crate::pagination::Paginated;
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::validation::{validate_maintenance_update, Validate};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct MaintenanceListQuery {
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/maintenance", get(list_maintenance).post(create_maintenance))
+        .route("/maintenance/{id}", get(get_maintenance).put(update_maintenance).delete(delete_maintenance))
+}
+
+async fn list_maintenance(
+    State(pool): State<DbPool>,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<MaintenanceListQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = PageRequest {
+        limit: query.limit,
+        cursor: query.cursor,
+        direction: query.order,
+    };
+    let windows = MaintenanceWindowRepository::list(&pool, &page).await?;
+    Ok(Paginated::new(windows, uri))
+}
+
+async fn create_maintenance(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Json(window), _): WithRejection<Json<CreateMaintenanceWindow>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    window.validate()?;
+
+    let window = MaintenanceWindowRepository::create(&pool, window).await?;
+    AuditService::record(&pool, &audit, AuditAction::Create, "maintenance_window", window.id, None, Some(&window)).await;
+    Ok((StatusCode::CREATED, version.json(&window)))
+}
+
+async fn get_maintenance(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let window = MaintenanceWindowRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    Ok(version.json(&window))
+}
+
+async fn update_maintenance(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+    WithRejection(Json(update), _): WithRejection<Json<UpdateMaintenanceWindow>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = MaintenanceWindowRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    validate_maintenance_update(&before, &update)?;
+
+    let window = MaintenanceWindowRepository::update(&pool, id, update).await?;
+    AuditService::record(&pool, &audit, AuditAction::Update, "maintenance_window", id, Some(&before), Some(&window)).await;
+    Ok(version.json(&window))
+}
+
+async fn delete_maintenance(
+    State(pool): State<DbPool>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = MaintenanceWindowRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
+    MaintenanceWindowRepository::delete(&pool, id).await?;
+    AuditService::record(&pool, &audit, AuditAction::Delete, "maintenance_window", id, Some(&before), None).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{with_sign_in, TestState};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn changing_maintenance_needs_a_session() {
+        let app = with_sign_in(api_router::<TestState>().with_state(TestState::offline()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = reqwest::Client::new();
+        let body = json!({ "title": "Upgrade", "starts_at": "2030-01-01T00:00:00Z", "ends_at": "2030-01-01T01:00:00Z" });
+
+        let requests = [
+            client.post(format!("{}/maintenance", base)).json(&body),
+            client.put(format!("{}/maintenance/1", base)).json(&body),
+            client.delete(format!("{}/maintenance/1", base)),
+        ];
+        for request in requests {
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status(), 401, "{}", response.url());
+        }
+    }
+}
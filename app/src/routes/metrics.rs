@@ -0,0 +1,37 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use db_core::DbPool;
+use std::sync::Arc;
+use crate::error::ApiError;
+use crate::metrics::HttpMetrics;
+use crate::services::metrics_service::MetricsService;
+use crate::services::worker::WorkerService;
+
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+    Arc<WorkerService>: axum::extract::FromRef<S>,
+    HttpMetrics: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/metrics", get(metrics))
+}
+
+/// Prometheus scrape endpoint
+async fn metrics(
+    State(pool): State<DbPool>,
+    State(worker): State<Arc<WorkerService>>,
+    State(http): State<HttpMetrics>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body = MetricsService::render(&pool, &worker, &http).await?;
+
+    Ok(([(header::CONTENT_TYPE, CONTENT_TYPE)], body))
+}
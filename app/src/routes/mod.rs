@@ -1,5 +1,19 @@
+pub mod api;
+pub mod api_docs;
+pub mod audit;
 pub mod auth;
+pub mod badges;
+pub mod dashboard;
+pub mod documents;
 pub mod home;
 pub mod jobs;
+pub mod maintenance;
+pub mod metrics;
 pub mod monitors;
+pub mod service_groups;
+pub mod slos;
 pub mod status;
+pub mod status_feeds;
+pub mod status_pages;
+pub mod statuspage_api;
+pub mod stream;
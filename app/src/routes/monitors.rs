@@ -8,6 +8,7 @@ use axum_inertia::Inertia;
 use serde_json::json;
 use db_core::DbPool;
 use crate::services::monitor_service::MonitorService;
+use status_page::StatusPageFilter;
 
 pub fn router<S>() -> Router<S>
 where
@@ -26,7 +27,7 @@ async fn monitors_list(
     State(pool): State<DbPool>,
     inertia: Inertia,
 ) -> impl IntoResponse {
-    match MonitorService::get_status_page_data(&pool).await {
+    match MonitorService::get_status_page_data(&pool, &StatusPageFilter::default()).await {
         Ok(data) => {
             inertia.render("Monitors", json!({
                 "monitors": data.monitors,
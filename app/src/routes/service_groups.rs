@@ -0,0 +1,140 @@
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use axum_extra::extract::WithRejection;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use db_core::DbPool;
+use db_core::models::audit_log::AuditAction;
+use db_core::models::service_group::{CreateServiceGroup, UpdateServiceGroup};
+use db_core::pagination::{PageRequest, SortDirection};
+use db_core::repositories::ServiceGroupRepository;
+use crate::api_version::ApiVersion;
+use crate::error::ApiError;
+use crate::pagination::Paginated;
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::validation::{validate_group_parent, Validate};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ServiceGroupListQuery {
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/service-groups", get(list_service_groups).post(create_service_group))
+        .route("/service-groups/{id}", get(get_service_group).put(update_service_group).delete(delete_service_group))
+}
+
+async fn list_service_groups(
+    State(pool): State<DbPool>,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<ServiceGroupListQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = PageRequest {
+        limit: query.limit,
+        cursor: query.cursor,
+        direction: query.order,
+    };
+    let groups = ServiceGroupRepository::list(&pool, &page).await?;
+    Ok(Paginated::new(groups, uri))
+}
+
+async fn create_service_group(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Json(group), _): WithRejection<Json<CreateServiceGroup>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    group.validate()?;
+    if let Some(parent_id) = group.parent_id {
+        let groups = ServiceGroupRepository::list_all(&pool).await?;
+        validate_group_parent(&groups, None, parent_id)?;
+    }
+
+    let group = ServiceGroupRepository::create(&pool, group).await?;
+    AuditService::record(&pool, &audit, AuditAction::Create, "service_group", group.id, None, Some(&group)).await;
+    Ok((StatusCode::CREATED, version.json(&group)))
+}
+
+async fn get_service_group(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let group = ServiceGroupRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    Ok(version.json(&group))
+}
+
+async fn update_service_group(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+    WithRejection(Json(update), _): WithRejection<Json<UpdateServiceGroup>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = ServiceGroupRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    update.validate()?;
+    // 0 moves the group to the top level, which is always allowed
+    if let Some(parent_id) = update.parent_id.filter(|parent_id| *parent_id != 0) {
+        let groups = ServiceGroupRepository::list_all(&pool).await?;
+        validate_group_parent(&groups, Some(id), parent_id)?;
+    }
+
+    let group = ServiceGroupRepository::update(&pool, id, update).await?;
+    AuditService::record(&pool, &audit, AuditAction::Update, "service_group", id, Some(&before), Some(&group)).await;
+    Ok(version.json(&group))
+}
+
+async fn delete_service_group(
+    State(pool): State<DbPool>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = ServiceGroupRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
+    ServiceGroupRepository::delete(&pool, id).await?;
+    AuditService::record(&pool, &audit, AuditAction::Delete, "service_group", id, Some(&before), None).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{with_sign_in, TestState};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn changing_service_groups_needs_a_session() {
+        let app = with_sign_in(api_router::<TestState>().with_state(TestState::offline()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = reqwest::Client::new();
+        let body = json!({ "name": "Edge" });
+
+        let requests = [
+            client.post(format!("{}/service-groups", base)).json(&body),
+            client.put(format!("{}/service-groups/1", base)).json(&body),
+            client.delete(format!("{}/service-groups/1", base)),
+        ];
+        for request in requests {
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status(), 401, "{}", response.url());
+        }
+    }
+}
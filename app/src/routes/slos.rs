@@ -0,0 +1,175 @@
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use axum_extra::extract::WithRejection;
+use axum_inertia::Inertia;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use db_core::DbPool;
+use db_core::models::audit_log::AuditAction;
+use db_core::models::slo::{CreateSlo, UpdateSlo, CRITICAL_BURN_RATE, WARNING_BURN_RATE};
+use db_core::pagination::{PageRequest, SortDirection};
+use db_core::repositories::SloRepository;
+use crate::api_version::ApiVersion;
+use crate::error::ApiError;
+use crate::pagination::Paginated;
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::services::slo_service::SloService;
+use crate::validation::Validate;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SloListQuery {
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+    axum_inertia::InertiaConfig: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/slos", get(slo_report_page))
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/slos", get(list_slos).post(create_slo))
+        .route("/slos/{id}", get(get_slo).put(update_slo).delete(delete_slo))
+        .route("/slos/{id}/status", get(get_slo_status))
+}
+
+async fn list_slos(
+    State(pool): State<DbPool>,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<SloListQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = PageRequest {
+        limit: query.limit,
+        cursor: query.cursor,
+        direction: query.order,
+    };
+    let slos = SloRepository::list(&pool, &page).await?;
+    Ok(Paginated::new(slos, uri))
+}
+
+async fn create_slo(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Json(slo), _): WithRejection<Json<CreateSlo>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Alerts mail whatever address the SLO names
+    audit.require_user()?;
+    slo.validate()?;
+    SloService::check_subject(&pool, slo.monitor_id, slo.service_group_id).await?;
+
+    let slo = SloRepository::create(&pool, slo).await?;
+    AuditService::record(&pool, &audit, AuditAction::Create, "slo", slo.id, None, Some(&slo)).await;
+    Ok((StatusCode::CREATED, version.json(&slo)))
+}
+
+async fn get_slo(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let slo = SloRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    Ok(version.json(&slo))
+}
+
+async fn update_slo(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+    WithRejection(Json(update), _): WithRejection<Json<UpdateSlo>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = SloRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    update.validate()?;
+
+    let slo = SloRepository::update(&pool, id, update).await?;
+    AuditService::record(&pool, &audit, AuditAction::Update, "slo", id, Some(&before), Some(&slo)).await;
+    Ok(version.json(&slo))
+}
+
+async fn delete_slo(
+    State(pool): State<DbPool>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = SloRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
+    SloRepository::delete(&pool, id).await?;
+    AuditService::record(&pool, &audit, AuditAction::Delete, "slo", id, Some(&before), None).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_slo_status(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let slo = SloRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    let status = SloRepository::get_status(&pool, slo).await?;
+    Ok(version.json(&status))
+}
+
+async fn slo_report_page(State(pool): State<DbPool>, inertia: Inertia) -> impl IntoResponse {
+    let thresholds = json!({
+        "critical": CRITICAL_BURN_RATE,
+        "warning": WARNING_BURN_RATE,
+    });
+
+    match SloService::report(&pool).await {
+        Ok(slos) => inertia.render("SloReport", json!({
+            "slos": slos,
+            "burnRates": thresholds,
+        })),
+        Err(_) => inertia.render("SloReport", json!({
+            "slos": [],
+            "burnRates": thresholds,
+            "error": "Failed to load SLOs",
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{with_sign_in, TestState};
+
+    #[tokio::test]
+    async fn changing_slos_needs_a_session() {
+        let app = with_sign_in(api_router::<TestState>().with_state(TestState::offline()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = reqwest::Client::new();
+        let slo = json!({ "name": "Checkout", "monitor_id": 1, "target": 99.0, "alert_email": "someone@example.com" });
+
+        let requests = [
+            client.post(format!("{}/slos", base)).json(&slo),
+            client.put(format!("{}/slos/1", base)).json(&slo),
+            client.delete(format!("{}/slos/1", base)),
+        ];
+        for request in requests {
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status(), 401, "{}", response.url());
+        }
+    }
+}
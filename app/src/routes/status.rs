@@ -1,42 +1,196 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{OriginalUri, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post, put},
     Json, Router,
 };
+use axum_extra::extract::WithRejection;
 use axum_inertia::Inertia;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
 use time::OffsetDateTime;
 use db_core::DbPool;
-use db_core::models::monitor::{CreateMonitor, UpdateMonitor};
-use db_core::models::status_event::{CreateStatusEvent, StatusType};
-use db_core::models::incident::{CreateIncident, UpdateIncident};
+use db_core::models::monitor::{CreateMonitor, MonitorFilter, MonitorSort, UpdateMonitor};
+use db_core::models::status_event::{CreateStatusEvent, StatsGranularity, StatusEventFilter, StatusEventSort, StatusType};
+use db_core::models::incident::{CreateIncident, IncidentFilter, IncidentSort, UpdateIncident};
+use db_core::pagination::{PageRequest, SortDirection};
+use db_core::models::audit_log::AuditAction;
 use db_core::repositories::IncidentRepository;
+use status_page::StatusPageFilter;
+use crate::api_version::ApiVersion;
+use crate::error::ApiError;
+use crate::pagination::Paginated;
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::services::check_service::{CheckNow, CheckService};
+use crate::services::export_service::{ExportFormat, ExportService};
 use crate::services::monitor_service::MonitorService;
+use crate::services::stream_service::StatusStream;
+use crate::services::worker::WorkerService;
+use crate::validation::{validate_monitor_update, validate_range, Validate};
 
-#[derive(Debug, Deserialize)]
-struct HeartbeatRequest {
+/// Seconds a "check now" request waits for its result by default
+const DEFAULT_CHECK_WAIT: u64 = 30;
+const MAX_CHECK_WAIT: u64 = 120;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct HeartbeatRequest {
     timestamp: String,
     metadata: Option<serde_json::Value>,
     stats: Option<HeartbeatStats>,
 }
 
-#[derive(Debug, Deserialize)]
-struct HeartbeatStats {
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct HeartbeatStats {
     sent: u64,
     failed: u64,
     uptime: u64,
 }
 
-#[derive(Debug, Serialize)]
-struct HeartbeatResponse {
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct HeartbeatResponse {
     success: bool,
     timestamp: String,
     message: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct MonitorListQuery {
+    name: Option<String>,
+    monitor_type: Option<String>,
+    is_active: Option<bool>,
+    status: Option<String>,
+    /// Name of the group monitors belong to directly
+    service_group: Option<String>,
+    service_group_id: Option<i32>,
+    q: Option<String>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    from: Option<OffsetDateTime>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    to: Option<OffsetDateTime>,
+    #[serde(default)]
+    sort: MonitorSort,
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+impl MonitorListQuery {
+    fn into_parts(self) -> (MonitorFilter, MonitorSort, PageRequest) {
+        let filter = MonitorFilter {
+            name: self.name,
+            monitor_type: self.monitor_type,
+            is_active: self.is_active,
+            status: self.status,
+            service_group: self.service_group,
+            service_group_id: self.service_group_id,
+            q: self.q,
+            from: self.from,
+            to: self.to,
+        };
+        let page = PageRequest {
+            limit: self.limit,
+            cursor: self.cursor,
+            direction: self.order,
+        };
+        (filter, self.sort, page)
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct EventListQuery {
+    status: Option<String>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    from: Option<OffsetDateTime>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    to: Option<OffsetDateTime>,
+    #[serde(default)]
+    sort: StatusEventSort,
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct EventExportQuery {
+    #[serde(default)]
+    format: ExportFormat,
+    status: Option<String>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    from: Option<OffsetDateTime>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    to: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct StatsQuery {
+    #[serde(default)]
+    granularity: StatsGranularity,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    from: Option<OffsetDateTime>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    to: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct CheckQuery {
+    /// Wait for the check to finish and return its status event
+    #[serde(default)]
+    wait: bool,
+    /// Seconds to wait before returning the queued job instead (default 30, at most 120)
+    timeout: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct IncidentListQuery {
+    severity: Option<String>,
+    is_resolved: Option<bool>,
+    monitor_id: Option<i32>,
+    q: Option<String>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    from: Option<OffsetDateTime>,
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    to: Option<OffsetDateTime>,
+    #[serde(default)]
+    sort: IncidentSort,
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+impl IncidentListQuery {
+    fn into_parts(self) -> (IncidentFilter, IncidentSort, PageRequest) {
+        let filter = IncidentFilter {
+            severity: self.severity,
+            is_resolved: self.is_resolved,
+            monitor_id: self.monitor_id,
+            q: self.q,
+            from: self.from,
+            to: self.to,
+            shown_with: None,
+        };
+        let page = PageRequest {
+            limit: self.limit,
+            cursor: self.cursor,
+            direction: self.order,
+        };
+        (filter, self.sort, page)
+    }
+}
+
 pub fn router<S>() -> Router<S>
 where
     S: Clone + Send + Sync + 'static,
@@ -45,19 +199,34 @@ where
 {
     Router::new()
         .route("/status", get(status_page))
-        .route("/api/monitors", get(list_monitors).post(create_monitor))
-        .route("/api/monitors/{id}", get(get_monitor).put(update_monitor).delete(delete_monitor))
-        .route("/api/monitors/{id}/events", post(record_event))
-        .route("/api/heartbeat/{monitor_id}", post(receive_heartbeat))
-        .route("/api/incidents", get(list_incidents).post(create_incident))
-        .route("/api/incidents/{id}", put(update_incident).delete(delete_incident))
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+    Arc<StatusStream>: axum::extract::FromRef<S>,
+    Arc<WorkerService>: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/monitors", get(list_monitors).post(create_monitor))
+        .route("/monitors/check", post(dry_run_check))
+        .route("/monitors/{id}", get(get_monitor).put(update_monitor).delete(delete_monitor))
+        .route("/monitors/{id}/check", post(check_monitor))
+        .route("/monitors/{id}/events", get(list_events).post(record_event))
+        .route("/monitors/{id}/events/export", get(export_events))
+        .route("/monitors/{id}/stats", get(monitor_stats))
+        .route("/heartbeat/{monitor_id}", post(receive_heartbeat))
+        .route("/incidents", get(list_incidents).post(create_incident))
+        .route("/incidents/{id}", put(update_incident).delete(delete_incident))
 }
 
 async fn status_page(
     State(pool): State<DbPool>,
     inertia: Inertia,
 ) -> impl IntoResponse {
-    match MonitorService::get_status_page_data(&pool).await {
+    match MonitorService::get_status_page_data(&pool, &StatusPageFilter::default()).await {
         Ok(data) => {
             inertia.render("EnhancedStatusPage", json!({
                 "statusData": data,
@@ -68,127 +237,262 @@ async fn status_page(
                 "all_operational": true,
                 "last_updated": OffsetDateTime::now_utc(),
                 "monitors": [],
-                "incidents": []
+                "incidents": [],
+                "maintenance": []
             }
         }))
     }
 }
 
-async fn list_monitors(State(pool): State<DbPool>) -> impl IntoResponse {
-    match MonitorService::get_all_monitors(&pool).await {
-        Ok(monitors) => Json(monitors).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+async fn list_monitors(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<MonitorListQuery>, ApiError>,
+) -> Result<Response, ApiError> {
+    // Unversioned clients predate pagination and expect every monitor in an array
+    if version == ApiVersion::Legacy {
+        let monitors = MonitorService::get_all_monitors(&pool).await?;
+        return Ok(Json(monitors).into_response());
     }
+
+    let (filter, sort, page) = query.into_parts();
+    let monitors = MonitorService::list_monitors(&pool, &filter, sort, &page).await?;
+    Ok(Paginated::new(monitors, uri).into_response())
 }
 
 async fn create_monitor(
     State(pool): State<DbPool>,
-    Json(monitor): Json<CreateMonitor>,
-) -> impl IntoResponse {
-    match MonitorService::create_monitor(&pool, monitor).await {
-        Ok(monitor) => (StatusCode::CREATED, Json(monitor)).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    State(worker): State<Arc<WorkerService>>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Json(monitor), _): WithRejection<Json<CreateMonitor>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    monitor.validate()?;
+
+    let monitor = MonitorService::create_monitor(&pool, monitor).await?;
+    AuditService::record(&pool, &audit, AuditAction::Create, "monitor", monitor.id, None, Some(&monitor)).await;
+    CheckService::sync_schedule(&worker, &monitor, None).await;
+    Ok((StatusCode::CREATED, version.json(&monitor)))
 }
 
 async fn get_monitor(
     State(pool): State<DbPool>,
-    Path(id): Path<i32>,
-) -> impl IntoResponse {
-    match MonitorService::get_monitor(&pool, id).await {
-        Ok(Some(monitor)) => Json(monitor).into_response(),
-        Ok(None) => StatusCode::NOT_FOUND.into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let monitor = MonitorService::get_monitor(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    Ok(version.json(&monitor))
 }
 
 async fn update_monitor(
     State(pool): State<DbPool>,
-    Path(id): Path<i32>,
-    Json(monitor): Json<UpdateMonitor>,
-) -> impl IntoResponse {
-    match MonitorService::update_monitor(&pool, id, monitor).await {
-        Ok(monitor) => Json(monitor).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    State(worker): State<Arc<WorkerService>>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+    WithRejection(Json(update), _): WithRejection<Json<UpdateMonitor>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let before = MonitorService::get_monitor(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    validate_monitor_update(&before, &update)?;
+
+    let monitor = MonitorService::update_monitor(&pool, id, update).await?;
+    AuditService::record(&pool, &audit, AuditAction::Update, "monitor", id, Some(&before), Some(&monitor)).await;
+    CheckService::sync_schedule(&worker, &monitor, Some(&before)).await;
+    Ok(version.json(&monitor))
 }
 
 async fn delete_monitor(
     State(pool): State<DbPool>,
-    Path(id): Path<i32>,
-) -> impl IntoResponse {
-    match MonitorService::delete_monitor(&pool, id).await {
-        Ok(_) => StatusCode::NO_CONTENT.into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    State(worker): State<Arc<WorkerService>>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    let before = MonitorService::get_monitor(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
+    MonitorService::delete_monitor(&pool, id).await?;
+    AuditService::record(&pool, &audit, AuditAction::Delete, "monitor", id, Some(&before), None).await;
+    CheckService::unschedule(&worker, id).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn check_monitor(
+    State(pool): State<DbPool>,
+    State(worker): State<Arc<WorkerService>>,
+    State(stream): State<Arc<StatusStream>>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    WithRejection(Query(query), _): WithRejection<Query<CheckQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let timeout = query.timeout.unwrap_or(DEFAULT_CHECK_WAIT);
+    if timeout > MAX_CHECK_WAIT {
+        return Err(ApiError::BadRequest(format!("timeout must be at most {} seconds", MAX_CHECK_WAIT)));
     }
+    let wait = query.wait.then(|| Duration::from_secs(timeout));
+
+    Ok(match CheckService::check_now(&pool, &worker, &stream, id, wait).await? {
+        CheckNow::Completed(event) => (StatusCode::OK, version.json(&event)),
+        CheckNow::Queued(queued) => (StatusCode::ACCEPTED, version.json(&queued)),
+    })
+}
+
+async fn dry_run_check(
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Json(config), _): WithRejection<Json<CreateMonitor>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let outcome = CheckService::dry_run(&config).await?;
+    Ok(version.json(&outcome))
 }
 
 async fn record_event(
     State(pool): State<DbPool>,
-    Path(id): Path<i32>,
-    Json(mut event): Json<CreateStatusEvent>,
-) -> impl IntoResponse {
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    WithRejection(Json(mut event), _): WithRejection<Json<CreateStatusEvent>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Check up front so an unknown monitor is a 404 rather than a foreign key failure
+    MonitorService::get_monitor(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
     event.monitor_id = id;
-    match MonitorService::record_status_event(&pool, event).await {
-        Ok(event) => (StatusCode::CREATED, Json(event)).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    let event = MonitorService::record_status_event(&pool, event).await?;
+    Ok((StatusCode::CREATED, version.json(&event)))
+}
+
+async fn list_events(
+    State(pool): State<DbPool>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<EventListQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    MonitorService::get_monitor(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    validate_range(query.from, query.to)?;
+
+    let filter = StatusEventFilter {
+        monitor_id: Some(id),
+        status: query.status,
+        from: query.from,
+        to: query.to,
+    };
+    let page = PageRequest {
+        limit: query.limit,
+        cursor: query.cursor,
+        direction: query.order,
+    };
+
+    let events = MonitorService::list_status_events(&pool, &filter, query.sort, &page).await?;
+    Ok(Paginated::new(events, uri))
+}
+
+async fn export_events(
+    State(pool): State<DbPool>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    WithRejection(Query(query), _): WithRejection<Query<EventExportQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    MonitorService::get_monitor(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    validate_range(query.from, query.to)?;
+
+    let filter = StatusEventFilter {
+        monitor_id: Some(id),
+        status: query.status,
+        from: query.from,
+        to: query.to,
+    };
+    let disposition = format!(
+        "attachment; filename=\"monitor-{}-events.{}\"",
+        id,
+        query.format.extension()
+    );
+
+    let body = ExportService::status_events(pool, filter, query.format);
+    Ok((
+        [
+            (header::CONTENT_TYPE, query.format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    ))
 }
 
-async fn list_incidents(State(pool): State<DbPool>) -> impl IntoResponse {
-    match IncidentRepository::list_active(&pool).await {
-        Ok(incidents) => Json(incidents).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+async fn monitor_stats(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    WithRejection(Query(query), _): WithRejection<Query<StatsQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    MonitorService::get_monitor(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
+    validate_range(query.from, query.to)?;
+
+    let stats = MonitorService::get_stats(&pool, id, query.granularity, query.from, query.to).await?;
+    Ok(version.json(&stats))
+}
+
+async fn list_incidents(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<IncidentListQuery>, ApiError>,
+) -> Result<Response, ApiError> {
+    // Unversioned clients predate pagination and expect the open incidents in an array
+    if version == ApiVersion::Legacy {
+        let incidents = IncidentRepository::list_active(&pool).await?;
+        return Ok(Json(incidents).into_response());
     }
+
+    let (filter, sort, page) = query.into_parts();
+    let incidents = IncidentRepository::list(&pool, &filter, sort, &page).await?;
+    Ok(Paginated::new(incidents, uri).into_response())
 }
 
 async fn create_incident(
     State(pool): State<DbPool>,
-    Json(incident): Json<CreateIncident>,
-) -> impl IntoResponse {
-    match IncidentRepository::create(&pool, incident).await {
-        Ok(incident) => (StatusCode::CREATED, Json(incident)).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Json(incident), _): WithRejection<Json<CreateIncident>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    incident.validate()?;
+
+    let incident = IncidentRepository::create(&pool, incident).await?;
+    AuditService::record(&pool, &audit, AuditAction::Create, "incident", incident.id, None, Some(&incident)).await;
+    Ok((StatusCode::CREATED, version.json(&incident)))
 }
 
 async fn update_incident(
     State(pool): State<DbPool>,
-    Path(id): Path<i32>,
-    Json(update): Json<UpdateIncident>,
-) -> impl IntoResponse {
-    match IncidentRepository::update(&pool, id, update).await {
-        Ok(incident) => Json(incident).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+    WithRejection(Json(update), _): WithRejection<Json<UpdateIncident>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    update.validate()?;
+    let before = IncidentRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
+    let incident = IncidentRepository::update(&pool, id, update).await?;
+    AuditService::record(&pool, &audit, AuditAction::Update, "incident", id, Some(&before), Some(&incident)).await;
+    Ok(version.json(&incident))
 }
 
 async fn delete_incident(
     State(pool): State<DbPool>,
-    Path(id): Path<i32>,
-) -> impl IntoResponse {
-    match IncidentRepository::delete(&pool, id).await {
-        Ok(_) => StatusCode::NO_CONTENT.into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    let before = IncidentRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
+    IncidentRepository::delete(&pool, id).await?;
+    AuditService::record(&pool, &audit, AuditAction::Delete, "incident", id, Some(&before), None).await;
+    Ok(StatusCode::NO_CONTENT)
 }
 
 async fn receive_heartbeat(
     State(pool): State<DbPool>,
-    Path(monitor_id): Path<String>,
-    Json(heartbeat): Json<HeartbeatRequest>,
-) -> impl IntoResponse {
-    // Parse monitor ID
-    let monitor_id = match monitor_id.parse::<i32>() {
-        Ok(id) => id,
-        Err(_) => {
-            return Json(HeartbeatResponse {
-                success: false,
-                timestamp: OffsetDateTime::now_utc().to_string(),
-                message: Some("Invalid monitor ID".to_string()),
-            }).into_response();
-        }
-    };
+    version: ApiVersion,
+    WithRejection(Path(monitor_id), _): WithRejection<Path<i32>, ApiError>,
+    WithRejection(Json(heartbeat), _): WithRejection<Json<HeartbeatRequest>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    MonitorService::get_monitor(&pool, monitor_id).await?.ok_or(ApiError::NotFound)?;
 
     // Record the heartbeat as a status event
     let event = CreateStatusEvent {
@@ -199,21 +503,122 @@ async fn receive_heartbeat(
         error_message: Some("Heartbeat received".to_string()),
         metadata: heartbeat.metadata,
     };
+    MonitorService::record_status_event(&pool, event).await?;
 
-    match MonitorService::record_status_event(&pool, event).await {
-        Ok(_) => {
-            Json(HeartbeatResponse {
-                success: true,
-                timestamp: OffsetDateTime::now_utc().to_string(),
-                message: Some("Heartbeat recorded successfully".to_string()),
-            }).into_response()
-        }
-        Err(e) => {
-            Json(HeartbeatResponse {
-                success: false,
-                timestamp: OffsetDateTime::now_utc().to_string(),
-                message: Some(format!("Failed to record heartbeat: {}", e)),
-            }).into_response()
-        }
+    Ok(version.json(&HeartbeatResponse {
+        success: true,
+        timestamp: OffsetDateTime::now_utc().to_string(),
+        message: Some("Heartbeat recorded successfully".to_string()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{session_cookie, with_sign_in, TestState};
+    use db_core::notifications::{status_change, Change};
+    use monitor_checks::schedule;
+    use serde_json::Value;
+
+    async fn serve(state: TestState) -> String {
+        let app = with_sign_in(api_router::<TestState>().with_state(state));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn dry_runs_need_a_session_and_stay_off_internal_addresses() {
+        let base = serve(TestState::offline()).await;
+        let client = reqwest::Client::new();
+        let config = json!({
+            "name": "dry-run",
+            "display_name": "Dry run",
+            "url": "tcp://127.0.0.1:5432",
+            "monitor_type": "tcp",
+            "check_interval": 60,
+            "timeout": 2,
+            "is_active": true,
+        });
+
+        let anonymous = client.post(format!("{}/monitors/check", base)).json(&config).send().await.unwrap();
+        assert_eq!(anonymous.status(), 401);
+
+        let cookie = session_cookie(&client.get(format!("{}/login", base)).send().await.unwrap());
+        let signed_in = client
+            .post(format!("{}/monitors/check", base))
+            .header("cookie", cookie)
+            .json(&config)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(signed_in.status(), 200);
+        let outcome: Value = signed_in.json().await.unwrap();
+        assert_eq!(outcome["status"], "major_outage");
+        assert_eq!(outcome["error_message"], "127.0.0.1 is not a public address");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn checks_answer_with_their_event_or_the_queued_job() {
+        let state = TestState::connect().await;
+        let monitor_id: i32 = sqlx::query_scalar(
+            r#"
+            INSERT INTO monitors (name, display_name, url, monitor_type, is_active)
+            VALUES ('check-now-test', 'Check now', 'https://example.com', 'http', TRUE)
+            RETURNING id
+            "#,
+        )
+        .fetch_one(state.pool.as_ref())
+        .await
+        .unwrap();
+        let stream = state.stream.clone();
+        let base = serve(state.clone()).await;
+        let check = |query: &'static str| reqwest::Client::new().post(format!("{}/monitors/{}/check{}", base, monitor_id, query)).send();
+
+        // Without waiting, and when no check reports back in time, the job is returned
+        let queued = check("").await.unwrap();
+        assert_eq!(queued.status(), 202);
+        let timed_out = check("?wait=true&timeout=1").await.unwrap();
+        assert_eq!(timed_out.status(), 202);
+        let job: Value = timed_out.json().await.unwrap();
+        assert_eq!(job["monitor_id"], monitor_id);
+        assert!(job["job_id"].is_string());
+
+        // The event the queued check records is returned instead, not one
+        // another check records while the request waits
+        let pool = state.pool.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let job_id: i64 = sqlx::query_scalar(
+                "SELECT id FROM graphile_worker._private_jobs WHERE payload->>'monitor_id' = $1::TEXT ORDER BY id DESC LIMIT 1",
+            )
+            .bind(monitor_id)
+            .fetch_one(pool.as_ref())
+            .await
+            .unwrap();
+            stream.publish(status_change(monitor_id, "major_outage", Some("operational")));
+            let mut ours = status_change(monitor_id, "degraded", Some("major_outage"));
+            if let Change::StatusEvent { event, .. } = &mut ours {
+                event.metadata = Some(json!({ schedule::EVENT_JOB_ID: job_id.to_string() }));
+            }
+            stream.publish(ours);
+        });
+        let completed = check("?wait=true&timeout=5").await.unwrap();
+        assert_eq!(completed.status(), 200);
+        let event: Value = completed.json().await.unwrap();
+
+        let too_long = check("?wait=true&timeout=121").await.unwrap();
+        assert_eq!(too_long.status(), 400);
+
+        sqlx::query("DELETE FROM graphile_worker._private_jobs WHERE payload->>'monitor_id' = $1::TEXT")
+            .bind(monitor_id)
+            .execute(state.pool.as_ref())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM monitors WHERE id = $1").bind(monitor_id).execute(state.pool.as_ref()).await.unwrap();
+        assert_eq!(event["monitor_id"], monitor_id);
+        assert_eq!(event["status"], "degraded");
+    }
+}
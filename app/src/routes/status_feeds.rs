@@ -0,0 +1,90 @@
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+use status_page::statuspage::PageInfo;
+use status_page::{feed, StatusPageData, StatusPageFilter};
+use tower_sessions::Session;
+use db_core::DbPool;
+use db_core::repositories::StatusPageRepository;
+use crate::error::ApiError;
+use crate::services::audit_service::AuditContext;
+use crate::services::monitor_service::MonitorService;
+use crate::services::status_page_service::StatusPageService;
+
+/// The feeds served for each page, relative to the page
+pub const FEEDS: [&str; 3] = ["/feed.atom", "/feed.rss", "/maintenance.ics"];
+
+/// Incidents in the Atom and RSS feeds
+const FEED_INCIDENTS: i64 = 50;
+
+#[derive(Clone)]
+struct StatusFeeds {
+    pool: DbPool,
+    /// For the links in each feed
+    app_url: Arc<str>,
+}
+
+/// Incident feeds and the maintenance calendar for the default page under
+/// `/status` and for each configured page under `/s/{slug}`
+pub fn router<S>(pool: DbPool, app_url: &str) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let state = StatusFeeds {
+        pool,
+        app_url: app_url.into(),
+    };
+
+    Router::new()
+        .route("/status/{feed}", get(default_page))
+        .route("/s/{slug}/{feed}", get(configured_page))
+        .with_state(state)
+}
+
+async fn default_page(State(feeds): State<StatusFeeds>, Path(feed): Path<String>) -> Result<Response, ApiError> {
+    let filter = StatusPageFilter::default();
+    let data = MonitorService::get_status_page_data(&feeds.pool, &filter).await?;
+    let info = StatusPageService::default_info(&feeds.app_url, data.last_updated);
+
+    render(&feeds.pool, &feed, &info, &filter, &data).await
+}
+
+async fn configured_page(
+    State(feeds): State<StatusFeeds>,
+    Path((slug, feed)): Path<(String, String)>,
+    session: Session,
+    audit: AuditContext,
+) -> Result<Response, ApiError> {
+    let page = StatusPageRepository::find_by_slug(&feeds.pool, &slug).await?.ok_or(ApiError::NotFound)?;
+    let data = StatusPageService::load_for(&feeds.pool, &page, &session, &audit).await?;
+    let info = StatusPageService::info(&page, &feeds.app_url, data.last_updated);
+
+    render(&feeds.pool, &feed, &info, &StatusPageFilter::from(&page), &data).await
+}
+
+async fn render(
+    pool: &DbPool,
+    feed: &str,
+    page: &PageInfo,
+    filter: &StatusPageFilter,
+    data: &StatusPageData,
+) -> Result<Response, ApiError> {
+    let (content_type, body) = match feed {
+        "feed.atom" => {
+            let incidents = status_page::incident_history(pool, filter, data, FEED_INCIDENTS).await?;
+            ("application/atom+xml; charset=utf-8", feed::atom(page, data, &incidents))
+        }
+        "feed.rss" => {
+            let incidents = status_page::incident_history(pool, filter, data, FEED_INCIDENTS).await?;
+            ("application/rss+xml; charset=utf-8", feed::rss(page, data, &incidents))
+        }
+        "maintenance.ics" => ("text/calendar; charset=utf-8", feed::calendar(page, data)),
+        _ => return Err(ApiError::NotFound),
+    };
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
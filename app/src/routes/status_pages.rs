@@ -0,0 +1,271 @@
+use axum::{
+    extract::{OriginalUri, Path, Query, Request, State},
+    http::{header, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use axum_extra::extract::WithRejection;
+use axum_inertia::Inertia;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use tower_sessions::Session;
+use tracing::error;
+use db_core::DbPool;
+use db_core::models::audit_log::AuditAction;
+use db_core::models::status_page::{CreateStatusPage, StatusPage, UpdateStatusPage};
+use db_core::pagination::{PageRequest, SortDirection};
+use db_core::repositories::StatusPageRepository;
+use crate::api_version::ApiVersion;
+use crate::error::ApiError;
+use crate::pagination::Paginated;
+use crate::routes::{status_feeds, statuspage_api};
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::services::status_page_service::{PageAccess, StatusPageService};
+use crate::validation::{validate_status_page_update, Validate};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct StatusPageListQuery {
+    order: Option<SortDirection>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnlockRequest {
+    password: String,
+}
+
+/// Host names other than the app's own that may serve a status page
+#[derive(Clone)]
+pub struct CustomDomains {
+    pool: DbPool,
+    /// The app's own host, which never serves a custom domain
+    app_host: Arc<str>,
+}
+
+impl CustomDomains {
+    pub fn new(pool: DbPool, app_url: &str) -> Self {
+        let app_host = app_url
+            .parse::<Uri>()
+            .ok()
+            .and_then(|uri| uri.host().map(str::to_ascii_lowercase))
+            .unwrap_or_default();
+        Self { pool, app_host: app_host.into() }
+    }
+}
+
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+    axum_inertia::InertiaConfig: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/s/{slug}", get(show_page))
+        .route("/s/{slug}/unlock", post(unlock_page))
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    DbPool: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/status-pages", get(list_status_pages).post(create_status_page))
+        .route("/status-pages/{id}", get(get_status_page).put(update_status_page).delete(delete_status_page))
+}
+
+/// Serve a page's custom domain at `/` by routing the request to the page's
+/// `/s/{slug}`, and its feeds and Statuspage documents at their paths under
+/// it. Wraps the whole router, since a layer added with `Router::layer` runs
+/// after the route has been picked.
+pub async fn serve_custom_domains(State(domains): State<CustomDomains>, mut request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    if path != "/" && !statuspage_api::DOCUMENTS.contains(&path) && !status_feeds::FEEDS.contains(&path) {
+        return next.run(request).await;
+    }
+
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(|host| host.rsplit_once(':').map_or(host, |(name, _port)| name).to_ascii_lowercase());
+    let Some(host) = host.filter(|host| *host != *domains.app_host) else {
+        return next.run(request).await;
+    };
+
+    match StatusPageRepository::find_by_domain(&domains.pool, &host).await {
+        Ok(Some(page)) => {
+            let document = request.uri().path().trim_start_matches('/');
+            let path = match (document, request.uri().query()) {
+                ("", Some(query)) => format!("/s/{}?{}", page.slug, query),
+                ("", None) => format!("/s/{}", page.slug),
+                (document, _) => format!("/s/{}/{}", page.slug, document),
+            };
+            match path.parse() {
+                Ok(uri) => *request.uri_mut() = uri,
+                Err(e) => error!("Status page path {} is not a valid URI: {}", path, e),
+            }
+        }
+        Ok(None) => {}
+        Err(e) => error!("Failed to look up the status page for {}: {}", host, e),
+    }
+
+    next.run(request).await
+}
+
+async fn show_page(
+    State(pool): State<DbPool>,
+    Path(slug): Path<String>,
+    session: Session,
+    audit: AuditContext,
+    inertia: Inertia,
+) -> Result<Response, ApiError> {
+    let page = StatusPageRepository::find_by_slug(&pool, &slug).await?.ok_or(ApiError::NotFound)?;
+
+    match StatusPageService::access(&page, &session, &audit).await {
+        PageAccess::Granted => {}
+        PageAccess::SignIn => return Ok(Redirect::to("/auth/login").into_response()),
+        PageAccess::Locked => return Ok(locked(inertia, &page, None)),
+    }
+
+    let data = StatusPageService::load(&pool, &page).await?;
+    Ok(inertia
+        .render("EnhancedStatusPage", json!({
+            "statusData": data,
+            "page": branding(&page),
+        }))
+        .into_response())
+}
+
+async fn unlock_page(
+    State(pool): State<DbPool>,
+    Path(slug): Path<String>,
+    session: Session,
+    inertia: Inertia,
+    WithRejection(Json(request), _): WithRejection<Json<UnlockRequest>, ApiError>,
+) -> Result<Response, ApiError> {
+    let page = StatusPageRepository::find_by_slug(&pool, &slug).await?.ok_or(ApiError::NotFound)?;
+
+    if StatusPageService::unlock(&page, &session, &request.password).await? {
+        Ok(Redirect::to(&format!("/s/{}", page.slug)).into_response())
+    } else {
+        Ok(locked(inertia, &page, Some("Incorrect password")))
+    }
+}
+
+fn locked(inertia: Inertia, page: &StatusPage, error: Option<&str>) -> Response {
+    inertia
+        .render("StatusPageLocked", json!({
+            "page": branding(page),
+            "error": error,
+        }))
+        .into_response()
+}
+
+/// What the frontend needs to brand a page, without its settings
+fn branding(page: &StatusPage) -> serde_json::Value {
+    json!({
+        "slug": page.slug,
+        "title": page.title,
+        "description": page.description,
+        "logo_url": page.logo_url,
+    })
+}
+
+async fn list_status_pages(
+    State(pool): State<DbPool>,
+    OriginalUri(uri): OriginalUri,
+    WithRejection(Query(query), _): WithRejection<Query<StatusPageListQuery>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = PageRequest {
+        limit: query.limit,
+        cursor: query.cursor,
+        direction: query.order,
+    };
+    let pages = StatusPageRepository::list(&pool, &page).await?;
+    Ok(Paginated::new(pages, uri))
+}
+
+async fn create_status_page(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    audit: AuditContext,
+    WithRejection(Json(page), _): WithRejection<Json<CreateStatusPage>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    page.validate()?;
+
+    let page = StatusPageService::create(&pool, page).await?;
+    AuditService::record(&pool, &audit, AuditAction::Create, "status_page", page.id, None, Some(&page)).await;
+    Ok((StatusCode::CREATED, version.json(&page)))
+}
+
+async fn get_status_page(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = StatusPageRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    Ok(version.json(&page))
+}
+
+async fn update_status_page(
+    State(pool): State<DbPool>,
+    version: ApiVersion,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+    WithRejection(Json(update), _): WithRejection<Json<UpdateStatusPage>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = StatusPageRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+    validate_status_page_update(&before, &update)?;
+
+    let page = StatusPageService::update(&pool, id, update).await?;
+    AuditService::record(&pool, &audit, AuditAction::Update, "status_page", id, Some(&before), Some(&page)).await;
+    Ok(version.json(&page))
+}
+
+async fn delete_status_page(
+    State(pool): State<DbPool>,
+    WithRejection(Path(id), _): WithRejection<Path<i32>, ApiError>,
+    audit: AuditContext,
+) -> Result<impl IntoResponse, ApiError> {
+    audit.require_user()?;
+    let before = StatusPageRepository::find_by_id(&pool, id).await?.ok_or(ApiError::NotFound)?;
+
+    StatusPageRepository::delete(&pool, id).await?;
+    AuditService::record(&pool, &audit, AuditAction::Delete, "status_page", id, Some(&before), None).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{with_sign_in, TestState};
+
+    #[tokio::test]
+    async fn changing_pages_needs_a_session() {
+        let app = with_sign_in(api_router::<TestState>().with_state(TestState::offline()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let client = reqwest::Client::new();
+        let page = json!({ "slug": "public", "title": "Public", "visibility": "public" });
+
+        let requests = [
+            client.post(format!("{}/status-pages", base)).json(&page),
+            client.put(format!("{}/status-pages/1", base)).json(&page),
+            client.delete(format!("{}/status-pages/1", base)),
+        ];
+        for request in requests {
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status(), 401, "{}", response.url());
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use std::sync::Arc;
+use status_page::statuspage::{self, PageInfo};
+use status_page::{StatusPageData, StatusPageFilter};
+use tower_http::set_header::SetResponseHeaderLayer;
+use tower_sessions::Session;
+use db_core::DbPool;
+use db_core::repositories::StatusPageRepository;
+use crate::error::ApiError;
+use crate::services::audit_service::AuditContext;
+use crate::services::monitor_service::MonitorService;
+use crate::services::status_page_service::StatusPageService;
+
+/// The Statuspage documents served for each page, relative to the page
+pub const DOCUMENTS: [&str; 3] = ["/api/v2/summary.json", "/api/v2/status.json", "/api/v2/incidents.json"];
+
+/// As many incidents as Statuspage lists in `incidents.json`
+const INCIDENT_HISTORY: i64 = 50;
+
+#[derive(Clone)]
+struct StatuspageApi {
+    pool: DbPool,
+    /// For the links in each document
+    app_url: Arc<str>,
+}
+
+/// Atlassian Statuspage-compatible JSON for the default page at
+/// `/status/api/v2/*.json` and for each configured page under `/s/{slug}`.
+/// Widgets fetch these from other origins, so any origin may read them.
+pub fn router<S>(pool: DbPool, app_url: &str) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let state = StatuspageApi {
+        pool,
+        app_url: app_url.into(),
+    };
+
+    Router::new()
+        .route("/status/api/v2/{document}", get(default_page))
+        .route("/s/{slug}/api/v2/{document}", get(configured_page))
+        .layer(SetResponseHeaderLayer::overriding(
+            header::ACCESS_CONTROL_ALLOW_ORIGIN,
+            HeaderValue::from_static("*"),
+        ))
+        .with_state(state)
+}
+
+async fn default_page(
+    State(api): State<StatuspageApi>,
+    Path(document): Path<String>,
+) -> Result<Response, ApiError> {
+    let filter = StatusPageFilter::default();
+    let data = MonitorService::get_status_page_data(&api.pool, &filter).await?;
+    let info = StatusPageService::default_info(&api.app_url, data.last_updated);
+
+    render(&api.pool, &document, info, &filter, &data).await
+}
+
+async fn configured_page(
+    State(api): State<StatuspageApi>,
+    Path((slug, document)): Path<(String, String)>,
+    session: Session,
+    audit: AuditContext,
+) -> Result<Response, ApiError> {
+    let page = StatusPageRepository::find_by_slug(&api.pool, &slug).await?.ok_or(ApiError::NotFound)?;
+    let data = StatusPageService::load_for(&api.pool, &page, &session, &audit).await?;
+    let info = StatusPageService::info(&page, &api.app_url, data.last_updated);
+
+    render(&api.pool, &document, info, &StatusPageFilter::from(&page), &data).await
+}
+
+async fn render(
+    pool: &DbPool,
+    document: &str,
+    page: PageInfo,
+    filter: &StatusPageFilter,
+    data: &StatusPageData,
+) -> Result<Response, ApiError> {
+    let response = match document {
+        "summary.json" => Json(statuspage::summary(page, data)).into_response(),
+        "status.json" => Json(statuspage::page_status(page, data)).into_response(),
+        "incidents.json" => {
+            let history = status_page::incident_history(pool, filter, data, INCIDENT_HISTORY).await?;
+            Json(statuspage::incidents(page, data, &history)).into_response()
+        }
+        _ => return Err(ApiError::NotFound),
+    };
+    Ok(response)
+}
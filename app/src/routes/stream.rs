@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+    Router,
+};
+use axum_extra::extract::WithRejection;
+use futures::stream::{Stream, StreamExt};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+use crate::error::ApiError;
+use crate::services::stream_service::StatusStream;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct StatusStreamQuery {
+    /// Comma-separated monitor IDs; all monitors when omitted
+    monitor_id: Option<String>,
+}
+
+/// JSON API routes, mounted under each version prefix by `routes::api`
+pub fn api_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    Arc<StatusStream>: axum::extract::FromRef<S>,
+{
+    Router::new()
+        .route("/stream/status", get(status_stream))
+}
+
+async fn status_stream(
+    State(stream): State<Arc<StatusStream>>,
+    headers: HeaderMap,
+    WithRejection(Query(query), _): WithRejection<Query<StatusStreamQuery>, ApiError>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let monitor_ids = match query.monitor_id.as_deref().filter(|ids| !ids.is_empty()) {
+        Some(ids) => ids
+            .split(',')
+            .map(|id| id.trim().parse::<i32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ApiError::BadRequest("monitor_id must be a comma-separated list of integers".to_string()))?,
+        None => Vec::new(),
+    };
+
+    // Sent by EventSource when it reconnects; an unparseable value is ignored
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    let events = stream.subscribe(monitor_ids, last_event_id).map(|event| {
+        Ok(Event::default()
+            .id(event.id.to_string())
+            .event(event.kind)
+            .data(&event.data))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
@@ -0,0 +1,242 @@
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use db_core::error::DbError;
+use db_core::models::audit_log::{
+    ActorType, AuditAction, AuditLogEntry, AuditLogFilter, CreateAuditLogEntry,
+};
+use db_core::repositories::AuditLogRepository;
+use db_core::DbPool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tower_sessions::Session;
+use tracing::error;
+
+use crate::error::ApiError;
+
+/// Fields that change on every write and would only add noise to a diff
+const IGNORED_DIFF_FIELDS: &[&str] = &["updated_at"];
+
+/// Who performed a request and from where, resolved from the session and
+/// the connection. Anything else a client sends about itself is unverified,
+/// so it is neither trusted as an actor nor as an address.
+#[derive(Debug, Clone)]
+pub struct AuditContext {
+    pub actor_type: ActorType,
+    pub actor_id: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl AuditContext {
+    /// Refuse the request unless it comes from a signed-in user
+    pub fn require_user(&self) -> Result<(), ApiError> {
+        if self.actor_type != ActorType::User {
+            return Err(ApiError::Unauthorized);
+        }
+        Ok(())
+    }
+}
+
+impl<S> FromRequestParts<S> for AuditContext
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let ip_address = parts.extensions.get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(peer)| {
+            let trusted = parts.extensions.get::<TrustedProxies>().map_or(&[][..], |proxies| &proxies.0);
+            client_ip(peer.ip(), &parts.headers, trusted).to_string()
+        });
+
+        if let Some(session) = parts.extensions.get::<Session>().cloned() {
+            if let Ok(Some(user_id)) = session.get::<i32>("user_id").await {
+                return Ok(Self {
+                    actor_type: ActorType::User,
+                    actor_id: Some(user_id.to_string()),
+                    ip_address,
+                });
+            }
+        }
+
+        Ok(Self {
+            actor_type: ActorType::Anonymous,
+            actor_id: None,
+            ip_address,
+        })
+    }
+}
+
+/// The reverse proxies from `server.trusted_proxies`, added to every request
+/// as an extension
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(pub Arc<[IpAddr]>);
+
+/// The address a request came from. Forwarding headers only count when the
+/// connection is from a trusted proxy; then the client is the nearest
+/// address in `X-Forwarded-For` that is not one of the proxies.
+fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted: &[IpAddr]) -> IpAddr {
+    if !trusted.contains(&peer) {
+        return peer;
+    }
+
+    let forwarded: Vec<IpAddr> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect();
+    if let Some(ip) = forwarded.iter().rev().find(|ip| !trusted.contains(ip)) {
+        return *ip;
+    }
+
+    headers
+        .get("x-real-ip")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|ip| ip.trim().parse().ok())
+        .unwrap_or(peer)
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AuditLogPage {
+    pub entries: Vec<AuditLogEntry>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+pub struct AuditService;
+
+impl AuditService {
+    /// Append an entry for a mutation. Failures are logged rather than
+    /// surfaced so that a broken audit insert never masks the change itself.
+    pub async fn record<T: Serialize>(
+        pool: &DbPool,
+        ctx: &AuditContext,
+        action: AuditAction,
+        entity_type: &str,
+        entity_id: impl ToString,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) {
+        let before_state = before.and_then(|b| serde_json::to_value(b).ok());
+        let after_state = after.and_then(|a| serde_json::to_value(a).ok());
+        let changes = diff_states(before_state.as_ref(), after_state.as_ref());
+
+        let entry = CreateAuditLogEntry {
+            actor_type: ctx.actor_type.clone().into(),
+            actor_id: ctx.actor_id.clone(),
+            action: action.into(),
+            entity_type: entity_type.to_string(),
+            entity_id: Some(entity_id.to_string()),
+            before_state,
+            after_state,
+            changes,
+            ip_address: ctx.ip_address.clone(),
+        };
+
+        if let Err(e) = AuditLogRepository::create(pool, entry).await {
+            error!("Failed to write audit log entry for {}: {}", entity_type, e);
+        }
+    }
+
+    pub async fn list(
+        pool: &DbPool,
+        filter: &AuditLogFilter,
+        page: i64,
+        per_page: i64,
+    ) -> Result<AuditLogPage, DbError> {
+        let page = page.max(1);
+        let per_page = per_page.clamp(1, 200);
+
+        let entries = AuditLogRepository::list(pool, filter, per_page, (page - 1) * per_page).await?;
+        let total = AuditLogRepository::count(pool, filter).await?;
+
+        Ok(AuditLogPage {
+            entries,
+            total,
+            page,
+            per_page,
+        })
+    }
+}
+
+/// Field-level diff between two JSON snapshots:
+/// `{ "field": { "before": ..., "after": ... } }` for every field that changed.
+pub fn diff_states(before: Option<&Value>, after: Option<&Value>) -> Option<Value> {
+    let empty = Map::new();
+    let before_fields = before.and_then(Value::as_object).unwrap_or(&empty);
+    let after_fields = after.and_then(Value::as_object).unwrap_or(&empty);
+
+    let mut changes = Map::new();
+    let keys = before_fields.keys().chain(after_fields.keys());
+
+    for key in keys {
+        if IGNORED_DIFF_FIELDS.contains(&key.as_str()) || changes.contains_key(key) {
+            continue;
+        }
+
+        let old = before_fields.get(key).unwrap_or(&Value::Null);
+        let new = after_fields.get(key).unwrap_or(&Value::Null);
+        if old != new {
+            changes.insert(
+                key.clone(),
+                serde_json::json!({ "before": old, "after": new }),
+            );
+        }
+    }
+
+    if changes.is_empty() {
+        None
+    } else {
+        Some(Value::Object(changes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_reports_only_changed_fields() {
+        let before = json!({ "name": "api", "check_interval": 60, "updated_at": "a" });
+        let after = json!({ "name": "api", "check_interval": 30, "updated_at": "b" });
+
+        let diff = diff_states(Some(&before), Some(&after)).unwrap();
+
+        assert_eq!(diff, json!({ "check_interval": { "before": 60, "after": 30 } }));
+    }
+
+    #[test]
+    fn diff_of_delete_lists_every_field() {
+        let before = json!({ "name": "api", "is_active": true });
+
+        let diff = diff_states(Some(&before), None).unwrap();
+
+        assert_eq!(diff["name"], json!({ "before": "api", "after": null }));
+        assert_eq!(diff["is_active"], json!({ "before": true, "after": null }));
+    }
+
+    #[test]
+    fn forwarding_headers_count_only_from_trusted_proxies() {
+        let ip = |ip: &str| ip.parse::<IpAddr>().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.9, 198.51.100.7, 10.0.0.2".parse().unwrap());
+        headers.insert("x-real-ip", "192.0.2.1".parse().unwrap());
+
+        // Anyone else gets the address they connected from
+        assert_eq!(client_ip(ip("198.51.100.50"), &headers, &[]), ip("198.51.100.50"));
+        // Behind the proxies, the nearest untrusted hop; earlier hops could be forged
+        let trusted = [ip("10.0.0.1"), ip("10.0.0.2")];
+        assert_eq!(client_ip(ip("10.0.0.1"), &headers, &trusted), ip("198.51.100.7"));
+
+        headers.remove("x-forwarded-for");
+        assert_eq!(client_ip(ip("10.0.0.1"), &headers, &trusted), ip("192.0.2.1"));
+    }
+}
@@ -0,0 +1,245 @@
+use db_core::models::badge_token::{BadgeSubject, BadgeToken};
+use db_core::models::status_event::MonitorStatusSummary;
+use db_core::repositories::{BadgeTokenRepository, MonitorRepository, ServiceGroupRepository, StatusEventRepository};
+use db_core::DbPool;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use status_page::{GroupWithStatus, StatusPageFilter};
+
+use crate::error::ApiError;
+
+/// Characters in a badge token, for about 238 bits of randomness
+const TOKEN_LENGTH: usize = 40;
+
+/// The uptime windows a badge can show, as kept in [`MonitorStatusSummary`]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+pub enum UptimeWindow {
+    #[serde(rename = "24h")]
+    Day,
+    #[serde(rename = "7d")]
+    Week,
+    #[serde(rename = "30d")]
+    Month,
+    #[serde(rename = "90d")]
+    Quarter,
+}
+
+impl UptimeWindow {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UptimeWindow::Day => "24h",
+            UptimeWindow::Week => "7d",
+            UptimeWindow::Month => "30d",
+            UptimeWindow::Quarter => "90d",
+        }
+    }
+
+    fn of(self, summary: &MonitorStatusSummary) -> f64 {
+        match self {
+            UptimeWindow::Day => summary.uptime_24h,
+            UptimeWindow::Week => summary.uptime_7d,
+            UptimeWindow::Month => summary.uptime_30d,
+            UptimeWindow::Quarter => summary.uptime_90d,
+        }
+    }
+}
+
+/// What a badge shows about its monitor or group
+#[derive(Debug)]
+pub struct BadgeState {
+    pub name: String,
+    pub status: String,
+    /// Over the window asked for; `None` when there was none, or for a
+    /// group without active monitors
+    pub uptime: Option<f64>,
+    /// Whether the badge needed a token
+    pub private: bool,
+}
+
+pub struct BadgeService;
+
+impl BadgeService {
+    /// The badge for `subject`, or `None` when it does not exist or is
+    /// private and `token` is not its token. Badges never made private or
+    /// public are shown only for what the default status page shows: an
+    /// active monitor listed by a public page or by no page, or a group of
+    /// such monitors.
+    pub async fn load(
+        pool: &DbPool,
+        subject: BadgeSubject,
+        token: Option<&str>,
+        window: Option<UptimeWindow>,
+    ) -> Result<Option<BadgeState>, ApiError> {
+        let (private, by_default) = match BadgeTokenRepository::find(pool, subject).await? {
+            Some(setting) if setting.private => {
+                let unlocked = token
+                    .zip(setting.token.as_deref())
+                    .is_some_and(|(token, expected)| same_token(token, expected));
+                if !unlocked {
+                    return Ok(None);
+                }
+                (true, false)
+            }
+            Some(_) => (false, false),
+            None => (false, true),
+        };
+
+        match subject {
+            BadgeSubject::Monitor(id) => {
+                let Some(monitor) = MonitorRepository::find_by_id(pool, id).await? else {
+                    return Ok(None);
+                };
+                if by_default && !status_page::public_monitor_ids(pool).await?.contains(&id) {
+                    return Ok(None);
+                }
+                let summary = StatusEventRepository::get_monitor_summary(pool, id).await?;
+
+                Ok(Some(BadgeState {
+                    name: monitor.display_name,
+                    uptime: window.map(|window| window.of(&summary)),
+                    status: summary.current_status,
+                    private,
+                }))
+            }
+            BadgeSubject::ServiceGroup(id) => {
+                let Some(group) = ServiceGroupRepository::find_by_id(pool, id).await? else {
+                    return Ok(None);
+                };
+                let filter = StatusPageFilter {
+                    service_group_ids: vec![id],
+                    ..Default::default()
+                };
+                let data = status_page::load(pool, &filter).await?;
+                if by_default {
+                    let public = status_page::public_monitor_ids(pool).await?;
+                    if data.monitors.is_empty() || data.monitors.iter().any(|entry| !public.contains(&entry.monitor.id)) {
+                        return Ok(None);
+                    }
+                }
+                let status = find_group(&data.groups, id)
+                    .map_or_else(|| "unknown".to_string(), |group| group.overall_status.clone());
+
+                // Monitors count toward the group's uptime by their weight
+                let mut uptime = None;
+                if let Some(window) = window {
+                    let (mut weighted, mut total) = (0.0, 0.0);
+                    for entry in &data.monitors {
+                        let summary = StatusEventRepository::get_monitor_summary(pool, entry.monitor.id).await?;
+                        weighted += window.of(&summary) * f64::from(entry.monitor.weight);
+                        total += f64::from(entry.monitor.weight);
+                    }
+                    uptime = (total > 0.0).then(|| weighted / total);
+                }
+
+                Ok(Some(BadgeState {
+                    name: group.name,
+                    status,
+                    uptime,
+                    private,
+                }))
+            }
+        }
+    }
+
+    /// Make the subject's badge private with a new token, replacing any
+    /// token it had
+    pub async fn issue_token(pool: &DbPool, subject: BadgeSubject) -> Result<BadgeToken, ApiError> {
+        ensure_exists(pool, subject).await?;
+
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+        Ok(BadgeTokenRepository::replace(pool, subject, &token).await?)
+    }
+
+    /// Make the subject's badge private or public, overriding the default
+    pub async fn set_private(pool: &DbPool, subject: BadgeSubject, private: bool) -> Result<BadgeToken, ApiError> {
+        ensure_exists(pool, subject).await?;
+        Ok(BadgeTokenRepository::set_private(pool, subject, private).await?)
+    }
+}
+
+async fn ensure_exists(pool: &DbPool, subject: BadgeSubject) -> Result<(), ApiError> {
+    let exists = match subject {
+        BadgeSubject::Monitor(id) => MonitorRepository::find_by_id(pool, id).await?.is_some(),
+        BadgeSubject::ServiceGroup(id) => ServiceGroupRepository::find_by_id(pool, id).await?.is_some(),
+    };
+    if !exists {
+        return Err(ApiError::NotFound);
+    }
+    Ok(())
+}
+
+fn find_group(groups: &[GroupWithStatus], id: i32) -> Option<&GroupWithStatus> {
+    groups
+        .iter()
+        .find_map(|group| if group.group.id == id { Some(group) } else { find_group(&group.groups, id) })
+}
+
+/// Compare without stopping at the first difference, so response times do
+/// not give a token away
+fn same_token(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_must_match_exactly() {
+        assert!(same_token("abc123", "abc123"));
+        assert!(!same_token("abc124", "abc123"));
+        assert!(!same_token("abc12", "abc123"));
+        assert!(!same_token("", "abc123"));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn revoking_a_token_keeps_the_badge_private() {
+        let pool: DbPool = std::sync::Arc::new(sqlx::PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap());
+        let id: i32 = sqlx::query_scalar(
+            "INSERT INTO monitors (name, display_name, is_active) VALUES ('badge-privacy-test', 'Badge', TRUE) RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+        let subject = BadgeSubject::Monitor(id);
+        let shown = |token: Option<&str>| {
+            let pool = pool.clone();
+            let token = token.map(str::to_string);
+            async move { BadgeService::load(&pool, subject, token.as_deref(), None).await.unwrap().is_some() }
+        };
+
+        // Shown by default, as the default status page lists it
+        assert!(shown(None).await);
+
+        let issued = BadgeService::issue_token(&pool, subject).await.unwrap().token.unwrap();
+        assert!(!shown(None).await);
+        assert!(shown(Some(&issued)).await);
+
+        BadgeTokenRepository::revoke(&pool, subject).await.unwrap();
+        assert!(!shown(None).await);
+        assert!(!shown(Some(&issued)).await);
+
+        let public = BadgeService::set_private(&pool, subject, false).await.unwrap();
+        assert!(!public.private && public.token.is_none());
+        assert!(shown(None).await);
+
+        // Private again, but the old token is gone with the public setting
+        BadgeService::set_private(&pool, subject, true).await.unwrap();
+        assert!(!shown(None).await);
+        assert!(!shown(Some(&issued)).await);
+
+        sqlx::query("DELETE FROM monitors WHERE id = $1").bind(id).execute(&*pool).await.unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+use db_core::models::monitor::{CreateMonitor, Monitor};
+use db_core::models::status_event::StatusEvent;
+use db_core::DbPool;
+use futures::stream::StreamExt;
+use monitor_checks::{schedule, CheckOutcome, CheckTarget, SUPPORTED_TYPES};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::error::ApiError;
+use crate::services::monitor_service::MonitorService;
+use crate::services::stream_service::StatusStream;
+use crate::services::worker::WorkerService;
+use crate::validation::{Validate, ValidationErrors};
+
+/// A check that was queued but had not finished when the response was sent
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct QueuedCheck {
+    pub monitor_id: i32,
+    pub job_id: String,
+}
+
+pub enum CheckNow {
+    Completed(StatusEvent),
+    Queued(QueuedCheck),
+}
+
+pub struct CheckService;
+
+impl CheckService {
+    /// Queue an immediate check of a saved monitor. With `wait`, resolve to
+    /// the status event the queued job records, or to the queued job if it
+    /// records none in time. Events from other checks are never returned.
+    pub async fn check_now(
+        pool: &DbPool,
+        worker: &WorkerService,
+        stream: &StatusStream,
+        monitor_id: i32,
+        wait: Option<Duration>,
+    ) -> Result<CheckNow, ApiError> {
+        let monitor = MonitorService::get_monitor(pool, monitor_id).await?.ok_or(ApiError::NotFound)?;
+
+        // The worker skips both of these, so a queued job would never report back
+        if !monitor.is_active {
+            return Err(ApiError::Conflict("Monitor is paused; resume it before checking".to_string()));
+        }
+        if !SUPPORTED_TYPES.contains(&monitor.monitor_type.as_str()) {
+            return Err(ApiError::Conflict(format!(
+                "{} monitors are reported externally and cannot be checked",
+                monitor.monitor_type
+            )));
+        }
+
+        // Subscribe before queuing so a fast check can't finish unseen
+        let events = stream.subscribe(vec![monitor_id], None);
+
+        let job_id = worker
+            .queue_check(monitor_id)
+            .await
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+        let queued = QueuedCheck { monitor_id, job_id };
+
+        let Some(wait) = wait else {
+            return Ok(CheckNow::Queued(queued));
+        };
+
+        let job_id = queued.job_id.clone();
+        let mut ours = Box::pin(events.filter_map(move |event| {
+            std::future::ready(
+                (event.kind == "status_event")
+                    .then(|| serde_json::from_str::<StatusEvent>(&event.data).ok())
+                    .flatten()
+                    .filter(|event| recorded_by(event, &job_id)),
+            )
+        }));
+        match tokio::time::timeout(wait, ours.next()).await {
+            Ok(Some(event)) => Ok(CheckNow::Completed(event)),
+            Ok(None) | Err(_) => Ok(CheckNow::Queued(queued)),
+        }
+    }
+
+    /// Bring a saved monitor's scheduled check in line with its config after
+    /// it was created (`before` is `None`) or updated. Scheduling is best
+    /// effort: the monitor is already saved, so a failure is only logged and
+    /// the worker's reconciler repairs the schedule later.
+    pub async fn sync_schedule(worker: &WorkerService, monitor: &Monitor, before: Option<&Monitor>) {
+        let result = if !is_checked(monitor) {
+            match before {
+                Some(before) if is_checked(before) => worker.cancel_check(monitor.id).await,
+                _ => Ok(()),
+            }
+        } else if before.is_none_or(|before| schedule_changed(before, monitor)) {
+            // Check the new config right away; the chain continues from there
+            worker.schedule_check(monitor.id).await.map(|_| ())
+        } else {
+            Ok(())
+        };
+
+        if let Err(e) = result {
+            warn!("Failed to update the check schedule of monitor {}: {}", monitor.id, e);
+        }
+    }
+
+    /// Stop checking a deleted monitor
+    pub async fn unschedule(worker: &WorkerService, monitor_id: i32) {
+        if let Err(e) = worker.cancel_check(monitor_id).await {
+            warn!("Failed to cancel the checks of monitor {}: {}", monitor_id, e);
+        }
+    }
+
+    /// Check an unsaved monitor config from the app itself. Nothing is
+    /// persisted and no job is queued. Only public addresses are reached, so
+    /// a dry run can't be used to look around the app's own network.
+    pub async fn dry_run(config: &CreateMonitor) -> Result<CheckOutcome, ApiError> {
+        config.validate()?;
+
+        let target = CheckTarget {
+            monitor_type: &config.monitor_type,
+            url: config.url.as_deref(),
+            timeout: config.timeout,
+            metadata: config.metadata.as_ref(),
+            public_only: true,
+        };
+        monitor_checks::run(target).await.ok_or_else(|| {
+            let mut errors = ValidationErrors::default();
+            errors.add(
+                "monitor_type",
+                "unsupported",
+                format!("Only {} monitors can be checked", SUPPORTED_TYPES.join(", ")),
+            );
+            errors.into()
+        })
+    }
+}
+
+/// Whether the check job `job_id` recorded `event`
+fn recorded_by(event: &StatusEvent, job_id: &str) -> bool {
+    event
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get(schedule::EVENT_JOB_ID))
+        .and_then(|id| id.as_str())
+        == Some(job_id)
+}
+
+/// Whether the worker should be checking this monitor on a schedule
+fn is_checked(monitor: &Monitor) -> bool {
+    monitor.is_active && SUPPORTED_TYPES.contains(&monitor.monitor_type.as_str())
+}
+
+fn schedule_changed(before: &Monitor, after: &Monitor) -> bool {
+    !is_checked(before)
+        || before.check_interval != after.check_interval
+        || before.timeout != after.timeout
+        || before.url != after.url
+        || before.monitor_type != after.monitor_type
+}
@@ -0,0 +1,156 @@
+use db_core::models::audit_log::AuditAction;
+use db_core::models::incident::UpdateIncident;
+use db_core::models::monitor::UpdateMonitor;
+use db_core::repositories::IncidentRepository;
+use db_core::DbPool;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::error::ApiError;
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::services::check_service::{CheckNow, CheckService};
+use crate::services::monitor_service::MonitorService;
+use crate::services::stream_service::{StatusStream, StreamEvent};
+use crate::services::worker::WorkerService;
+
+/// A message from the dashboard. `id` is optional and echoed back in the
+/// reply so the client can match replies to requests.
+#[derive(Debug, Deserialize)]
+pub struct ClientMessage {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(flatten)]
+    pub command: DashboardCommand,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DashboardCommand {
+    /// Only forward live events for these monitors; all monitors when empty
+    Subscribe {
+        #[serde(default)]
+        monitor_ids: Vec<i32>,
+    },
+    CheckNow { monitor_id: i32 },
+    PauseMonitor { monitor_id: i32 },
+    ResumeMonitor { monitor_id: i32 },
+    AcknowledgeIncident { incident_id: i32 },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// A live update, the same events the SSE stream carries
+    Event {
+        id: u64,
+        event: &'static str,
+        data: Value,
+    },
+    Result {
+        id: Option<String>,
+        data: Value,
+    },
+    Error {
+        id: Option<String>,
+        code: &'static str,
+        message: String,
+    },
+}
+
+impl ServerMessage {
+    pub fn event(event: &StreamEvent) -> Self {
+        ServerMessage::Event {
+            id: event.id,
+            event: event.kind,
+            data: serde_json::from_str(&event.data).unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn error(id: Option<String>, error: &ApiError) -> Self {
+        ServerMessage::Error {
+            id,
+            code: error.code(),
+            message: error.to_string(),
+        }
+    }
+}
+
+pub struct DashboardService;
+
+impl DashboardService {
+    /// Run a command that changes state. `Subscribe` is connection state and
+    /// is handled by the socket itself.
+    pub async fn execute(
+        pool: &DbPool,
+        worker: &WorkerService,
+        stream: &StatusStream,
+        audit: &AuditContext,
+        command: DashboardCommand,
+    ) -> Result<Value, ApiError> {
+        match command {
+            DashboardCommand::Subscribe { monitor_ids } => Ok(json!({ "monitor_ids": monitor_ids })),
+            DashboardCommand::CheckNow { monitor_id } => {
+                // The result arrives as a live event like any other check
+                match CheckService::check_now(pool, worker, stream, monitor_id, None).await? {
+                    CheckNow::Completed(event) => Ok(json!(event)),
+                    CheckNow::Queued(queued) => Ok(json!(queued)),
+                }
+            }
+            DashboardCommand::PauseMonitor { monitor_id } => {
+                Self::set_monitor_active(pool, worker, audit, monitor_id, false).await
+            }
+            DashboardCommand::ResumeMonitor { monitor_id } => {
+                Self::set_monitor_active(pool, worker, audit, monitor_id, true).await
+            }
+            DashboardCommand::AcknowledgeIncident { incident_id } => {
+                Self::acknowledge_incident(pool, audit, incident_id).await
+            }
+        }
+    }
+
+    async fn set_monitor_active(
+        pool: &DbPool,
+        worker: &WorkerService,
+        audit: &AuditContext,
+        id: i32,
+        is_active: bool,
+    ) -> Result<Value, ApiError> {
+        let before = MonitorService::get_monitor(pool, id).await?.ok_or(ApiError::NotFound)?;
+        let update = UpdateMonitor {
+            is_active: Some(is_active),
+            ..Default::default()
+        };
+
+        let monitor = MonitorService::update_monitor(pool, id, update).await?;
+        AuditService::record(pool, audit, AuditAction::Update, "monitor", id, Some(&before), Some(&monitor)).await;
+        CheckService::sync_schedule(worker, &monitor, Some(&before)).await;
+        Ok(json!(monitor))
+    }
+
+    /// Acknowledgement is recorded in the incident's metadata so the incident
+    /// shape served by the API stays the same
+    async fn acknowledge_incident(pool: &DbPool, audit: &AuditContext, id: i32) -> Result<Value, ApiError> {
+        let before = IncidentRepository::find_by_id(pool, id).await?.ok_or(ApiError::NotFound)?;
+
+        let mut metadata = match before.metadata.clone() {
+            Some(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        metadata.insert(
+            "acknowledged_at".to_string(),
+            json!(OffsetDateTime::now_utc().format(&Rfc3339).ok()),
+        );
+        metadata.insert("acknowledged_by".to_string(), json!(audit.actor_id));
+
+        let update = UpdateIncident {
+            metadata: Some(Value::Object(metadata)),
+            ..Default::default()
+        };
+        let incident = IncidentRepository::update(pool, id, update).await?;
+        AuditService::record(pool, audit, AuditAction::Update, "incident", id, Some(&before), Some(&incident)).await;
+        Ok(json!(incident))
+    }
+}
+
@@ -1,5 +1,6 @@
 use db_core::{
-    models::document::{Document, CreateDocument, UpdateDocument},
+    models::document::{Document, CreateDocument, DocumentFilter, DocumentSort, UpdateDocument},
+    pagination::{Page, PageRequest},
     repositories::DocumentRepository,
     error::DbError,
     DbPool,
@@ -19,6 +20,16 @@ impl DocumentService {
         repo.get_all().await
     }
 
+    pub async fn list(
+        &self,
+        filter: &DocumentFilter,
+        sort: DocumentSort,
+        page: &PageRequest,
+    ) -> Result<Page<Document>, DbError> {
+        let repo = DocumentRepository::new(self.pool.clone());
+        repo.list(filter, sort, page).await
+    }
+
     pub async fn get_by_id(&self, id: i32) -> Result<Document, DbError> {
         let repo = DocumentRepository::new(self.pool.clone());
         match repo.get_by_id(id).await {
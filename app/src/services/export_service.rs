@@ -0,0 +1,210 @@
+use axum::body::{Body, Bytes};
+use db_core::error::DbError;
+use db_core::models::status_event::{StatusEvent, StatusEventFilter};
+use db_core::repositories::StatusEventRepository;
+use db_core::DbPool;
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use time::format_description::well_known::Rfc3339;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::error;
+
+/// Rows are batched into chunks of roughly this size before being written
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Chunks buffered ahead of a slow client before the query is paused
+const CHANNEL_CAPACITY: usize = 8;
+
+const CSV_HEADER: &str =
+    "time,monitor_id,status,response_time,status_code,error_message,metadata,created_at\n";
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+
+    fn write_event(self, out: &mut String, event: &StatusEvent) {
+        match self {
+            ExportFormat::Csv => write_csv_row(out, event),
+            ExportFormat::Ndjson => {
+                if let Ok(line) = serde_json::to_string(event) {
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+            }
+        }
+    }
+}
+
+pub struct ExportService;
+
+impl ExportService {
+    /// Stream status events as a response body. Rows are read from a database
+    /// cursor on a background task and handed over through a bounded channel,
+    /// so memory use stays flat however large the range is.
+    pub fn status_events(pool: DbPool, filter: StatusEventFilter, format: ExportFormat) -> Body {
+        let (tx, rx) = mpsc::channel::<Result<Bytes, DbError>>(CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            let mut buffer = String::with_capacity(CHUNK_SIZE);
+            if format == ExportFormat::Csv {
+                buffer.push_str(CSV_HEADER);
+            }
+
+            let mut events = StatusEventRepository::stream(&pool, &filter);
+            while let Some(result) = events.next().await {
+                match result {
+                    Ok(event) => format.write_event(&mut buffer, &event),
+                    Err(e) => {
+                        error!("Status event export failed: {}", e);
+                        // Failing the body aborts the response instead of ending it cleanly
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                }
+
+                if buffer.len() >= CHUNK_SIZE {
+                    let chunk = std::mem::replace(&mut buffer, String::with_capacity(CHUNK_SIZE));
+                    if tx.send(Ok(Bytes::from(chunk))).await.is_err() {
+                        // Client went away
+                        return;
+                    }
+                }
+            }
+
+            if !buffer.is_empty() {
+                let _ = tx.send(Ok(Bytes::from(buffer))).await;
+            }
+        });
+
+        Body::from_stream(ReceiverStream::new(rx))
+    }
+}
+
+fn write_csv_row(out: &mut String, event: &StatusEvent) {
+    let time = event.time.format(&Rfc3339).unwrap_or_default();
+    let created_at = event.created_at.format(&Rfc3339).unwrap_or_default();
+    let metadata = event.metadata.as_ref().map(|m| m.to_string());
+
+    let fields = [
+        time,
+        event.monitor_id.to_string(),
+        event.status.clone(),
+        event.response_time.map(|v| v.to_string()).unwrap_or_default(),
+        event.status_code.map(|v| v.to_string()).unwrap_or_default(),
+        event.error_message.clone().unwrap_or_default(),
+        metadata.unwrap_or_default(),
+        created_at,
+    ];
+
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        push_csv_field(out, field);
+    }
+    out.push('\n');
+}
+
+/// Quote a field when it contains a delimiter, quote or line break (RFC 4180)
+fn push_csv_field(out: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use time::OffsetDateTime;
+
+    #[test]
+    fn csv_rows_quote_fields_that_need_it() {
+        let noon = OffsetDateTime::from_unix_timestamp(1_735_732_800).unwrap();
+        let event = StatusEvent {
+            id: 1,
+            time: noon,
+            monitor_id: 3,
+            status: "major_outage".to_string(),
+            response_time: Some(120),
+            status_code: None,
+            error_message: Some("connection refused, \"retrying\"".to_string()),
+            metadata: Some(json!({ "attempt": 1 })),
+            created_at: noon,
+        };
+        let mut out = String::new();
+
+        write_csv_row(&mut out, &event);
+
+        assert_eq!(
+            out,
+            "2025-01-01T12:00:00Z,3,major_outage,120,,\"connection refused, \"\"retrying\"\"\",\
+             \"{\"\"attempt\"\":1}\",2025-01-01T12:00:00Z\n"
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn ndjson_exports_stream_every_event_in_range_oldest_first() {
+        let pool = std::sync::Arc::new(sqlx::PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap());
+        let monitor_id: i32 = sqlx::query_scalar(
+            "INSERT INTO monitors (name, display_name) VALUES ('export-stream-test', 'Export') RETURNING id",
+        )
+        .fetch_one(pool.as_ref())
+        .await
+        .unwrap();
+        // Long enough messages that the export spans several chunks
+        sqlx::query(
+            "INSERT INTO status_events (time, monitor_id, status, error_message)
+             SELECT NOW() - n * INTERVAL '1 minute', $1, 'degraded', repeat('x', 100)
+             FROM generate_series(1, 2000) n",
+        )
+        .bind(monitor_id)
+        .execute(pool.as_ref())
+        .await
+        .unwrap();
+
+        let filter = StatusEventFilter {
+            monitor_id: Some(monitor_id),
+            from: Some(OffsetDateTime::now_utc() - time::Duration::minutes(1500) - time::Duration::seconds(30)),
+            ..Default::default()
+        };
+        let body = ExportService::status_events(pool.clone(), filter, ExportFormat::Ndjson);
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        sqlx::query("DELETE FROM monitors WHERE id = $1").bind(monitor_id).execute(pool.as_ref()).await.unwrap();
+
+        assert!(bytes.len() > CHUNK_SIZE);
+        let events: Vec<StatusEvent> = std::str::from_utf8(&bytes)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 1500);
+        assert!(events.windows(2).all(|pair| pair[0].time < pair[1].time));
+        assert!(events.iter().all(|event| event.monitor_id == monitor_id));
+    }
+}
@@ -0,0 +1,76 @@
+use db_core::pagination::{Page, PageRequest};
+
+use crate::error::ApiError;
+use crate::services::worker::job_queue::JOB_STATES;
+use crate::services::worker::{JobFilter, JobRecord, RescheduleJob, WorkerService};
+use crate::validation::Validate;
+
+/// Job states a retry or cancel applies to: those that have failed at least once
+const FAILED_STATES: &[&str] = &["retrying", "failed"];
+
+pub struct JobService;
+
+impl JobService {
+    pub async fn list_jobs(
+        worker: &WorkerService,
+        filter: &JobFilter,
+        page: &PageRequest,
+    ) -> Result<Page<JobRecord>, ApiError> {
+        if let Some(state) = &filter.state {
+            if !JOB_STATES.contains(&state.as_str()) {
+                return Err(ApiError::BadRequest(format!("state must be one of {}", JOB_STATES.join(", "))));
+            }
+        }
+
+        Ok(worker.list_jobs(filter, page).await?)
+    }
+
+    pub async fn get_job(worker: &WorkerService, id: i64) -> Result<JobRecord, ApiError> {
+        worker.get_job(id).await?.ok_or(ApiError::NotFound)
+    }
+
+    /// Run a failed job again now, with its attempts reset
+    pub async fn retry_job(worker: &WorkerService, job: &JobRecord) -> Result<JobRecord, ApiError> {
+        if !FAILED_STATES.contains(&job.state.as_str()) {
+            return Err(ApiError::Conflict(format!("Only failed jobs can be retried; this job is {}", job.state)));
+        }
+
+        if !worker.retry_job(job.id).await? {
+            return Err(locked());
+        }
+        Self::get_job(worker, job.id).await
+    }
+
+    pub async fn reschedule_job(
+        worker: &WorkerService,
+        job: &JobRecord,
+        changes: &RescheduleJob,
+    ) -> Result<JobRecord, ApiError> {
+        changes.validate()?;
+        if job.state == "running" {
+            return Err(locked());
+        }
+
+        if !worker.reschedule_job(job.id, changes).await? {
+            return Err(locked());
+        }
+        Self::get_job(worker, job.id).await
+    }
+
+    /// Remove a failed job, so it is never attempted again
+    pub async fn cancel_job(worker: &WorkerService, job: &JobRecord) -> Result<(), ApiError> {
+        if !FAILED_STATES.contains(&job.state.as_str()) {
+            return Err(ApiError::Conflict(format!("Only failed jobs can be cancelled; this job is {}", job.state)));
+        }
+
+        if !worker.cancel_job(job.id).await? {
+            return Err(locked());
+        }
+        Ok(())
+    }
+}
+
+// graphile_worker leaves jobs a worker has locked untouched
+fn locked() -> ApiError {
+    ApiError::Conflict("Job is running; wait for it to finish".to_string())
+}
@@ -0,0 +1,210 @@
+use db_core::repositories::{IncidentRepository, StatusEventRepository};
+use db_core::DbPool;
+use prometheus_client::encoding::text::encode;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::registry::Registry;
+use std::sync::atomic::AtomicU64;
+
+use crate::error::ApiError;
+use crate::metrics::HttpMetrics;
+use crate::services::worker::WorkerService;
+
+type FloatGauge = Gauge<f64, AtomicU64>;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct MonitorLabels {
+    monitor_id: i32,
+    monitor: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct MonitorStatusLabels {
+    monitor_id: i32,
+    monitor: String,
+    status: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct UptimeLabels {
+    monitor_id: i32,
+    monitor: String,
+    window: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct IncidentLabels {
+    severity: String,
+    state: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct JobLabels {
+    task: String,
+    state: String,
+}
+
+pub struct MetricsService;
+
+impl MetricsService {
+    /// Render every metric in the Prometheus text format. Monitor, incident
+    /// and job figures are read from the database on each scrape; request
+    /// latencies are accumulated in memory since the app started.
+    pub async fn render(pool: &DbPool, worker: &WorkerService, http: &HttpMetrics) -> Result<String, ApiError> {
+        let mut registry = Registry::default();
+        http.register(&mut registry);
+
+        let app = registry.sub_registry_with_prefix("rustgenie");
+        Self::register_monitors(pool, app).await?;
+        Self::register_incidents(pool, app).await?;
+        Self::register_jobs(worker, app).await?;
+
+        let mut text = String::new();
+        encode(&mut text, &registry).map_err(|e| ApiError::Internal(e.to_string()))?;
+        Ok(text)
+    }
+
+    async fn register_monitors(pool: &DbPool, registry: &mut Registry) -> Result<(), ApiError> {
+        let status = Family::<MonitorStatusLabels, Gauge>::default();
+        let response_time = Family::<MonitorLabels, FloatGauge>::default();
+        let uptime = Family::<UptimeLabels, FloatGauge>::default();
+
+        for monitor in StatusEventRepository::get_monitor_metrics(pool).await? {
+            status
+                .get_or_create(&MonitorStatusLabels {
+                    monitor_id: monitor.monitor_id,
+                    monitor: monitor.name.clone(),
+                    status: monitor.current_status,
+                })
+                .set(1);
+
+            if let Some(ms) = monitor.response_time {
+                response_time
+                    .get_or_create(&MonitorLabels {
+                        monitor_id: monitor.monitor_id,
+                        monitor: monitor.name.clone(),
+                    })
+                    .set(ms as f64 / 1000.0);
+            }
+
+            for (window, percentage) in [("24h", monitor.uptime_24h), ("7d", monitor.uptime_7d), ("30d", monitor.uptime_30d)] {
+                uptime
+                    .get_or_create(&UptimeLabels {
+                        monitor_id: monitor.monitor_id,
+                        monitor: monitor.name.clone(),
+                        window: window.to_string(),
+                    })
+                    .set(percentage / 100.0);
+            }
+        }
+
+        registry.register(
+            "monitor_status",
+            "Current status of each active monitor; 1 for the status it is in",
+            status,
+        );
+        registry.register(
+            "monitor_response_time_seconds",
+            "Response time of each monitor's latest check",
+            response_time,
+        );
+        registry.register(
+            "monitor_uptime_ratio",
+            "Share of each monitor's checks that were operational, per window",
+            uptime,
+        );
+        Ok(())
+    }
+
+    async fn register_incidents(pool: &DbPool, registry: &mut Registry) -> Result<(), ApiError> {
+        let incidents = Family::<IncidentLabels, Gauge>::default();
+
+        for count in IncidentRepository::count_by_severity(pool).await? {
+            let state = if count.is_resolved { "resolved" } else { "active" };
+            incidents
+                .get_or_create(&IncidentLabels {
+                    severity: count.severity,
+                    state: state.to_string(),
+                })
+                .set(count.count);
+        }
+
+        registry.register("incidents", "Number of incidents by severity and state", incidents);
+        Ok(())
+    }
+
+    async fn register_jobs(worker: &WorkerService, registry: &mut Registry) -> Result<(), ApiError> {
+        let jobs = Family::<JobLabels, Gauge>::default();
+
+        let counts = worker.job_counts().await.map_err(|e| ApiError::Internal(e.to_string()))?;
+        for count in counts {
+            jobs.get_or_create(&JobLabels {
+                task: count.task,
+                state: count.state,
+            })
+            .set(count.count);
+        }
+
+        registry.register(
+            "jobs",
+            "Pending background jobs by task and state (queued, running, retrying, failed)",
+            jobs,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestState;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn monitors_incidents_and_jobs_are_rendered() {
+        let state = TestState::connect().await;
+        let pool = state.pool.as_ref();
+        let monitor_id: i32 = sqlx::query_scalar(
+            "INSERT INTO monitors (name, display_name, is_active) VALUES ('metrics-render-test', 'Metrics', TRUE) RETURNING id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO status_events (time, monitor_id, status, response_time)
+            VALUES
+                (NOW() - INTERVAL '4 minutes', $1, 'operational', 100),
+                (NOW() - INTERVAL '3 minutes', $1, 'major_outage', NULL),
+                (NOW() - INTERVAL '2 minutes', $1, 'operational', 120),
+                (NOW() - INTERVAL '1 minute', $1, 'operational', 250)
+            "#,
+        )
+        .bind(monitor_id)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query("SELECT graphile_worker.add_job('metrics_render_test', '{}'::json)")
+            .execute(pool)
+            .await
+            .unwrap();
+
+        let text = MetricsService::render(&state.pool, &state.worker, &HttpMetrics::default()).await;
+
+        sqlx::query("DELETE FROM graphile_worker._private_jobs WHERE task_id IN (SELECT id FROM graphile_worker._private_tasks WHERE identifier = 'metrics_render_test')")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM monitors WHERE id = $1").bind(monitor_id).execute(pool).await.unwrap();
+
+        let text = text.unwrap();
+        let labels = format!(r#"monitor_id="{}",monitor="metrics-render-test""#, monitor_id);
+        assert!(text.contains(&format!("rustgenie_monitor_status{{{},status=\"operational\"}} 1", labels)), "{}", text);
+        assert!(text.contains(&format!("rustgenie_monitor_response_time_seconds{{{}}} 0.25", labels)), "{}", text);
+        assert!(text.contains(&format!("rustgenie_monitor_uptime_ratio{{{},window=\"24h\"}} 0.75", labels)), "{}", text);
+        assert!(text.contains(r#"rustgenie_jobs{task="metrics_render_test",state="queued"} 1"#), "{}", text);
+        assert!(text.contains("# TYPE rustgenie_incidents gauge"));
+        assert!(text.contains("# TYPE http_request_duration_seconds histogram"));
+    }
+}
@@ -1,3 +1,13 @@
+pub mod audit_service;
+pub mod badge_service;
+pub mod check_service;
+pub mod dashboard_service;
 pub mod document_service;
+pub mod export_service;
+pub mod job_service;
+pub mod metrics_service;
+pub mod slo_service;
+pub mod stream_service;
 pub mod worker;
 pub mod monitor_service;
+pub mod status_page_service;
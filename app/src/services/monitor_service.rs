@@ -1,14 +1,16 @@
 use db_core::error::DbError;
-use db_core::models::monitor::{CreateMonitor, Monitor, UpdateMonitor};
+use db_core::models::monitor::{CreateMonitor, Monitor, MonitorFilter, MonitorSort, UpdateMonitor};
 use db_core::models::status_event::{
-    CreateStatusEvent, MonitorStatusSummary, StatusEvent, StatusDailyStat,
+    CreateStatusEvent, MonitorStatusSummary, StatsGranularity, StatusEvent, StatusEventFilter,
+    StatusEventSort, StatusDailyStat, StatusHourlyStat,
 };
-use db_core::models::incident::Incident;
-use db_core::repositories::{MonitorRepository, StatusEventRepository, IncidentRepository};
+use db_core::repositories::{MonitorRepository, StatusEventRepository};
 use time::OffsetDateTime;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use db_core::pagination::{Page, PageRequest};
 use db_core::DbPool;
-use tracing::debug;
+use status_page::{StatusPageData, StatusPageFilter};
 
 fn month_name(month: time::Month) -> &'static str {
     match month {
@@ -30,38 +32,31 @@ fn month_name(month: time::Month) -> &'static str {
 pub struct MonitorService;
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct MonitorWithStatus {
+pub struct MonitorDetailData {
     pub monitor: Monitor,
-    pub current_status: String,
-    #[serde(with = "db_core::time_serde::option")]
-    pub last_check_time: Option<OffsetDateTime>,
-    pub uptime_percentage: f64,
-    pub daily_stats: Vec<StatusDailyStat>,
+    pub summary: MonitorStatusSummary,
+    pub tracker_data: Vec<TrackerDataPoint>,
+    pub recent_events: Vec<StatusEvent>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ServiceGroup {
-    pub name: String,
-    pub description: Option<String>,
-    pub monitors: Vec<MonitorWithStatus>,
-    pub overall_status: String,
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum StatsBuckets {
+    Hourly(Vec<StatusHourlyStat>),
+    Daily(Vec<StatusDailyStat>),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct StatusPageData {
-    pub all_operational: bool,
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MonitorStats {
+    pub monitor_id: i32,
+    pub granularity: StatsGranularity,
     #[serde(with = "db_core::time_serde")]
-    pub last_updated: OffsetDateTime,
-    pub monitors: Vec<MonitorWithStatus>,
-    pub incidents: Vec<Incident>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MonitorDetailData {
-    pub monitor: Monitor,
-    pub summary: MonitorStatusSummary,
-    pub tracker_data: Vec<TrackerDataPoint>,
-    pub recent_events: Vec<StatusEvent>,
+    #[schemars(with = "db_core::time_serde::Timestamp")]
+    pub from: OffsetDateTime,
+    #[serde(with = "db_core::time_serde")]
+    #[schemars(with = "db_core::time_serde::Timestamp")]
+    pub to: OffsetDateTime,
+    pub buckets: StatsBuckets,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -102,46 +97,12 @@ impl MonitorService {
         StatusEventRepository::create(pool, event).await
     }
 
-    pub async fn get_status_page_data(pool: &DbPool) -> Result<StatusPageData, DbError> {
-        let monitors = MonitorRepository::list_active(pool).await?;
-        let incidents = IncidentRepository::list_active(pool).await?;
-        let mut monitors_with_status = Vec::new();
-        let mut all_operational = true;
-
-        for monitor in monitors {
-            let summary = StatusEventRepository::get_monitor_summary(pool, monitor.id).await?;
-            let daily_stats = StatusEventRepository::get_daily_stats(pool, monitor.id, 90).await?;
-            
-            debug!("Monitor {} daily_stats count: {}", monitor.id, daily_stats.len());
-            if !daily_stats.is_empty() {
-                debug!("First stat: {:?}", daily_stats.first());
-                debug!("Last stat: {:?}", daily_stats.last());
-            }
-            
-            if summary.current_status != "operational" {
-                all_operational = false;
-            }
-
-            monitors_with_status.push(MonitorWithStatus {
-                monitor,
-                current_status: summary.current_status,
-                last_check_time: Some(summary.last_check_time),
-                uptime_percentage: summary.uptime_90d,
-                daily_stats,
-            });
-        }
-        
-        // If there are active incidents, we're not fully operational
-        if !incidents.is_empty() {
-            all_operational = false;
-        }
-
-        Ok(StatusPageData {
-            all_operational,
-            last_updated: OffsetDateTime::now_utc(),
-            monitors: monitors_with_status,
-            incidents,
-        })
+    /// The status page showing the monitors `filter` selects
+    pub async fn get_status_page_data(
+        pool: &DbPool,
+        filter: &StatusPageFilter,
+    ) -> Result<StatusPageData, DbError> {
+        status_page::load(pool, filter).await
     }
 
     pub async fn get_monitor_detail(
@@ -160,18 +121,9 @@ impl MonitorService {
         let tracker_data: Vec<TrackerDataPoint> = tracker_raw
             .into_iter()
             .map(|(date, status)| {
-                let tooltip = match status.as_str() {
-                    "operational" => "Operational",
-                    "degraded" => "Degraded Performance",
-                    "partial_outage" => "Partial Outage",
-                    "major_outage" => "Major Outage",
-                    "maintenance" => "Maintenance",
-                    _ => "Unknown",
-                };
-
                 TrackerDataPoint {
                     date: format!("{:02} {}, {}", date.day(), month_name(date.month()), date.year()),
-                    tooltip: tooltip.to_string(),
+                    tooltip: status_page::status_label(&status).to_string(),
                     status,
                 }
             })
@@ -185,7 +137,64 @@ impl MonitorService {
         }))
     }
 
+    pub async fn list_status_events(
+        pool: &DbPool,
+        filter: &StatusEventFilter,
+        sort: StatusEventSort,
+        page: &PageRequest,
+    ) -> Result<Page<StatusEvent>, DbError> {
+        StatusEventRepository::list(pool, filter, sort, page).await
+    }
+
+    /// Aggregated statistics for a monitor. Without an explicit range this
+    /// covers the last 24 hours for hourly buckets and 30 days for daily ones.
+    pub async fn get_stats(
+        pool: &DbPool,
+        monitor_id: i32,
+        granularity: StatsGranularity,
+        from: Option<OffsetDateTime>,
+        to: Option<OffsetDateTime>,
+    ) -> Result<MonitorStats, DbError> {
+        let to = to.unwrap_or_else(OffsetDateTime::now_utc);
+        let from = from.unwrap_or_else(|| match granularity {
+            StatsGranularity::Hour => to - time::Duration::hours(24),
+            StatsGranularity::Day => to - time::Duration::days(30),
+        });
+
+        // The repository lists newest first; charts read oldest first
+        let buckets = match granularity {
+            StatsGranularity::Hour => {
+                let mut stats = StatusEventRepository::get_hourly_stats(pool, monitor_id, from, to).await?;
+                stats.reverse();
+                StatsBuckets::Hourly(stats)
+            }
+            StatsGranularity::Day => {
+                let mut stats = StatusEventRepository::get_daily_stats(pool, monitor_id, from, to).await?;
+                stats.reverse();
+                StatsBuckets::Daily(stats)
+            }
+        };
+
+        Ok(MonitorStats {
+            monitor_id,
+            granularity,
+            from,
+            to,
+            buckets,
+        })
+    }
+
+    pub async fn list_monitors(
+        pool: &DbPool,
+        filter: &MonitorFilter,
+        sort: MonitorSort,
+        page: &PageRequest,
+    ) -> Result<Page<Monitor>, DbError> {
+        MonitorRepository::list(pool, filter, sort, page).await
+    }
+
+    /// Every monitor by display name, as the unversioned API lists them
     pub async fn get_all_monitors(pool: &DbPool) -> Result<Vec<Monitor>, DbError> {
         MonitorRepository::list_all(pool).await
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,64 @@
+use db_core::models::slo::SloStatus;
+use db_core::repositories::{MonitorRepository, ServiceGroupRepository, SloRepository};
+use db_core::DbPool;
+use serde::Serialize;
+
+use crate::error::ApiError;
+use crate::validation::ValidationErrors;
+
+/// An SLO's status with the name of what it covers, for the report page
+#[derive(Debug, Serialize)]
+pub struct SloReportEntry {
+    #[serde(flatten)]
+    pub status: SloStatus,
+    /// The monitor's display name or the service group's name
+    pub subject: String,
+}
+
+pub struct SloService;
+
+impl SloService {
+    /// Every SLO's status, by name
+    pub async fn report(pool: &DbPool) -> Result<Vec<SloReportEntry>, ApiError> {
+        let slos = SloRepository::list_all(pool).await?;
+        let monitors = MonitorRepository::list_all(pool).await?;
+        let groups = ServiceGroupRepository::list_all(pool).await?;
+
+        let mut entries = Vec::with_capacity(slos.len());
+        for slo in slos {
+            let subject = match (slo.monitor_id, slo.service_group_id) {
+                (Some(id), _) => monitors.iter().find(|m| m.id == id).map(|m| m.display_name.clone()),
+                (_, Some(id)) => groups.iter().find(|g| g.id == id).map(|g| g.name.clone()),
+                (None, None) => None,
+            };
+            entries.push(SloReportEntry {
+                subject: subject.unwrap_or_default(),
+                status: SloRepository::get_status(pool, slo).await?,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Check that the monitor or service group a new SLO covers exists
+    pub async fn check_subject(
+        pool: &DbPool,
+        monitor_id: Option<i32>,
+        service_group_id: Option<i32>,
+    ) -> Result<(), ApiError> {
+        let mut errors = ValidationErrors::default();
+
+        if let Some(id) = monitor_id {
+            if MonitorRepository::find_by_id(pool, id).await?.is_none() {
+                errors.add("monitor_id", "not_found", format!("monitor {} does not exist", id));
+            }
+        }
+        if let Some(id) = service_group_id {
+            if ServiceGroupRepository::find_by_id(pool, id).await?.is_none() {
+                errors.add("service_group_id", "not_found", format!("service group {} does not exist", id));
+            }
+        }
+
+        Ok(errors.into_result()?)
+    }
+}
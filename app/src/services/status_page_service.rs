@@ -0,0 +1,169 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use db_core::models::audit_log::ActorType;
+use db_core::models::status_page::{CreateStatusPage, StatusPage, StatusPageVisibility, UpdateStatusPage};
+use db_core::repositories::StatusPageRepository;
+use db_core::DbPool;
+use status_page::statuspage::PageInfo;
+use status_page::{StatusPageData, StatusPageFilter};
+use time::OffsetDateTime;
+use tower_sessions::Session;
+use tracing::error;
+
+use crate::error::ApiError;
+use crate::services::audit_service::AuditContext;
+use crate::services::monitor_service::MonitorService;
+
+/// Whether a visitor may see a page
+#[derive(Debug, PartialEq)]
+pub enum PageAccess {
+    Granted,
+    /// Ask for the page's password
+    Locked,
+    /// Send the visitor to sign in
+    SignIn,
+}
+
+pub struct StatusPageService;
+
+impl StatusPageService {
+    pub async fn create(pool: &DbPool, mut page: CreateStatusPage) -> Result<StatusPage, ApiError> {
+        page.custom_domain = page.custom_domain.map(|domain| normalize_domain(&domain));
+        let password_hash = page.password.as_deref().map(hash_password).transpose()?;
+
+        Ok(StatusPageRepository::create(pool, page, password_hash).await?)
+    }
+
+    pub async fn update(pool: &DbPool, id: i32, mut update: UpdateStatusPage) -> Result<StatusPage, ApiError> {
+        update.custom_domain = update.custom_domain.map(|domain| normalize_domain(&domain));
+        let password_hash = update.password.as_deref().map(hash_password).transpose()?;
+
+        Ok(StatusPageRepository::update(pool, id, update, password_hash).await?)
+    }
+
+    /// The page's monitors, incidents and maintenance, without internal
+    /// details unless the page shows them
+    pub async fn load(pool: &DbPool, page: &StatusPage) -> Result<StatusPageData, ApiError> {
+        let mut data = MonitorService::get_status_page_data(pool, &StatusPageFilter::from(page)).await?;
+        if !page.show_internal_details {
+            data.hide_internal_details();
+        }
+        Ok(data)
+    }
+
+    /// [`Self::load`] for a visitor, who is turned away unless they may see
+    /// the page
+    pub async fn load_for(
+        pool: &DbPool,
+        page: &StatusPage,
+        session: &Session,
+        audit: &AuditContext,
+    ) -> Result<StatusPageData, ApiError> {
+        if Self::access(page, session, audit).await != PageAccess::Granted {
+            return Err(ApiError::Unauthorized);
+        }
+        Self::load(pool, page).await
+    }
+
+    /// How feeds and documents name and link to the default page at
+    /// `/status`
+    pub fn default_info(app_url: &str, updated_at: OffsetDateTime) -> PageInfo {
+        PageInfo::new("status", "System status", format!("{}/status", app_url.trim_end_matches('/')), updated_at)
+    }
+
+    /// How feeds and documents name and link to `page`: on its custom domain
+    /// when it has one, otherwise at `/s/{slug}`
+    pub fn info(page: &StatusPage, app_url: &str, updated_at: OffsetDateTime) -> PageInfo {
+        let app_url = app_url.trim_end_matches('/');
+        let url = match &page.custom_domain {
+            Some(domain) => {
+                let scheme = app_url.split_once("://").map_or("https", |(scheme, _)| scheme);
+                format!("{}://{}", scheme, domain)
+            }
+            None => format!("{}/s/{}", app_url, page.slug),
+        };
+        PageInfo::new(page.slug.clone(), page.title.clone(), url, updated_at)
+    }
+
+    pub async fn access(page: &StatusPage, session: &Session, audit: &AuditContext) -> PageAccess {
+        match StatusPageVisibility::from(page.visibility.clone()) {
+            StatusPageVisibility::Public => PageAccess::Granted,
+            StatusPageVisibility::Sso if audit.actor_type == ActorType::User => PageAccess::Granted,
+            StatusPageVisibility::Sso => PageAccess::SignIn,
+            StatusPageVisibility::Password => {
+                let unlocked = session.get::<String>(&unlock_key(page)).await.ok().flatten();
+                // Changing the password locks everyone out again
+                if unlocked.is_some() && unlocked == page.password_hash {
+                    PageAccess::Granted
+                } else {
+                    PageAccess::Locked
+                }
+            }
+        }
+    }
+
+    /// Check `password` and, when it matches, remember in the session that
+    /// the page is unlocked
+    pub async fn unlock(page: &StatusPage, session: &Session, password: &str) -> Result<bool, ApiError> {
+        let Some(hash) = &page.password_hash else {
+            return Ok(false);
+        };
+        if !verify_password(hash, password) {
+            return Ok(false);
+        }
+
+        session
+            .insert(&unlock_key(page), hash)
+            .await
+            .map_err(|e| ApiError::Internal(format!("Failed to store status page unlock: {}", e)))?;
+        Ok(true)
+    }
+}
+
+fn unlock_key(page: &StatusPage) -> String {
+    format!("status_page_{}", page.id)
+}
+
+/// Host names are matched case-insensitively and without a trailing dot
+fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+fn hash_password(password: &str) -> Result<String, ApiError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| ApiError::Internal(format!("Failed to hash status page password: {}", e)))
+}
+
+fn verify_password(hash: &str, password: &str) -> bool {
+    match PasswordHash::new(hash) {
+        Ok(parsed) => Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok(),
+        Err(e) => {
+            error!("Stored status page password hash is invalid: {}", e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passwords_verify_only_against_their_own_hash() {
+        let hash = hash_password("correct horse").unwrap();
+
+        assert!(hash.starts_with("$argon2"));
+        assert!(verify_password(&hash, "correct horse"));
+        assert!(!verify_password(&hash, "battery staple"));
+        assert!(!verify_password("not a hash", "correct horse"));
+    }
+
+    #[test]
+    fn domains_are_normalized() {
+        assert_eq!(normalize_domain(" Status.Example.COM. "), "status.example.com");
+    }
+}
@@ -0,0 +1,221 @@
+use db_core::notifications::{self, Change};
+use db_core::DbPool;
+use futures::stream::{self, Stream, StreamExt};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::future::ready;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tracing::warn;
+
+/// Events kept for clients reconnecting with `Last-Event-ID`
+const HISTORY_SIZE: usize = 1000;
+/// Events buffered per subscriber before a slow client starts missing some
+const CHANNEL_CAPACITY: usize = 256;
+
+/// One message on the live status stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEvent {
+    pub id: u64,
+    /// SSE event name: `status_event`, `monitor_state`, `incident` or `maintenance`
+    pub kind: &'static str,
+    /// Monitors the event concerns, used for per-monitor filtering
+    pub monitor_ids: Vec<i32>,
+    /// JSON payload, serialized once and shared by every subscriber
+    pub data: String,
+}
+
+impl StreamEvent {
+    pub fn matches(&self, monitor_ids: &[i32]) -> bool {
+        monitor_ids.is_empty() || self.monitor_ids.iter().any(|id| monitor_ids.contains(id))
+    }
+}
+
+/// Fans database change notifications out to connected clients and keeps a
+/// short history so reconnecting clients can catch up.
+///
+/// Event ids and the history belong to this process. A client that
+/// reconnects to another app instance, or after a restart, misses what was
+/// sent in between and should reload its current state from the JSON API.
+pub struct StatusStream {
+    sender: broadcast::Sender<Arc<StreamEvent>>,
+    history: Mutex<VecDeque<Arc<StreamEvent>>>,
+    next_id: AtomicU64,
+}
+
+impl Default for StatusStream {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        // Seeded from the clock so ids keep increasing across restarts and a
+        // stale Last-Event-ID never hides new events
+        let seed = (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1000) as u64;
+
+        Self {
+            sender,
+            history: Mutex::new(VecDeque::with_capacity(HISTORY_SIZE)),
+            next_id: AtomicU64::new(seed),
+        }
+    }
+}
+
+impl StatusStream {
+    /// Create the stream and start listening for changes in the background
+    pub fn start(pool: DbPool) -> Arc<Self> {
+        let stream = Arc::new(Self::default());
+        tokio::spawn(Arc::clone(&stream).listen(pool));
+        stream
+    }
+
+    async fn listen(self: Arc<Self>, pool: DbPool) {
+        notifications::listen(&pool, "Status stream", |change| {
+            self.publish(change);
+            ready(())
+        })
+        .await
+    }
+
+    /// Turn a change into stream events, record them and send them to subscribers
+    pub fn publish(&self, change: Change) {
+        for (kind, monitor_ids, data) in events_for(change) {
+            let event = Arc::new(StreamEvent {
+                id: self.next_id.fetch_add(1, Ordering::Relaxed),
+                kind,
+                monitor_ids,
+                data: data.to_string(),
+            });
+
+            {
+                let mut history = self.history.lock().unwrap();
+                if history.len() == HISTORY_SIZE {
+                    history.pop_front();
+                }
+                history.push_back(Arc::clone(&event));
+            }
+
+            // No receivers just means nobody is watching
+            let _ = self.sender.send(event);
+        }
+    }
+
+    /// Events for the given monitors (all monitors when empty). With a
+    /// `last_event_id`, buffered events after it are replayed first.
+    pub fn subscribe(
+        &self,
+        monitor_ids: Vec<i32>,
+        last_event_id: Option<u64>,
+    ) -> impl Stream<Item = Arc<StreamEvent>> + Send + 'static {
+        // Subscribe before reading the history so nothing falls in between
+        let receiver = self.sender.subscribe();
+        // An id this process never issued came from another instance, and
+        // filtering on it could hide events until our ids caught up
+        let last_event_id = last_event_id.filter(|&last| last < self.next_id.load(Ordering::Relaxed));
+
+        let replay: Vec<Arc<StreamEvent>> = match last_event_id {
+            Some(last) => self
+                .history
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|event| event.id > last)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        let replayed_up_to = replay.last().map(|event| event.id).or(last_event_id).unwrap_or(0);
+
+        let live = BroadcastStream::new(receiver).filter_map(move |result| {
+            ready(match result {
+                Ok(event) if event.id > replayed_up_to => Some(event),
+                Ok(_) => None,
+                Err(e) => {
+                    warn!("Status stream subscriber fell behind: {}", e);
+                    None
+                }
+            })
+        });
+
+        stream::iter(replay)
+            .chain(live)
+            .filter(move |event| ready(event.matches(&monitor_ids)))
+    }
+}
+
+fn events_for(change: Change) -> Vec<(&'static str, Vec<i32>, serde_json::Value)> {
+    match change {
+        Change::StatusEvent { event, previous_status } => {
+            let transition = (previous_status.as_deref() != Some(event.status.as_str())).then(|| {
+                json!({
+                    "monitor_id": event.monitor_id,
+                    "status": event.status,
+                    "previous_status": previous_status,
+                    "time": event.time.format(&Rfc3339).ok(),
+                })
+            });
+
+            let mut events = vec![("status_event", vec![event.monitor_id], json!(event))];
+            if let Some(transition) = transition {
+                events.push(("monitor_state", vec![event.monitor_id], transition));
+            }
+            events
+        }
+        Change::Incident { action, incident } => {
+            vec![(
+                "incident",
+                incident.affected_monitors.clone(),
+                json!({ "action": action, "incident": incident }),
+            )]
+        }
+        Change::Maintenance { action, window } => {
+            vec![(
+                "maintenance",
+                window.affected_monitors.clone(),
+                json!({ "action": action, "window": window }),
+            )]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db_core::notifications::status_change;
+
+    #[test]
+    fn status_changes_emit_a_state_transition() {
+        let unchanged = events_for(status_change(1, "operational", Some("operational")));
+        let changed = events_for(status_change(1, "major_outage", Some("operational")));
+
+        assert_eq!(unchanged.iter().map(|e| e.0).collect::<Vec<_>>(), ["status_event"]);
+        assert_eq!(changed.iter().map(|e| e.0).collect::<Vec<_>>(), ["status_event", "monitor_state"]);
+        assert_eq!(changed[1].2["previous_status"], "operational");
+    }
+
+    #[tokio::test]
+    async fn reconnecting_clients_replay_missed_events_for_their_monitors() {
+        let stream = StatusStream::default();
+        stream.publish(status_change(1, "operational", Some("operational")));
+        let seen = stream.history.lock().unwrap().back().unwrap().id;
+        stream.publish(status_change(2, "operational", Some("operational")));
+        stream.publish(status_change(1, "degraded", Some("operational")));
+
+        let events: Vec<_> = stream.subscribe(vec![1], Some(seen)).take(2).collect().await;
+
+        assert_eq!(events.iter().map(|e| e.kind).collect::<Vec<_>>(), ["status_event", "monitor_state"]);
+        assert!(events.iter().all(|e| e.id > seen && e.monitor_ids == [1]));
+    }
+
+    #[tokio::test]
+    async fn ids_from_another_instance_do_not_hide_new_events() {
+        let stream = StatusStream::default();
+        let elsewhere = stream.next_id.load(Ordering::Relaxed) + 1_000_000;
+
+        let mut events = Box::pin(stream.subscribe(vec![], Some(elsewhere)));
+        stream.publish(status_change(1, "operational", Some("operational")));
+
+        assert_eq!(events.next().await.unwrap().kind, "status_event");
+    }
+}
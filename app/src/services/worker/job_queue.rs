@@ -1,20 +1,115 @@
-use graphile_worker::{WorkerOptions, WorkerUtils, Job};
+use chrono::{DateTime, Utc};
+use graphile_worker::worker_utils::RescheduleJobOptions;
+use graphile_worker::{JobKeyMode, JobSpec, WorkerOptions, WorkerUtils, Job};
+use monitor_checks::schedule;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use db_core::pagination::{Keyset, Page, PageRequest, SortColumn, SortDirection, SortKind, ID_KEY};
 use db_core::DbPool;
+use sqlx::{Postgres, QueryBuilder};
 use std::sync::Arc;
 use thiserror::Error;
-use tracing::info;
+use time::OffsetDateTime;
+use tracing::{info, Span};
 
 #[derive(Error, Debug)]
 pub enum WorkerError {
     #[error("Failed to create job: {0}")]
     JobCreationError(String),
     
+    #[error("Failed to remove job: {0}")]
+    JobRemovalFailed(String),
+
+    #[error("Failed to query jobs: {0}")]
+    JobQueryFailed(String),
+
+    #[error("{0}")]
+    InvalidPage(String),
+
+    #[error("Failed to update job: {0}")]
+    JobUpdateFailed(String),
+
     #[error("Failed to initialize worker: {0}")]
     InitializationError(String),
 }
 
+/// Number of pending jobs of a task in a given state: `queued`, `running`,
+/// `retrying` (failed before, will run again) or `failed` (out of attempts)
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobCount {
+    pub task: String,
+    pub state: String,
+    pub count: i64,
+}
+
+/// Derives a job's state from the `graphile_worker.jobs` columns. Shared by
+/// the counts and the job list so both agree on what each state means.
+const JOB_STATE: &str = r#"
+    CASE
+        WHEN locked_at IS NOT NULL THEN 'running'
+        WHEN attempts >= max_attempts THEN 'failed'
+        WHEN attempts > 0 THEN 'retrying'
+        ELSE 'queued'
+    END"#;
+
+pub const JOB_STATES: &[&str] = &["queued", "running", "retrying", "failed"];
+
+/// Newest jobs first unless the client asks otherwise
+const JOB_ID_SORT: SortColumn = SortColumn {
+    column: "id",
+    field: "id",
+    kind: SortKind::Int,
+    default_direction: SortDirection::Desc,
+};
+
+/// A pending job as graphile_worker stores it. Completed jobs are deleted,
+/// so a job that finished can no longer be looked up.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, JsonSchema)]
+pub struct JobRecord {
+    pub id: i64,
+    pub queue_name: Option<String>,
+    pub task_identifier: String,
+    pub payload: Value,
+    pub priority: i16,
+    pub state: String,
+    pub attempts: i16,
+    pub max_attempts: i16,
+    pub last_error: Option<String>,
+    pub key: Option<String>,
+    #[serde(with = "db_core::time_serde")]
+    #[schemars(with = "db_core::time_serde::Timestamp")]
+    pub run_at: OffsetDateTime,
+    #[serde(with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    pub locked_at: Option<OffsetDateTime>,
+    pub locked_by: Option<String>,
+    #[serde(with = "db_core::time_serde")]
+    #[schemars(with = "db_core::time_serde::Timestamp")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "db_core::time_serde")]
+    #[schemars(with = "db_core::time_serde::Timestamp")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JobFilter {
+    pub state: Option<String>,
+    pub task: Option<String>,
+}
+
+/// New scheduling for a pending job; fields left out are unchanged
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct RescheduleJob {
+    #[serde(default, with = "db_core::time_serde::option")]
+    #[schemars(with = "Option<db_core::time_serde::Timestamp>")]
+    pub run_at: Option<OffsetDateTime>,
+    pub priority: Option<i16>,
+    pub max_attempts: Option<i16>,
+}
+
 pub struct WorkerService {
+    pool: DbPool,
     utils: WorkerUtils,
 }
 
@@ -31,7 +126,13 @@ impl WorkerService {
         // Create the utils helper
         let utils = worker.create_utils();
 
-        Ok(Self { utils })
+        Ok(Self { pool, utils })
+    }
+
+    /// Build the service around existing utils, without touching the database
+    #[cfg(test)]
+    pub fn with_utils(pool: DbPool, utils: WorkerUtils) -> Self {
+        Self { pool, utils }
     }
 
     /// Queue a job to be processed by the worker
@@ -44,7 +145,7 @@ impl WorkerService {
         let job: Job = self.utils
             .add_raw_job(
                 task_name,
-                payload,
+                with_trace_context(payload),
                 Default::default(),
             )
             .await
@@ -71,4 +172,240 @@ impl WorkerService {
 
         self.queue_job("send_email", payload).await
     }
+
+    /// Queue an immediate check of a monitor, outside its regular schedule
+    pub async fn queue_check(&self, monitor_id: i32) -> Result<String, WorkerError> {
+        let payload = serde_json::json!({ "monitor_id": monitor_id });
+
+        self.queue_job(schedule::TASK, payload).await
+    }
+
+    /// Run the monitor's scheduled check now, replacing the pending one. The
+    /// check then reschedules itself with the monitor's current interval.
+    pub async fn schedule_check(&self, monitor_id: i32) -> Result<String, WorkerError> {
+        let payload = serde_json::json!({ "monitor_id": monitor_id });
+        let spec = JobSpec {
+            job_key: Some(schedule::job_key(monitor_id)),
+            job_key_mode: Some(JobKeyMode::Replace),
+            ..Default::default()
+        };
+
+        let job = self.utils
+            .add_raw_job(schedule::TASK, with_trace_context(payload), spec)
+            .await
+            .map_err(|e| WorkerError::JobCreationError(e.to_string()))?;
+
+        info!("Scheduled check of monitor {} as job {}", monitor_id, job.id());
+        Ok(job.id().to_string())
+    }
+
+    /// Cancel the monitor's pending scheduled check, ending its check chain
+    pub async fn cancel_check(&self, monitor_id: i32) -> Result<(), WorkerError> {
+        self.utils
+            .remove_job(&schedule::job_key(monitor_id))
+            .await
+            .map_err(|e| WorkerError::JobRemovalFailed(e.to_string()))?;
+
+        info!("Cancelled scheduled checks of monitor {}", monitor_id);
+        Ok(())
+    }
+
+    /// Count pending jobs by task and state. Completed jobs are deleted by
+    /// graphile_worker, so they don't show up here.
+    pub async fn job_counts(&self) -> Result<Vec<JobCount>, WorkerError> {
+        let query = format!(
+            r#"
+            SELECT task_identifier AS task, {} AS state, COUNT(*) AS count
+            FROM graphile_worker.jobs
+            GROUP BY 1, 2
+            ORDER BY 1, 2
+            "#,
+            JOB_STATE
+        );
+
+        sqlx::query_as::<_, JobCount>(&query)
+            .fetch_all(self.pool.as_ref())
+            .await
+            .map_err(|e| WorkerError::JobQueryFailed(e.to_string()))
+    }
+
+    /// Page through pending jobs, newest first by default
+    pub async fn list_jobs(&self, filter: &JobFilter, page: &PageRequest) -> Result<Page<JobRecord>, WorkerError> {
+        let keyset = Keyset::new(JOB_ID_SORT, ID_KEY, page).map_err(|e| WorkerError::InvalidPage(e.to_string()))?;
+
+        let mut query = QueryBuilder::<Postgres>::new(job_select());
+        query.push(" WHERE TRUE");
+        if let Some(state) = &filter.state {
+            query.push(" AND state = ").push_bind(state.clone());
+        }
+        if let Some(task) = &filter.task {
+            query.push(" AND task_identifier = ").push_bind(task.clone());
+        }
+        keyset
+            .push_condition(&mut query)
+            .map_err(|e| WorkerError::InvalidPage(e.to_string()))?;
+        keyset.push_order(&mut query);
+
+        let jobs = query
+            .build_query_as::<JobRecord>()
+            .fetch_all(self.pool.as_ref())
+            .await
+            .map_err(|e| WorkerError::JobQueryFailed(e.to_string()))?;
+
+        Ok(keyset.into_page(jobs))
+    }
+
+    pub async fn get_job(&self, id: i64) -> Result<Option<JobRecord>, WorkerError> {
+        sqlx::query_as::<_, JobRecord>(&format!("{} WHERE id = $1", job_select()))
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await
+            .map_err(|e| WorkerError::JobQueryFailed(e.to_string()))
+    }
+
+    /// Run a job again as soon as possible with a fresh set of attempts.
+    /// Returns `false` if the job is gone or a worker holds it.
+    pub async fn retry_job(&self, id: i64) -> Result<bool, WorkerError> {
+        let options = RescheduleJobOptions {
+            run_at: Some(Utc::now()),
+            attempts: Some(0),
+            ..Default::default()
+        };
+        let updated = self.utils
+            .reschedule_jobs(&[id], options)
+            .await
+            .map_err(|e| WorkerError::JobUpdateFailed(e.to_string()))?;
+
+        info!("Retrying job {}", id);
+        Ok(!updated.is_empty())
+    }
+
+    /// Change when and how a job runs. Returns `false` if the job is gone or
+    /// a worker holds it.
+    pub async fn reschedule_job(&self, id: i64, changes: &RescheduleJob) -> Result<bool, WorkerError> {
+        let options = RescheduleJobOptions {
+            run_at: changes.run_at.map(to_chrono),
+            priority: changes.priority,
+            max_attempts: changes.max_attempts,
+            ..Default::default()
+        };
+        let updated = self.utils
+            .reschedule_jobs(&[id], options)
+            .await
+            .map_err(|e| WorkerError::JobUpdateFailed(e.to_string()))?;
+
+        info!("Rescheduled job {}", id);
+        Ok(!updated.is_empty())
+    }
+
+    /// Delete a job so it never runs (again). Returns `false` if the job is
+    /// gone or a worker holds it.
+    pub async fn cancel_job(&self, id: i64) -> Result<bool, WorkerError> {
+        let removed = self.utils
+            .complete_jobs(&[id])
+            .await
+            .map_err(|e| WorkerError::JobRemovalFailed(e.to_string()))?;
+
+        info!("Cancelled job {}", id);
+        Ok(!removed.is_empty())
+    }
+}
+
+/// Carry the current trace in the payload, so the worker's span for the job
+/// joins the trace of the request that queued it
+fn with_trace_context(mut payload: Value) -> Value {
+    let carrier = telemetry::inject(&Span::current());
+    if let Some(fields) = payload.as_object_mut().filter(|_| !carrier.is_empty()) {
+        fields.insert(telemetry::PAYLOAD_KEY.to_string(), serde_json::json!(carrier));
+    }
+    payload
+}
+
+/// Jobs with their payload, which the public view leaves out, wrapped so
+/// filters and the keyset can refer to the derived `state`
+fn job_select() -> String {
+    format!(
+        r#"
+        SELECT * FROM (
+            SELECT id, queue_name, task_identifier,
+                (SELECT p.payload FROM graphile_worker._private_jobs p WHERE p.id = j.id) AS payload,
+                priority, {} AS state, attempts, max_attempts, last_error, key,
+                run_at, locked_at, locked_by, created_at, updated_at
+            FROM graphile_worker.jobs j
+        ) jobs
+        "#,
+        JOB_STATE
+    )
+}
+
+fn to_chrono(time: OffsetDateTime) -> DateTime<Utc> {
+    DateTime::from_timestamp(time.unix_timestamp(), time.nanosecond()).unwrap_or_else(Utc::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestState;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn jobs_are_listed_by_state_and_task_with_their_payload() {
+        let state = TestState::connect().await;
+        let pool = state.pool.as_ref();
+        let task = "job-list-test";
+
+        let mut ids = Vec::new();
+        for n in 0..4 {
+            let id: i64 = sqlx::query_scalar("SELECT id FROM graphile_worker.add_job($1, json_build_object('n', $2::int))")
+                .bind(task)
+                .bind(n)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+        // Oldest to newest: queued, retrying, failed, running
+        let states = [
+            "",
+            "attempts = 1",
+            "attempts = max_attempts",
+            "locked_at = now(), locked_by = 'job-list-test'",
+        ];
+        for (id, set) in ids.iter().zip(states).skip(1) {
+            sqlx::query(&format!("UPDATE graphile_worker._private_jobs SET {} WHERE id = $1", set))
+                .bind(id)
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+
+        let by_task = |state: Option<&str>| JobFilter { state: state.map(String::from), task: Some(task.into()) };
+        let first = PageRequest { limit: Some(3), ..Default::default() };
+        let page = state.worker.list_jobs(&by_task(None), &first).await.unwrap();
+        let listed: Vec<(i64, &str, &Value)> = page.data.iter().map(|job| (job.id, job.state.as_str(), &job.payload)).collect();
+        assert_eq!(listed, vec![
+            (ids[3], "running", &serde_json::json!({ "n": 3 })),
+            (ids[2], "failed", &serde_json::json!({ "n": 2 })),
+            (ids[1], "retrying", &serde_json::json!({ "n": 1 })),
+        ]);
+        assert!(page.has_more);
+
+        let next = PageRequest { limit: Some(3), cursor: page.next_cursor, ..Default::default() };
+        let rest = state.worker.list_jobs(&by_task(None), &next).await.unwrap();
+        assert_eq!(rest.data.iter().map(|job| (job.id, job.state.as_str())).collect::<Vec<_>>(), vec![(ids[0], "queued")]);
+        assert!(!rest.has_more);
+
+        for (id, expected) in ids.iter().zip(["queued", "retrying", "failed", "running"]) {
+            let filtered = state.worker.list_jobs(&by_task(Some(expected)), &PageRequest::default()).await.unwrap();
+            assert_eq!(filtered.data.iter().map(|job| job.id).collect::<Vec<_>>(), vec![*id], "state {}", expected);
+        }
+        let other_task = JobFilter { task: Some("job-list-test-other".into()), ..Default::default() };
+        assert!(state.worker.list_jobs(&other_task, &PageRequest::default()).await.unwrap().data.is_empty());
+
+        sqlx::query("DELETE FROM graphile_worker._private_jobs WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
 }
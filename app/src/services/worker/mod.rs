@@ -1,3 +1,3 @@
 pub mod job_queue;
 
-pub use job_queue::WorkerService;
+pub use job_queue::{JobFilter, JobRecord, RescheduleJob, WorkerService};
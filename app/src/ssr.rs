@@ -0,0 +1,247 @@
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use config_core::SsrConfig;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// What `axum_inertia`'s layouts render for the page to mount on
+const APP_ELEMENT: &str = r#"<div id="app" data-page=""#;
+
+/// Pages are far smaller. Responses known to be bigger, by their
+/// `Content-Length` or body, are passed through untouched; ones that only
+/// turn out bigger while being read fail with a 500.
+const MAX_PAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Renders Inertia pages to HTML through an Inertia SSR server
+/// (`@inertiajs/react/server`), which answers `POST /render` with the page
+/// markup and any `<head>` tags.
+pub struct SsrRenderer {
+    client: reqwest::Client,
+    render_url: String,
+    pages: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Rendered {
+    #[serde(default)]
+    head: Vec<String>,
+    body: String,
+}
+
+#[derive(Deserialize)]
+struct PageComponent {
+    component: String,
+}
+
+impl SsrRenderer {
+    pub fn new(config: &SsrConfig) -> Result<Self, reqwest::Error> {
+        Ok(Self {
+            client: reqwest::Client::builder().timeout(config.timeout()).build()?,
+            render_url: format!("{}/render", config.url.trim_end_matches('/')),
+            pages: config.pages.clone(),
+        })
+    }
+
+    /// The document with the page rendered into it, or `None` to leave it to
+    /// the browser: the page is not one to render, or the SSR server failed
+    /// or was too slow.
+    async fn render(&self, html: &str) -> Option<String> {
+        let start = html.find(APP_ELEMENT)?;
+        let attribute_start = start + APP_ELEMENT.len();
+        let attribute_len = html[attribute_start..].find('"')?;
+        let end = attribute_start + attribute_len + html[attribute_start + attribute_len..].find("</div>")? + "</div>".len();
+
+        let page = unescape(&html[attribute_start..attribute_start + attribute_len]);
+        let component = serde_json::from_str::<PageComponent>(&page).ok()?.component;
+        if !self.pages.contains(&component) {
+            return None;
+        }
+
+        let rendered = match self.request(page).await {
+            Ok(rendered) => rendered,
+            Err(e) => {
+                warn!("Server-side rendering of {} failed, rendering in the browser: {}", component, e);
+                return None;
+            }
+        };
+        debug!("Rendered {} on the server", component);
+
+        let head = rendered.head.concat();
+        let document = format!("{}{}{}", &html[..start], rendered.body, &html[end..]);
+        Some(document.replacen("</head>", &format!("{}</head>", head), 1))
+    }
+
+    async fn request(&self, page: String) -> Result<Rendered, Box<dyn std::error::Error + Send + Sync>> {
+        let response = self
+            .client
+            .post(&self.render_url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(page)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(serde_json::from_slice(&response.bytes().await?)?)
+    }
+}
+
+/// Middleware: renders first page loads of the configured pages on the
+/// server, so they read without scripts and to crawlers. Later Inertia visits
+/// are JSON and untouched.
+pub async fn render_pages(State(renderer): State<Arc<SsrRenderer>>, request: Request, next: Next) -> Response {
+    let page_load = request.method() == Method::GET && !request.headers().contains_key("x-inertia");
+    let response = next.run(request).await;
+
+    let is_html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    if !page_load || !is_html || too_big(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_PAGE_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            // Part of the body is gone, so it cannot be passed on
+            warn!("Failed to read page for server-side rendering: {}", e);
+            parts.headers.remove(header::CONTENT_LENGTH);
+            parts.status = StatusCode::INTERNAL_SERVER_ERROR;
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+
+    let rendered = match std::str::from_utf8(&bytes) {
+        Ok(html) => renderer.render(html).await,
+        Err(_) => None,
+    };
+    match rendered {
+        Some(html) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(html))
+        }
+        None => Response::from_parts(parts, Body::from(bytes)),
+    }
+}
+
+/// Whether the response says it is over [`MAX_PAGE_BYTES`] before any of it
+/// is read
+fn too_big(response: &Response) -> bool {
+    let declared = response
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    let least = declared.unwrap_or(0).max(response.body().size_hint().lower());
+    least > MAX_PAGE_BYTES as u64
+}
+
+// Reverses the attribute escaping of the layout, ampersands last
+fn unescape(attribute: &str) -> String {
+    attribute
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, middleware, routing::{get, post}, Json, Router};
+    use axum_inertia::Inertia;
+    use config_core::ServerConfig;
+    use serde_json::{json, Value};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tower::ServiceExt;
+
+    /// Stands in for `npm run ssr`, echoing the rendered page's props
+    async fn ssr_server(delay: Duration) -> String {
+        let app = Router::new().route(
+            "/render",
+            post(move |Json(page): Json<Value>| async move {
+                tokio::time::sleep(delay).await;
+                Json(json!({
+                    "head": ["<title inertia>Status</title>"],
+                    "body": format!("<div id=\"app\" data-server-rendered=\"true\">{}</div>", page["props"]["name"]),
+                }))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    async fn page(url: String, path: &str) -> String {
+        let config = SsrConfig {
+            enabled: true,
+            url,
+            timeout_ms: 200,
+            ..SsrConfig::default()
+        };
+        let renderer = Arc::new(SsrRenderer::new(&config).unwrap());
+        let inertia = crate::assets::inertia_config(&ServerConfig::default()).unwrap();
+
+        let app = Router::new()
+            .route("/status", get(|i: Inertia| async move { i.render("EnhancedStatusPage", json!({ "name": "R&D <API>" })) }))
+            .route("/dashboard", get(|i: Inertia| async move { i.render("Dashboard", json!({ "name": "Dashboard" })) }))
+            .route("/huge", get(|| async { axum::response::Html(huge_page()) }))
+            .layer(middleware::from_fn_with_state(renderer, render_pages))
+            .with_state(inertia);
+
+        let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn configured_pages_are_rendered_on_the_server() {
+        let url = ssr_server(Duration::ZERO).await;
+
+        let html = page(url.clone(), "/status").await;
+        assert!(html.contains(r#"<div id="app" data-server-rendered="true">"R&D <API>"</div>"#), "{}", html);
+        assert!(html.contains("<title inertia>Status</title></head>"));
+
+        // Pages not listed keep rendering in the browser
+        let html = page(url, "/dashboard").await;
+        assert!(html.contains(r#"<div id="app" data-page=""#));
+    }
+
+    /// A configured page over the size limit
+    fn huge_page() -> String {
+        let page = r#"{"component":"EnhancedStatusPage","props":{"name":"Huge"}}"#.replace('"', "&quot;");
+        format!(r#"<html><head></head><body><div id="app" data-page="{}"></div>{}</body></html>"#, page, " ".repeat(MAX_PAGE_BYTES))
+    }
+
+    #[tokio::test]
+    async fn pages_over_the_limit_pass_through_untouched() {
+        let url = ssr_server(Duration::ZERO).await;
+        let html = page(url, "/huge").await;
+        assert_eq!(html, huge_page());
+    }
+
+    #[tokio::test]
+    async fn pages_render_in_the_browser_when_ssr_is_unavailable() {
+        let slow = ssr_server(Duration::from_secs(5)).await;
+        let html = page(slow, "/status").await;
+        assert!(html.contains(r#"<div id="app" data-page=""#) && html.contains("EnhancedStatusPage"));
+
+        // Nothing listening
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let down = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let html = page(down, "/status").await;
+        assert!(html.contains(r#"<div id="app" data-page=""#));
+    }
+}
@@ -0,0 +1,17 @@
+import { createInertiaApp } from '@inertiajs/react';
+import createServer from '@inertiajs/react/server';
+import ReactDOMServer from 'react-dom/server';
+
+// Renders pages for the Rust app when `ssr.enabled` is set; listens on
+// port 13714 and answers POST /render with the page HTML
+createServer(page =>
+  createInertiaApp({
+    page,
+    render: ReactDOMServer.renderToString,
+    resolve: name => {
+      const pages = import.meta.glob('./views/pages/**/*.tsx', { eager: true });
+      return pages[`./views/pages/${name}.tsx`];
+    },
+    setup: ({ App, props }) => <App {...props} />,
+  }),
+);
@@ -0,0 +1,95 @@
+//! Fixtures shared by the route and service tests
+
+use axum::extract::FromRef;
+use axum::routing::get;
+use axum::Router;
+use axum_inertia::InertiaConfig;
+use db_core::DbPool;
+use graphile_worker::WorkerUtils;
+use sqlx::postgres::PgPoolOptions;
+use std::sync::Arc;
+use tower_sessions::{MemoryStore, Session, SessionManagerLayer};
+
+use crate::services::stream_service::StatusStream;
+use crate::services::worker::WorkerService;
+
+/// The parts of the app state the API routes need
+#[derive(Clone)]
+pub struct TestState {
+    pub pool: DbPool,
+    pub stream: Arc<StatusStream>,
+    pub worker: Arc<WorkerService>,
+}
+
+impl TestState {
+    /// State around `pool`; building it does not touch the database
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            worker: Arc::new(WorkerService::with_utils(
+                pool.clone(),
+                WorkerUtils::new(pool.as_ref().clone(), "graphile_worker".into()),
+            )),
+            stream: Arc::new(StatusStream::default()),
+            pool,
+        }
+    }
+
+    /// State for tests that never reach the database
+    pub fn offline() -> Self {
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        Self::new(Arc::new(pool))
+    }
+
+    /// State on the migrated database at `DATABASE_URL`, with the job queue's
+    /// schema in place
+    pub async fn connect() -> Self {
+        let pool: DbPool = Arc::new(sqlx::PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap());
+        let worker = WorkerService::new(pool.clone()).await.unwrap();
+        Self {
+            worker: Arc::new(worker),
+            ..Self::new(pool)
+        }
+    }
+}
+
+impl FromRef<TestState> for DbPool {
+    fn from_ref(state: &TestState) -> Self {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<TestState> for Arc<StatusStream> {
+    fn from_ref(state: &TestState) -> Self {
+        state.stream.clone()
+    }
+}
+
+impl FromRef<TestState> for Arc<WorkerService> {
+    fn from_ref(state: &TestState) -> Self {
+        state.worker.clone()
+    }
+}
+
+/// Pages render their props bare, without the app's layout
+impl FromRef<TestState> for InertiaConfig {
+    fn from_ref(_: &TestState) -> Self {
+        InertiaConfig::new(None, Box::new(|props| props))
+    }
+}
+
+/// Add sessions and a `/login` route that signs in as user 1
+pub fn with_sign_in(router: Router) -> Router {
+    router
+        .route(
+            "/login",
+            get(|session: Session| async move { session.insert("user_id", 1).await.unwrap() }),
+        )
+        .layer(SessionManagerLayer::new(MemoryStore::default()).with_secure(false))
+}
+
+/// The session cookie from a `/login` response
+pub fn session_cookie(login: &reqwest::Response) -> String {
+    login.headers()["set-cookie"].to_str().unwrap().split(';').next().unwrap().to_string()
+}
@@ -0,0 +1,55 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::collections::HashMap;
+use tracing::field::Empty;
+use tracing::{info_span, Instrument};
+
+/// Requests that matched no route share one span name, like their metrics
+const UNMATCHED_PATH: &str = "unmatched";
+
+/// W3C trace context headers a caller may send to continue its trace
+const TRACE_HEADERS: &[&str] = &["traceparent", "tracestate"];
+
+/// Middleware for the whole app: runs each request in a span named after its
+/// route. Repository calls and queued jobs made while handling it become
+/// part of the same trace.
+pub async fn trace_requests(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED_PATH, |path| path.as_str())
+        .to_string();
+
+    let span = info_span!(
+        "request",
+        otel.name = format!("{} {}", method, route),
+        otel.kind = "server",
+        otel.status_code = Empty,
+        http.request.method = method,
+        http.route = route,
+        url.path = request.uri().path(),
+        http.response.status_code = Empty,
+    );
+
+    let carrier: HashMap<String, String> = TRACE_HEADERS
+        .iter()
+        .filter_map(|name| {
+            let value = request.headers().get(*name)?.to_str().ok()?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect();
+    telemetry::set_parent(&span, &carrier);
+
+    let response = next.run(request).instrument(span.clone()).await;
+
+    let status = response.status();
+    span.record("http.response.status_code", status.as_u16());
+    if status.is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    response
+}
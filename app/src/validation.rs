@@ -0,0 +1,769 @@
+use db_core::models::incident::{CreateIncident, UpdateIncident};
+use db_core::models::maintenance_window::{CreateMaintenanceWindow, MaintenanceWindow, UpdateMaintenanceWindow};
+use db_core::models::monitor::{CreateMonitor, Monitor, UpdateMonitor};
+use db_core::models::service_group::{CreateServiceGroup, ServiceGroup, UpdateServiceGroup};
+use db_core::models::slo::{CreateSlo, UpdateSlo};
+use db_core::models::status_page::{CreateStatusPage, StatusPage, UpdateStatusPage};
+use reqwest::Url;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::net::IpAddr;
+use time::OffsetDateTime;
+
+use crate::services::worker::RescheduleJob;
+
+/// Shortest check interval the worker will schedule, in seconds
+pub const MIN_CHECK_INTERVAL: i32 = 10;
+/// Longest check interval, one day in seconds
+pub const MAX_CHECK_INTERVAL: i32 = 86_400;
+
+pub const MONITOR_TYPES: &[&str] = &["http", "tcp", "ping", "dns", "prometheus", "custom"];
+pub const INCIDENT_SEVERITIES: &[&str] = &["info", "warning", "critical"];
+pub const STATUS_PAGE_VISIBILITIES: &[&str] = &["public", "password", "sso"];
+pub const STATUS_ROLLUPS: &[&str] = &["worst", "weighted"];
+
+// Column widths from the monitors and incidents migrations
+const MAX_NAME_LENGTH: usize = 255;
+const MAX_URL_LENGTH: usize = 500;
+// From the status_pages migration
+const MAX_SLUG_LENGTH: usize = 64;
+/// Longest SLO window, as far back as the aggregates are kept
+pub const MAX_SLO_WINDOW_DAYS: i32 = 90;
+
+#[derive(Debug, Clone, Serialize, PartialEq, JsonSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Collects every problem with a payload so clients can fix them in one round trip
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: &str, code: &str, message: impl Into<String>) {
+        self.errors.push(FieldError::new(field, code, message));
+    }
+
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn into_inner(self) -> Vec<FieldError> {
+        self.errors
+    }
+}
+
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+impl Validate for CreateMonitor {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        check_name(&mut errors, "name", &self.name);
+        check_name(&mut errors, "display_name", &self.display_name);
+        check_monitor_config(
+            &mut errors,
+            &self.monitor_type,
+            self.url.as_deref(),
+            self.check_interval,
+            self.timeout,
+            self.metadata.as_ref(),
+        );
+        check_weight(&mut errors, self.weight);
+
+        errors.into_result()
+    }
+}
+
+/// Validate a partial update against the monitor it will be applied to, so
+/// cross-field rules such as timeout < interval hold for the merged result.
+pub fn validate_monitor_update(existing: &Monitor, update: &UpdateMonitor) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+
+    if let Some(name) = &update.name {
+        check_name(&mut errors, "name", name);
+    }
+    if let Some(display_name) = &update.display_name {
+        check_name(&mut errors, "display_name", display_name);
+    }
+
+    let monitor_type = update.monitor_type.as_deref().unwrap_or(&existing.monitor_type);
+    let url = update.url.as_deref().or(existing.url.as_deref());
+    check_monitor_config(
+        &mut errors,
+        monitor_type,
+        url,
+        update.check_interval.unwrap_or(existing.check_interval),
+        update.timeout.unwrap_or(existing.timeout),
+        update.metadata.as_ref().or(existing.metadata.as_ref()),
+    );
+    if let Some(weight) = update.weight {
+        check_weight(&mut errors, weight);
+    }
+
+    errors.into_result()
+}
+
+impl Validate for CreateIncident {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        check_name(&mut errors, "title", &self.title);
+        check_severity(&mut errors, &self.severity);
+
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateIncident {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        if let Some(title) = &self.title {
+            check_name(&mut errors, "title", title);
+        }
+        if let Some(severity) = &self.severity {
+            check_severity(&mut errors, severity);
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for CreateMaintenanceWindow {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        check_name(&mut errors, "title", &self.title);
+        check_window(&mut errors, self.starts_at, self.ends_at);
+
+        errors.into_result()
+    }
+}
+
+/// Validate a partial update against the window it will be applied to, so a
+/// new start or end still leaves the window ending after it starts.
+pub fn validate_maintenance_update(
+    existing: &MaintenanceWindow,
+    update: &UpdateMaintenanceWindow,
+) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+
+    if let Some(title) = &update.title {
+        check_name(&mut errors, "title", title);
+    }
+    check_window(
+        &mut errors,
+        update.starts_at.unwrap_or(existing.starts_at),
+        update.ends_at.unwrap_or(existing.ends_at),
+    );
+
+    errors.into_result()
+}
+
+impl Validate for CreateServiceGroup {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        check_name(&mut errors, "name", &self.name);
+        check_weight(&mut errors, self.weight);
+        check_status_rollup(&mut errors, &self.status_rollup);
+
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateServiceGroup {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        if let Some(name) = &self.name {
+            check_name(&mut errors, "name", name);
+        }
+        if let Some(weight) = self.weight {
+            check_weight(&mut errors, weight);
+        }
+        if let Some(status_rollup) = &self.status_rollup {
+            check_status_rollup(&mut errors, status_rollup);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Check that nesting group `id` (`None` for a new group) in `parent_id`
+/// names an existing group and does not make the group its own ancestor
+pub fn validate_group_parent(groups: &[ServiceGroup], id: Option<i32>, parent_id: i32) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+
+    let mut ancestor = Some(parent_id);
+    // A chain longer than the number of groups already contains a cycle
+    for _ in 0..=groups.len() {
+        let Some(ancestor_id) = ancestor else { break };
+        if Some(ancestor_id) == id {
+            errors.add("parent_id", "cycle", "a group cannot be nested in itself or one of its subgroups");
+            break;
+        }
+        let Some(group) = groups.iter().find(|group| group.id == ancestor_id) else {
+            if ancestor_id == parent_id {
+                errors.add("parent_id", "not_found", format!("service group {} does not exist", parent_id));
+            }
+            break;
+        };
+        ancestor = group.parent_id;
+    }
+
+    errors.into_result()
+}
+
+/// A `from`/`to` query range, where either end may be left open
+pub fn validate_range(from: Option<OffsetDateTime>, to: Option<OffsetDateTime>) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            errors.add("to", "out_of_range", "to must not be before from");
+        }
+    }
+
+    errors.into_result()
+}
+
+impl Validate for CreateSlo {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        check_name(&mut errors, "name", &self.name);
+        if self.monitor_id.is_some() == self.service_group_id.is_some() {
+            errors.add("monitor_id", "invalid_choice", "set exactly one of monitor_id and service_group_id");
+        }
+        check_slo_target(&mut errors, self.target);
+        check_slo_window(&mut errors, self.window_days);
+        if let Some(latency_p95_ms) = self.latency_p95_ms {
+            check_latency(&mut errors, latency_p95_ms);
+        }
+        if let Some(alert_email) = &self.alert_email {
+            check_alert_email(&mut errors, alert_email);
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for UpdateSlo {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        if let Some(name) = &self.name {
+            check_name(&mut errors, "name", name);
+        }
+        if let Some(target) = self.target {
+            check_slo_target(&mut errors, target);
+        }
+        if let Some(window_days) = self.window_days {
+            check_slo_window(&mut errors, window_days);
+        }
+        // 0 removes the latency objective
+        if let Some(latency_p95_ms) = self.latency_p95_ms.filter(|latency| *latency != 0) {
+            check_latency(&mut errors, latency_p95_ms);
+        }
+        // Empty turns alerts off
+        if let Some(alert_email) = self.alert_email.as_deref().filter(|email| !email.is_empty()) {
+            check_alert_email(&mut errors, alert_email);
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for CreateStatusPage {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        check_slug(&mut errors, &self.slug);
+        check_name(&mut errors, "title", &self.title);
+        check_page_links(&mut errors, self.logo_url.as_deref(), self.custom_domain.as_deref());
+        check_visibility(&mut errors, &self.visibility, self.password.is_some());
+        if let Some(password) = &self.password {
+            check_password(&mut errors, password);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Validate a partial update against the page it will be applied to, so a
+/// page made password-protected ends up with a password.
+pub fn validate_status_page_update(existing: &StatusPage, update: &UpdateStatusPage) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+
+    if let Some(slug) = &update.slug {
+        check_slug(&mut errors, slug);
+    }
+    if let Some(title) = &update.title {
+        check_name(&mut errors, "title", title);
+    }
+    check_page_links(&mut errors, update.logo_url.as_deref(), update.custom_domain.as_deref());
+    check_visibility(
+        &mut errors,
+        update.visibility.as_deref().unwrap_or(&existing.visibility),
+        update.password.is_some() || existing.password_hash.is_some(),
+    );
+    if let Some(password) = &update.password {
+        check_password(&mut errors, password);
+    }
+
+    errors.into_result()
+}
+
+impl Validate for RescheduleJob {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        if self.run_at.is_none() && self.priority.is_none() && self.max_attempts.is_none() {
+            errors.add("body", "required", "Set at least one of run_at, priority or max_attempts");
+        }
+        if self.max_attempts.is_some_and(|attempts| attempts < 1) {
+            errors.add("max_attempts", "out_of_range", "max_attempts must be at least 1");
+        }
+
+        errors.into_result()
+    }
+}
+
+fn check_name(errors: &mut ValidationErrors, field: &str, value: &str) {
+    if value.trim().is_empty() {
+        errors.add(field, "required", format!("{} must not be empty", field));
+    } else if value.chars().count() > MAX_NAME_LENGTH {
+        errors.add(field, "too_long", format!("{} must be at most {} characters", field, MAX_NAME_LENGTH));
+    }
+}
+
+fn check_weight(errors: &mut ValidationErrors, weight: i32) {
+    if weight < 1 {
+        errors.add("weight", "out_of_range", "weight must be at least 1");
+    }
+}
+
+fn check_status_rollup(errors: &mut ValidationErrors, status_rollup: &str) {
+    if !STATUS_ROLLUPS.contains(&status_rollup) {
+        errors.add(
+            "status_rollup",
+            "invalid_choice",
+            format!("status_rollup must be one of: {}", STATUS_ROLLUPS.join(", ")),
+        );
+    }
+}
+
+fn check_slo_target(errors: &mut ValidationErrors, target: f64) {
+    if !(target > 0.0 && target < 100.0) {
+        errors.add("target", "out_of_range", "target must be a percentage above 0 and below 100");
+    }
+}
+
+fn check_slo_window(errors: &mut ValidationErrors, window_days: i32) {
+    if !(1..=MAX_SLO_WINDOW_DAYS).contains(&window_days) {
+        errors.add(
+            "window_days",
+            "out_of_range",
+            format!("window_days must be between 1 and {}", MAX_SLO_WINDOW_DAYS),
+        );
+    }
+}
+
+fn check_latency(errors: &mut ValidationErrors, latency_p95_ms: i32) {
+    if latency_p95_ms < 1 {
+        errors.add("latency_p95_ms", "out_of_range", "latency_p95_ms must be at least 1");
+    }
+}
+
+/// Just enough to catch a name or a URL pasted in by mistake
+fn check_alert_email(errors: &mut ValidationErrors, email: &str) {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => !local.is_empty() && domain.contains('.') && !domain.contains('@'),
+        None => false,
+    };
+    if !valid {
+        errors.add("alert_email", "invalid_format", "alert_email must be an email address");
+    } else if email.chars().count() > MAX_NAME_LENGTH {
+        errors.add("alert_email", "too_long", format!("alert_email must be at most {} characters", MAX_NAME_LENGTH));
+    }
+}
+
+fn check_severity(errors: &mut ValidationErrors, severity: &str) {
+    if !INCIDENT_SEVERITIES.contains(&severity) {
+        errors.add(
+            "severity",
+            "invalid_choice",
+            format!("severity must be one of: {}", INCIDENT_SEVERITIES.join(", ")),
+        );
+    }
+}
+
+fn check_window(errors: &mut ValidationErrors, starts_at: OffsetDateTime, ends_at: OffsetDateTime) {
+    if ends_at <= starts_at {
+        errors.add("ends_at", "out_of_range", "ends_at must be after starts_at");
+    }
+}
+
+/// Lowercase letters and digits in runs joined by single hyphens, as the
+/// slug appears in `/s/{slug}`
+fn check_slug(errors: &mut ValidationErrors, slug: &str) {
+    let valid_run = |run: &str| !run.is_empty() && run.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+
+    if slug.is_empty() {
+        errors.add("slug", "required", "slug must not be empty");
+    } else if slug.len() > MAX_SLUG_LENGTH {
+        errors.add("slug", "too_long", format!("slug must be at most {} characters", MAX_SLUG_LENGTH));
+    } else if !slug.split('-').all(valid_run) {
+        errors.add(
+            "slug",
+            "invalid_format",
+            "slug must be lowercase letters and digits, separated by single hyphens",
+        );
+    }
+}
+
+/// Empty values clear these fields, so only non-empty ones are checked
+fn check_page_links(errors: &mut ValidationErrors, logo_url: Option<&str>, custom_domain: Option<&str>) {
+    if let Some(logo_url) = logo_url.filter(|url| !url.is_empty()) {
+        let is_web_url = Url::parse(logo_url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+        if logo_url.len() > MAX_URL_LENGTH {
+            errors.add("logo_url", "too_long", format!("logo_url must be at most {} characters", MAX_URL_LENGTH));
+        } else if !is_web_url {
+            errors.add("logo_url", "invalid_url", "logo_url must be an http or https URL");
+        }
+    }
+
+    if let Some(domain) = custom_domain.map(str::trim).filter(|domain| !domain.is_empty()) {
+        if domain.parse::<IpAddr>().is_ok() {
+            errors.add("custom_domain", "invalid_host", "custom_domain must be a host name, not an IP address");
+        } else if let Err(message) = check_host(domain) {
+            errors.add("custom_domain", "invalid_host", message);
+        }
+    }
+}
+
+fn check_visibility(errors: &mut ValidationErrors, visibility: &str, has_password: bool) {
+    if !STATUS_PAGE_VISIBILITIES.contains(&visibility) {
+        errors.add(
+            "visibility",
+            "invalid_choice",
+            format!("visibility must be one of: {}", STATUS_PAGE_VISIBILITIES.join(", ")),
+        );
+    } else if visibility == "password" && !has_password {
+        errors.add("password", "required", "password is required for password-protected pages");
+    }
+}
+
+fn check_password(errors: &mut ValidationErrors, password: &str) {
+    if password.is_empty() {
+        errors.add("password", "required", "password must not be empty");
+    }
+}
+
+fn check_monitor_config(
+    errors: &mut ValidationErrors,
+    monitor_type: &str,
+    url: Option<&str>,
+    check_interval: i32,
+    timeout: i32,
+    metadata: Option<&Value>,
+) {
+    if !(MIN_CHECK_INTERVAL..=MAX_CHECK_INTERVAL).contains(&check_interval) {
+        errors.add(
+            "check_interval",
+            "out_of_range",
+            format!(
+                "check_interval must be between {} and {} seconds",
+                MIN_CHECK_INTERVAL, MAX_CHECK_INTERVAL
+            ),
+        );
+    }
+
+    if timeout < 1 {
+        errors.add("timeout", "out_of_range", "timeout must be at least 1 second");
+    } else if timeout >= check_interval {
+        errors.add("timeout", "must_be_less_than_interval", "timeout must be less than check_interval");
+    }
+
+    if !MONITOR_TYPES.contains(&monitor_type) {
+        errors.add(
+            "monitor_type",
+            "invalid_choice",
+            format!("monitor_type must be one of: {}", MONITOR_TYPES.join(", ")),
+        );
+        return;
+    }
+
+    if monitor_type == "prometheus" {
+        if let Err(message) = monitor_checks::prometheus::rules(metadata) {
+            errors.add("metadata", "invalid_format", message);
+        }
+    }
+
+    let url = url.map(str::trim).filter(|u| !u.is_empty());
+    let Some(url) = url else {
+        if monitor_type != "custom" {
+            errors.add("url", "required", format!("url is required for {} monitors", monitor_type));
+        }
+        return;
+    };
+
+    if url.len() > MAX_URL_LENGTH {
+        errors.add("url", "too_long", format!("url must be at most {} characters", MAX_URL_LENGTH));
+        return;
+    }
+
+    let result = match monitor_type {
+        "http" | "prometheus" => check_http_url(url),
+        "tcp" => check_tcp_address(url),
+        "ping" | "dns" => check_host(url),
+        _ => Ok(()),
+    };
+
+    if let Err(message) = result {
+        errors.add("url", "invalid_format", message);
+    }
+}
+
+fn check_http_url(url: &str) -> Result<(), String> {
+    let parsed = Url::parse(url).map_err(|e| format!("url is not a valid URL: {}", e))?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("url must use the http or https scheme".to_string());
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err("url must include a host".to_string());
+    }
+
+    Ok(())
+}
+
+/// Accepts `host:port` or `tcp://host:port`, matching what the worker parses
+fn check_tcp_address(url: &str) -> Result<(), String> {
+    let address = url.strip_prefix("tcp://").unwrap_or(url);
+    let (host, port) = address
+        .rsplit_once(':')
+        .ok_or_else(|| "url must be in the form host:port or tcp://host:port".to_string())?;
+
+    match port.parse::<u16>() {
+        Ok(port) if port > 0 => {}
+        _ => return Err("url must include a port between 1 and 65535".to_string()),
+    }
+
+    check_host(host.trim_start_matches('[').trim_end_matches(']'))
+}
+
+fn check_host(host: &str) -> Result<(), String> {
+    if host.parse::<IpAddr>().is_ok() {
+        return Ok(());
+    }
+
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+
+    if host.len() <= 253 && host.trim_end_matches('.').split('.').all(valid_label) {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a valid hostname or IP address", host))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(monitor_type: &str, url: Option<&str>) -> CreateMonitor {
+        CreateMonitor {
+            name: "api".to_string(),
+            display_name: "API".to_string(),
+            description: None,
+            url: url.map(str::to_string),
+            monitor_type: monitor_type.to_string(),
+            check_interval: 60,
+            timeout: 10,
+            is_active: true,
+            metadata: None,
+            service_group_id: None,
+            weight: 1,
+        }
+    }
+
+    fn fields(result: Result<(), ValidationErrors>) -> Vec<String> {
+        result
+            .unwrap_err()
+            .into_inner()
+            .into_iter()
+            .map(|e| e.field)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_valid_monitors_of_each_type() {
+        assert!(monitor("http", Some("https://example.com/health")).validate().is_ok());
+        assert!(monitor("tcp", Some("tcp://db.example.com:5432")).validate().is_ok());
+        assert!(monitor("tcp", Some("10.0.0.1:6379")).validate().is_ok());
+        assert!(monitor("ping", Some("example.com")).validate().is_ok());
+        assert!(monitor("prometheus", Some("http://api.internal:9090/metrics")).validate().is_ok());
+        assert!(monitor("custom", None).validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_urls_that_do_not_match_the_monitor_type() {
+        assert_eq!(fields(monitor("http", Some("ftp://example.com")).validate()), ["url"]);
+        assert_eq!(fields(monitor("tcp", Some("db.example.com")).validate()), ["url"]);
+        assert_eq!(fields(monitor("dns", Some("https://example.com")).validate()), ["url"]);
+        assert_eq!(fields(monitor("http", None).validate()), ["url"]);
+    }
+
+    #[test]
+    fn rejects_invalid_prometheus_rules() {
+        let mut m = monitor("prometheus", Some("http://api.internal:9090/metrics"));
+        m.metadata = Some(serde_json::json!({ "rules": [{ "expr": "up{job=\"api\"} < 1", "status": "degraded" }] }));
+        assert!(m.validate().is_ok());
+
+        m.metadata = Some(serde_json::json!({ "rules": [{ "expr": "up is down" }] }));
+        assert_eq!(fields(m.validate()), ["metadata"]);
+    }
+
+    #[test]
+    fn enforces_interval_bounds_and_timeout_below_interval() {
+        let mut m = monitor("http", Some("https://example.com"));
+        m.check_interval = 5;
+        m.timeout = 5;
+        assert_eq!(fields(m.validate()), ["check_interval", "timeout"]);
+
+        let mut m = monitor("http", Some("https://example.com"));
+        m.timeout = 60;
+        assert_eq!(fields(m.validate()), ["timeout"]);
+    }
+
+    #[test]
+    fn reschedules_must_change_something_and_keep_an_attempt() {
+        assert_eq!(fields(RescheduleJob::default().validate()), ["body"]);
+
+        let reschedule = RescheduleJob { max_attempts: Some(0), ..Default::default() };
+        assert_eq!(fields(reschedule.validate()), ["max_attempts"]);
+
+        let reschedule = RescheduleJob { priority: Some(-5), ..Default::default() };
+        assert!(reschedule.validate().is_ok());
+    }
+
+    #[test]
+    fn status_pages_need_a_clean_slug_and_a_password_when_protected() {
+        let page = |slug: &str, visibility: &str, password: Option<&str>| CreateStatusPage {
+            slug: slug.to_string(),
+            title: "Acme status".to_string(),
+            description: None,
+            logo_url: None,
+            custom_domain: Some("status.acme.test".to_string()),
+            monitor_ids: vec![],
+            service_group_ids: vec![],
+            visibility: visibility.to_string(),
+            password: password.map(str::to_string),
+            show_internal_details: false,
+        };
+
+        assert!(page("acme-eu", "public", None).validate().is_ok());
+        assert!(page("acme", "password", Some("hunter2")).validate().is_ok());
+        assert_eq!(fields(page("Acme--eu", "public", None).validate()), ["slug"]);
+        assert_eq!(fields(page("acme", "password", None).validate()), ["password"]);
+        assert_eq!(fields(page("acme", "internal", None).validate()), ["visibility"]);
+
+        let mut ip_domain = page("acme", "sso", None);
+        ip_domain.custom_domain = Some("10.0.0.1".to_string());
+        assert_eq!(fields(ip_domain.validate()), ["custom_domain"]);
+    }
+
+    #[test]
+    fn groups_cannot_be_nested_in_themselves() {
+        let now = OffsetDateTime::now_utc();
+        let group = |id: i32, parent_id: Option<i32>| ServiceGroup {
+            id,
+            name: format!("group-{}", id),
+            description: None,
+            parent_id,
+            position: 0,
+            weight: 1,
+            status_rollup: "worst".to_string(),
+            created_at: now,
+            updated_at: now,
+        };
+        // 3 is nested in 2, which is nested in 1
+        let groups = [group(1, None), group(2, Some(1)), group(3, Some(2))];
+
+        assert!(validate_group_parent(&groups, None, 3).is_ok());
+        assert!(validate_group_parent(&groups, Some(3), 1).is_ok());
+        assert_eq!(fields(validate_group_parent(&groups, Some(1), 3)), ["parent_id"]);
+        assert_eq!(fields(validate_group_parent(&groups, Some(2), 2)), ["parent_id"]);
+        assert_eq!(fields(validate_group_parent(&groups, None, 42)), ["parent_id"]);
+    }
+
+    #[test]
+    fn ranges_cannot_end_before_they_start() {
+        let now = OffsetDateTime::now_utc();
+
+        assert!(validate_range(Some(now), Some(now)).is_ok());
+        assert!(validate_range(Some(now), None).is_ok());
+        assert_eq!(fields(validate_range(Some(now), Some(now - time::Duration::hours(1)))), ["to"]);
+    }
+
+    #[test]
+    fn slos_need_one_subject_and_a_reachable_target() {
+        let slo = |monitor_id: Option<i32>, service_group_id: Option<i32>, target: f64| CreateSlo {
+            name: "API availability".to_string(),
+            description: None,
+            monitor_id,
+            service_group_id,
+            target,
+            window_days: 30,
+            latency_p95_ms: Some(500),
+            alert_email: Some("oncall@example.com".to_string()),
+        };
+
+        assert!(slo(Some(1), None, 99.9).validate().is_ok());
+        assert!(slo(None, Some(1), 99.0).validate().is_ok());
+        assert_eq!(fields(slo(Some(1), Some(1), 99.9).validate()), ["monitor_id"]);
+        assert_eq!(fields(slo(None, None, 99.9).validate()), ["monitor_id"]);
+        assert_eq!(fields(slo(Some(1), None, 100.0).validate()), ["target"]);
+
+        let mut long_window = slo(Some(1), None, 99.9);
+        long_window.window_days = 365;
+        long_window.alert_email = Some("oncall".to_string());
+        assert_eq!(fields(long_window.validate()), ["window_days", "alert_email"]);
+
+        let clear = UpdateSlo {
+            latency_p95_ms: Some(0),
+            alert_email: Some(String::new()),
+            ..Default::default()
+        };
+        assert!(clear.validate().is_ok());
+    }
+}
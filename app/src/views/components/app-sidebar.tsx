@@ -1,9 +1,12 @@
 import * as React from "react"
 import {
   IconChartBar,
+  IconClock,
   IconDashboard,
+  IconHistory,
   IconInnerShadowTop,
   IconListDetails,
+  IconTarget,
 } from "@tabler/icons-react"
 
 import { NavMain } from "@/views/components/nav-main"
@@ -40,6 +43,21 @@ const data = {
       url: "/status",
       icon: IconChartBar,
     },
+    {
+      title: "SLOs",
+      url: "/slos",
+      icon: IconTarget,
+    },
+    {
+      title: "Jobs",
+      url: "/jobs",
+      icon: IconClock,
+    },
+    {
+      title: "Audit Log",
+      url: "/audit-log",
+      icon: IconHistory,
+    },
   ],
   navClouds: [],
   navSecondary: [],
@@ -328,7 +328,7 @@ const columns: ColumnDef<MonitorData>[] = [
         }
         
         try {
-          router.delete(`/api/monitors/${monitor.id}`, {
+          router.delete(`/api/v1/monitors/${monitor.id}`, {
             onSuccess: () => {
               toast.success("Monitor deleted successfully!")
             },
@@ -0,0 +1,71 @@
+import * as React from "react"
+import { router } from "@inertiajs/react"
+
+const SOCKET_PATH = "/ws/dashboard"
+const RECONNECT_DELAY_MS = 5000
+
+// Bursts of events (e.g. every monitor checked at once) trigger a single reload
+const RELOAD_DELAY_MS = 1000
+
+export type DashboardCommand =
+  | { type: "check_now"; monitor_id: number }
+  | { type: "pause_monitor"; monitor_id: number }
+  | { type: "resume_monitor"; monitor_id: number }
+  | { type: "acknowledge_incident"; incident_id: number }
+
+/**
+ * Keep the dashboard live over its WebSocket: props are reloaded when a
+ * status event or incident change arrives, or after a command succeeds.
+ * Returns a `send` function for dashboard commands.
+ */
+export function useDashboardSocket() {
+  const socketRef = React.useRef<WebSocket | null>(null)
+
+  React.useEffect(() => {
+    let reloadTimer: number | undefined
+    let reconnectTimer: number | undefined
+    let closed = false
+
+    const scheduleReload = () => {
+      if (reloadTimer !== undefined) return
+      reloadTimer = window.setTimeout(() => {
+        reloadTimer = undefined
+        router.reload()
+      }, RELOAD_DELAY_MS)
+    }
+
+    const connect = () => {
+      const protocol = window.location.protocol === "https:" ? "wss:" : "ws:"
+      const socket = new WebSocket(`${protocol}//${window.location.host}${SOCKET_PATH}`)
+      socketRef.current = socket
+
+      socket.onmessage = (message) => {
+        const data = JSON.parse(message.data)
+        if (data.type === "error") {
+          console.error(`Dashboard command failed: ${data.message}`)
+        } else {
+          scheduleReload()
+        }
+      }
+      socket.onclose = () => {
+        socketRef.current = null
+        if (!closed) reconnectTimer = window.setTimeout(connect, RECONNECT_DELAY_MS)
+      }
+    }
+
+    connect()
+    return () => {
+      closed = true
+      socketRef.current?.close()
+      window.clearTimeout(reloadTimer)
+      window.clearTimeout(reconnectTimer)
+    }
+  }, [])
+
+  return React.useCallback((command: DashboardCommand) => {
+    const socket = socketRef.current
+    if (socket?.readyState === WebSocket.OPEN) {
+      socket.send(JSON.stringify({ id: crypto.randomUUID(), ...command }))
+    }
+  }, [])
+}
@@ -0,0 +1,37 @@
+import * as React from "react"
+import { router } from "@inertiajs/react"
+
+const STREAM_URL = "/api/v1/stream/status"
+const EVENT_TYPES = ["status_event", "monitor_state", "incident", "maintenance"]
+
+// Bursts of events (e.g. every monitor checked at once) trigger a single reload
+const RELOAD_DELAY_MS = 1000
+
+/**
+ * Reload the current page's props whenever the live status stream reports a
+ * change, optionally only for the given monitors. EventSource reconnects on
+ * its own and resumes from the last event it saw.
+ */
+export function useStatusStream(monitorIds?: number[]) {
+  const filter = monitorIds?.join(",") ?? ""
+
+  React.useEffect(() => {
+    const url = filter ? `${STREAM_URL}?monitor_id=${filter}` : STREAM_URL
+    const source = new EventSource(url)
+    let timer: number | undefined
+
+    const onChange = () => {
+      if (timer !== undefined) return
+      timer = window.setTimeout(() => {
+        timer = undefined
+        router.reload()
+      }, RELOAD_DELAY_MS)
+    }
+
+    EVENT_TYPES.forEach((type) => source.addEventListener(type, onChange))
+    return () => {
+      source.close()
+      window.clearTimeout(timer)
+    }
+  }, [filter])
+}
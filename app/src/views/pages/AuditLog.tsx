@@ -0,0 +1,203 @@
+import { router } from "@inertiajs/react"
+import MainLayout from "@/views/layouts/Main"
+import { ReactNode, useState } from "react"
+import { Button } from "@/views/components/ui/button"
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/views/components/ui/card"
+import { Badge } from "@/views/components/ui/badge"
+import { Input } from "@/views/components/ui/input"
+import { Table, TableBody, TableCell, TableHead, TableHeader, TableRow } from "@/views/components/ui/table"
+import { IconHistory, IconChevronLeft, IconChevronRight } from "@tabler/icons-react"
+
+interface AuditLogEntry {
+  id: number
+  actor_type: string
+  actor_id?: string
+  action: string
+  entity_type: string
+  entity_id?: string
+  changes?: Record<string, { before: unknown; after: unknown }>
+  ip_address?: string
+  created_at: string
+}
+
+interface AuditLogFilters {
+  actor_type?: string
+  actor_id?: string
+  action?: string
+  entity_type?: string
+  entity_id?: string
+}
+
+interface AuditLogProps {
+  entries: AuditLogEntry[]
+  total: number
+  page: number
+  perPage: number
+  filters: AuditLogFilters
+  error?: string
+}
+
+const actionVariant = (action: string) => {
+  switch (action) {
+    case "create":
+      return "default" as const
+    case "delete":
+      return "destructive" as const
+    default:
+      return "secondary" as const
+  }
+}
+
+const formatValue = (value: unknown) => {
+  if (value === null || value === undefined) return "—"
+  if (typeof value === "object") return JSON.stringify(value)
+  return String(value)
+}
+
+function AuditLogPage({ entries, total, page, perPage, filters, error }: AuditLogProps) {
+  const [form, setForm] = useState<AuditLogFilters>({
+    actor_id: filters.actor_id ?? "",
+    action: filters.action ?? "",
+    entity_type: filters.entity_type ?? "",
+    entity_id: filters.entity_id ?? "",
+  })
+
+  const totalPages = Math.max(1, Math.ceil(total / perPage))
+
+  const visit = (nextPage: number, nextFilters: AuditLogFilters = form) => {
+    // Drop empty values so the server does not receive blank filters
+    const params = Object.fromEntries(
+      Object.entries({ ...nextFilters, page: String(nextPage) }).filter(([, v]) => v)
+    )
+    router.get("/audit-log", params, { preserveState: true })
+  }
+
+  return (
+    <div className="container mx-auto px-4 py-6">
+      <div className="flex items-center justify-between mb-6">
+        <div>
+          <h1 className="text-2xl font-bold">Audit Log</h1>
+          <p className="text-muted-foreground">
+            Every configuration change, who made it and what changed
+          </p>
+        </div>
+        <IconHistory className="h-8 w-8 text-muted-foreground" />
+      </div>
+
+      <Card className="mb-6">
+        <CardHeader>
+          <CardTitle>Filters</CardTitle>
+          <CardDescription>Narrow the log by actor, action or entity</CardDescription>
+        </CardHeader>
+        <CardContent>
+          <form
+            className="grid grid-cols-1 md:grid-cols-5 gap-4"
+            onSubmit={(e) => {
+              e.preventDefault()
+              visit(1)
+            }}
+          >
+            <Input
+              placeholder="Actor ID"
+              value={form.actor_id}
+              onChange={(e) => setForm({ ...form, actor_id: e.target.value })}
+            />
+            <Input
+              placeholder="Action (create, update, delete)"
+              value={form.action}
+              onChange={(e) => setForm({ ...form, action: e.target.value })}
+            />
+            <Input
+              placeholder="Entity type (monitor, incident)"
+              value={form.entity_type}
+              onChange={(e) => setForm({ ...form, entity_type: e.target.value })}
+            />
+            <Input
+              placeholder="Entity ID"
+              value={form.entity_id}
+              onChange={(e) => setForm({ ...form, entity_id: e.target.value })}
+            />
+            <Button type="submit">Apply</Button>
+          </form>
+        </CardContent>
+      </Card>
+
+      {error && <p className="text-destructive mb-4">{error}</p>}
+
+      <Card>
+        <CardContent className="p-0">
+          <Table>
+            <TableHeader>
+              <TableRow>
+                <TableHead>Time</TableHead>
+                <TableHead>Actor</TableHead>
+                <TableHead>Action</TableHead>
+                <TableHead>Entity</TableHead>
+                <TableHead>Changes</TableHead>
+                <TableHead>IP</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {entries.length === 0 ? (
+                <TableRow>
+                  <TableCell colSpan={6} className="text-center text-muted-foreground py-8">
+                    No audit entries found
+                  </TableCell>
+                </TableRow>
+              ) : (
+                entries.map((entry) => (
+                  <TableRow key={entry.id}>
+                    <TableCell className="whitespace-nowrap">
+                      {new Date(entry.created_at).toLocaleString()}
+                    </TableCell>
+                    <TableCell>
+                      {entry.actor_type}
+                      {entry.actor_id && <span className="text-muted-foreground"> #{entry.actor_id}</span>}
+                    </TableCell>
+                    <TableCell>
+                      <Badge variant={actionVariant(entry.action)}>{entry.action}</Badge>
+                    </TableCell>
+                    <TableCell>
+                      {entry.entity_type} #{entry.entity_id}
+                    </TableCell>
+                    <TableCell className="text-xs">
+                      {entry.changes
+                        ? Object.entries(entry.changes).map(([field, change]) => (
+                            <div key={field}>
+                              <span className="font-medium">{field}</span>:{" "}
+                              <span className="text-muted-foreground">{formatValue(change.before)}</span>
+                              {" → "}
+                              {formatValue(change.after)}
+                            </div>
+                          ))
+                        : "—"}
+                    </TableCell>
+                    <TableCell className="text-muted-foreground">{entry.ip_address ?? "—"}</TableCell>
+                  </TableRow>
+                ))
+              )}
+            </TableBody>
+          </Table>
+        </CardContent>
+      </Card>
+
+      <div className="flex items-center justify-between mt-4">
+        <p className="text-sm text-muted-foreground">
+          {total} entries · page {page} of {totalPages}
+        </p>
+        <div className="flex gap-2">
+          <Button variant="outline" size="sm" disabled={page <= 1} onClick={() => visit(page - 1)}>
+            <IconChevronLeft className="h-4 w-4" />
+          </Button>
+          <Button variant="outline" size="sm" disabled={page >= totalPages} onClick={() => visit(page + 1)}>
+            <IconChevronRight className="h-4 w-4" />
+          </Button>
+        </div>
+      </div>
+    </div>
+  )
+}
+
+AuditLogPage.layout = (page: ReactNode) => <MainLayout children={page} />
+
+export default AuditLogPage
@@ -5,10 +5,11 @@ import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/vie
 import { Button } from "@/views/components/ui/button"
 import { Badge } from "@/views/components/ui/badge"
 import { router } from "@inertiajs/react"
-import { IconPlus, IconActivity, IconEye } from "@tabler/icons-react"
+import { IconPlus, IconActivity, IconEye, IconRefresh, IconPlayerPause, IconPlayerPlay } from "@tabler/icons-react"
 
 import data from "@/data/dashboard/data.json"
 import MainLayout from "@/views/layouts/Main"
+import { useDashboardSocket } from "@/views/hooks/use-dashboard-socket"
 import { ReactNode } from "react"
 
 interface Monitor {
@@ -67,6 +68,8 @@ const formatUptime = (percentage: number) => {
 }
 
 function Page({ message, statusData }: DashboardProps) {
+    const send = useDashboardSocket()
+
     const handleViewMonitors = () => {
         router.visit("/monitors")
     }
@@ -151,6 +154,26 @@ function Page({ message, statusData }: DashboardProps) {
                                                     {formatUptime(uptime_percentage)}
                                                 </span>
                                             </div>
+                                            <div className="flex gap-2 mt-3">
+                                                <Button
+                                                    variant="outline"
+                                                    size="sm"
+                                                    className="gap-1"
+                                                    onClick={() => send({ type: "check_now", monitor_id: monitor.id })}
+                                                >
+                                                    <IconRefresh className="h-4 w-4" />
+                                                    Check now
+                                                </Button>
+                                                <Button
+                                                    variant="outline"
+                                                    size="sm"
+                                                    className="gap-1"
+                                                    onClick={() => send({ type: monitor.is_active ? "pause_monitor" : "resume_monitor", monitor_id: monitor.id })}
+                                                >
+                                                    {monitor.is_active ? <IconPlayerPause className="h-4 w-4" /> : <IconPlayerPlay className="h-4 w-4" />}
+                                                    {monitor.is_active ? "Pause" : "Resume"}
+                                                </Button>
+                                            </div>
                                         </div>
                                     ))}
                                 </div>
@@ -8,6 +8,7 @@ import { ServiceGroupCard } from '@/views/components/service-group-card';
 import { CompactMonitorCard } from '@/views/components/compact-monitor-card';
 import { StatusTracker } from '@/views/components/status-tracker';
 import { Card } from '@/views/components/ui/card';
+import { useStatusStream } from '@/views/hooks/use-status-stream';
 
 interface StatusDailyStat {
   bucket: string | null;
@@ -26,7 +27,6 @@ interface Monitor {
   display_name: string;
   description?: string;
   metadata?: {
-    service_group?: string;
     service_category?: string;
     priority?: number;
   };
@@ -51,15 +51,55 @@ interface Incident {
   is_resolved: boolean;
 }
 
+interface MaintenanceWindow {
+  id: number;
+  title: string;
+  description?: string;
+  affected_monitors: number[];
+  starts_at: string;
+  ends_at: string;
+}
+
+interface GroupWithStatus {
+  group: {
+    id: number;
+    name: string;
+    description?: string;
+  };
+  monitor_ids: number[];
+  groups: GroupWithStatus[];
+  overall_status: string;
+}
+
 interface EnhancedStatusPageData {
   all_operational: boolean;
   last_updated: string;
   monitors: MonitorWithStatus[];
+  groups?: GroupWithStatus[];
   incidents?: Incident[];
+  maintenance?: MaintenanceWindow[];
+}
+
+const formatWindowTime = (time: string) =>
+  new Date(time).toLocaleString('en-US', {
+    month: 'short',
+    day: 'numeric',
+    hour: 'numeric',
+    minute: '2-digit',
+    timeZoneName: 'short'
+  });
+
+// Branding of a configured status page; absent on the default /status page
+interface StatusPageBranding {
+  slug: string;
+  title: string;
+  description?: string;
+  logo_url?: string;
 }
 
 interface EnhancedStatusPageProps {
   statusData: EnhancedStatusPageData;
+  page?: StatusPageBranding;
 }
 
 // Convert daily stats to tracker data format with missing days filled
@@ -130,56 +170,42 @@ const convertDailyStatsToTrackerData = (dailyStats: StatusDailyStat[], days: num
   return result;
 };
 
-// Group monitors by service
-const groupMonitorsByService = (monitors: MonitorWithStatus[]) => {
-  const groups = new Map<string, MonitorWithStatus[]>();
-  const ungrouped: MonitorWithStatus[] = [];
-  
-  monitors.forEach(monitor => {
-    const serviceGroup = monitor.monitor.metadata?.service_group;
-    
-    if (serviceGroup) {
-      if (!groups.has(serviceGroup)) {
-        groups.set(serviceGroup, []);
-      }
-      groups.get(serviceGroup)!.push(monitor);
-    } else {
-      ungrouped.push(monitor);
-    }
-  });
-  
-  // Convert to array and calculate overall status for each group
-  const serviceGroups = Array.from(groups.entries()).map(([name, monitors]) => {
-    // Determine overall status (worst status in the group)
-    let overallStatus = 'operational';
-    for (const monitor of monitors) {
-      if (monitor.current_status === 'major_outage') {
-        overallStatus = 'major_outage';
-        break;
-      } else if (monitor.current_status === 'partial_outage' && overallStatus !== 'major_outage') {
-        overallStatus = 'partial_outage';
-      } else if (monitor.current_status === 'degraded' && overallStatus === 'operational') {
-        overallStatus = 'degraded';
-      }
+// Flatten the server's group tree into cards, in the server's order.
+// Subgroups get their own card named after their parents.
+const groupMonitorsByService = (monitors: MonitorWithStatus[], groups: GroupWithStatus[]) => {
+  const byId = new Map(monitors.map(monitor => [monitor.monitor.id, monitor]));
+  const grouped = new Set<number>();
+  const serviceGroups: { name: string; description?: string; monitors: MonitorWithStatus[]; overallStatus: string }[] = [];
+
+  const visit = (group: GroupWithStatus, prefix: string) => {
+    const name = prefix ? `${prefix} / ${group.group.name}` : group.group.name;
+    const members = group.monitor_ids
+      .map(id => byId.get(id))
+      .filter((monitor): monitor is MonitorWithStatus => monitor !== undefined);
+    members.forEach(monitor => grouped.add(monitor.monitor.id));
+
+    if (members.length > 0) {
+      serviceGroups.push({
+        name,
+        description: group.group.description,
+        monitors: members,
+        overallStatus: group.overall_status,
+      });
     }
-    
-    return {
-      name,
-      monitors,
-      overallStatus,
-    };
-  });
-  
-  // Sort groups by priority or name
-  serviceGroups.sort((a, b) => a.name.localeCompare(b.name));
-  
+    group.groups.forEach(child => visit(child, name));
+  };
+  groups.forEach(group => visit(group, ''));
+
+  const ungrouped = monitors.filter(monitor => !grouped.has(monitor.monitor.id));
   return { serviceGroups, ungrouped };
 };
 
-export default function EnhancedStatusPage({ statusData }: EnhancedStatusPageProps) {
-  const { all_operational, last_updated, monitors, incidents = [] } = statusData;
+export default function EnhancedStatusPage({ statusData, page }: EnhancedStatusPageProps) {
+  const { all_operational, last_updated, monitors, groups = [], incidents = [], maintenance = [] } = statusData;
   const [viewMode, setViewMode] = React.useState<'grouped' | 'grid'>('grouped');
   const [trackerDays, setTrackerDays] = React.useState(14);
+  useStatusStream();
+  const feedBase = page ? `/s/${page.slug}` : '/status';
   
   
   // Process monitors with tracker data
@@ -189,7 +215,7 @@ export default function EnhancedStatusPage({ statusData }: EnhancedStatusPagePro
   }));
   
   // Group monitors by service (use processedMonitors instead of monitors)
-  const { serviceGroups, ungrouped } = groupMonitorsByService(processedMonitors);
+  const { serviceGroups, ungrouped } = groupMonitorsByService(processedMonitors, groups);
   
   // Calculate overall system status
   const operationalCount = monitors.filter(m => m.current_status === 'operational').length;
@@ -197,10 +223,27 @@ export default function EnhancedStatusPage({ statusData }: EnhancedStatusPagePro
   
   return (
     <>
-      <Head title="System Status" />
+      <Head title={page?.title ?? "System Status"}>
+        <link rel="alternate" type="application/atom+xml" title="Incidents" href={`${feedBase}/feed.atom`} />
+        <link rel="alternate" type="application/rss+xml" title="Incidents" href={`${feedBase}/feed.rss`} />
+      </Head>
       
       <div className="min-h-screen bg-background">
         <div className="mx-auto max-w-7xl px-4 py-8">
+          {page && (
+            <div className="mb-6 flex items-center gap-4">
+              {page.logo_url && (
+                <img src={page.logo_url} alt="" className="h-10 w-auto" />
+              )}
+              <div>
+                <h2 className="text-xl font-semibold text-foreground">{page.title}</h2>
+                {page.description && (
+                  <p className="text-sm text-muted-foreground">{page.description}</p>
+                )}
+              </div>
+            </div>
+          )}
+
           {/* Header */}
           <div className="mb-8">
             <div className="flex items-start justify-between mb-2">
@@ -250,6 +293,36 @@ export default function EnhancedStatusPage({ statusData }: EnhancedStatusPagePro
             monitors={monitors.map(m => m.monitor)}
           />
 
+          {/* Scheduled Maintenance */}
+          {maintenance.length > 0 && (
+            <div className="mb-6 space-y-2">
+              {maintenance.map((window) => {
+                const inProgress = new Date(window.starts_at) <= new Date();
+                return (
+                  <Card key={window.id} className="p-4 border-indigo-200 dark:border-indigo-800">
+                    <div className="flex items-start gap-3">
+                      <Settings className="size-5 text-indigo-500 mt-0.5" />
+                      <div className="flex-1">
+                        <div className="flex items-center justify-between gap-2">
+                          <h3 className="font-semibold text-foreground">{window.title}</h3>
+                          <span className="text-xs font-medium text-indigo-600 dark:text-indigo-400">
+                            {inProgress ? 'In progress' : 'Scheduled'}
+                          </span>
+                        </div>
+                        {window.description && (
+                          <p className="text-sm text-muted-foreground mt-1">{window.description}</p>
+                        )}
+                        <p className="text-xs text-muted-foreground mt-1">
+                          {formatWindowTime(window.starts_at)} – {formatWindowTime(window.ends_at)}
+                        </p>
+                      </div>
+                    </div>
+                  </Card>
+                );
+              })}
+            </div>
+          )}
+
           {/* View Modes */}
           {viewMode === 'grouped' ? (
             <div className="space-y-4">
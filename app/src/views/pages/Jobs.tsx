@@ -0,0 +1,235 @@
+import { router } from "@inertiajs/react"
+import MainLayout from "@/views/layouts/Main"
+import { ReactNode, useState } from "react"
+import { toast } from "sonner"
+import { Button } from "@/views/components/ui/button"
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/views/components/ui/card"
+import { Badge } from "@/views/components/ui/badge"
+import { Input } from "@/views/components/ui/input"
+import { Table, TableBody, TableCell, TableHead, TableHeader, TableRow } from "@/views/components/ui/table"
+import { IconClock, IconChevronRight } from "@tabler/icons-react"
+
+interface Job {
+  id: number
+  queue_name?: string
+  task_identifier: string
+  payload: unknown
+  priority: number
+  state: string
+  attempts: number
+  max_attempts: number
+  last_error?: string
+  key?: string
+  run_at: string
+  locked_at?: string
+  locked_by?: string
+  created_at: string
+  updated_at: string
+}
+
+interface JobFilters {
+  state?: string
+  task?: string
+}
+
+interface JobsProps {
+  jobs: { data: Job[]; next_cursor?: string; has_more: boolean }
+  states: string[]
+  filters: JobFilters
+  error?: string
+}
+
+const stateVariant = (state: string) => {
+  switch (state) {
+    case "failed":
+      return "destructive" as const
+    case "running":
+      return "default" as const
+    default:
+      return "secondary" as const
+  }
+}
+
+function JobsPage({ jobs, states, filters, error }: JobsProps) {
+  const [form, setForm] = useState<JobFilters>({
+    state: filters.state ?? "",
+    task: filters.task ?? "",
+  })
+  const [busy, setBusy] = useState<number | null>(null)
+
+  const visit = (cursor?: string) => {
+    // Drop empty values so the server does not receive blank filters
+    const params = Object.fromEntries(
+      Object.entries({ ...form, cursor }).filter(([, v]) => v)
+    )
+    router.get("/jobs", params, { preserveState: true })
+  }
+
+  const act = async (job: Job, action: "retry" | "cancel" | "reschedule", body?: object) => {
+    setBusy(job.id)
+
+    try {
+      const response = await fetch(`/api/v1/jobs/${job.id}/${action}`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: body ? JSON.stringify(body) : undefined,
+      })
+
+      if (!response.ok) {
+        const body = await response.json()
+        toast.error(body.error?.message || `Failed to ${action} job`)
+      } else {
+        toast.success(`Job #${job.id}: ${action} done`)
+        router.reload({ only: ["jobs"] })
+      }
+    } catch (error) {
+      toast.error("An unexpected error occurred")
+      console.error(error)
+    } finally {
+      setBusy(null)
+    }
+  }
+
+  return (
+    <div className="container mx-auto px-4 py-6">
+      <div className="flex items-center justify-between mb-6">
+        <div>
+          <h1 className="text-2xl font-bold">Jobs</h1>
+          <p className="text-muted-foreground">
+            Background jobs waiting to run, running or out of attempts
+          </p>
+        </div>
+        <IconClock className="h-8 w-8 text-muted-foreground" />
+      </div>
+
+      <Card className="mb-6">
+        <CardHeader>
+          <CardTitle>Filters</CardTitle>
+          <CardDescription>Narrow the queue by state or task</CardDescription>
+        </CardHeader>
+        <CardContent>
+          <form
+            className="grid grid-cols-1 md:grid-cols-3 gap-4"
+            onSubmit={(e) => {
+              e.preventDefault()
+              visit()
+            }}
+          >
+            <select
+              className="border-input h-9 rounded-md border bg-transparent px-3 text-sm"
+              value={form.state}
+              onChange={(e) => setForm({ ...form, state: e.target.value })}
+            >
+              <option value="">Any state</option>
+              {states.map((state) => (
+                <option key={state} value={state}>
+                  {state}
+                </option>
+              ))}
+            </select>
+            <Input
+              placeholder="Task (send_email, check_monitor)"
+              value={form.task}
+              onChange={(e) => setForm({ ...form, task: e.target.value })}
+            />
+            <Button type="submit">Apply</Button>
+          </form>
+        </CardContent>
+      </Card>
+
+      {error && <p className="text-destructive mb-4">{error}</p>}
+
+      <Card>
+        <CardContent className="p-0">
+          <Table>
+            <TableHeader>
+              <TableRow>
+                <TableHead>ID</TableHead>
+                <TableHead>Task</TableHead>
+                <TableHead>State</TableHead>
+                <TableHead>Attempts</TableHead>
+                <TableHead>Runs at</TableHead>
+                <TableHead>Last error</TableHead>
+                <TableHead />
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {jobs.data.length === 0 ? (
+                <TableRow>
+                  <TableCell colSpan={7} className="text-center text-muted-foreground py-8">
+                    No jobs found
+                  </TableCell>
+                </TableRow>
+              ) : (
+                jobs.data.map((job) => (
+                  <TableRow key={job.id}>
+                    <TableCell className="text-muted-foreground">#{job.id}</TableCell>
+                    <TableCell>
+                      {job.task_identifier}
+                      {job.key && <div className="text-xs text-muted-foreground">{job.key}</div>}
+                    </TableCell>
+                    <TableCell>
+                      <Badge variant={stateVariant(job.state)}>{job.state}</Badge>
+                    </TableCell>
+                    <TableCell>
+                      {job.attempts} / {job.max_attempts}
+                    </TableCell>
+                    <TableCell className="whitespace-nowrap">
+                      {new Date(job.run_at).toLocaleString()}
+                    </TableCell>
+                    <TableCell className="text-xs max-w-md truncate" title={job.last_error}>
+                      {job.last_error ?? "—"}
+                    </TableCell>
+                    <TableCell className="whitespace-nowrap text-right">
+                      {job.state !== "running" && (
+                        <div className="flex gap-2 justify-end">
+                          {job.attempts > 0 ? (
+                            <Button size="sm" variant="outline" disabled={busy === job.id} onClick={() => act(job, "retry")}>
+                              Retry
+                            </Button>
+                          ) : (
+                            <Button
+                              size="sm"
+                              variant="outline"
+                              disabled={busy === job.id}
+                              onClick={() => act(job, "reschedule", { run_at: new Date().toISOString() })}
+                            >
+                              Run now
+                            </Button>
+                          )}
+                          {job.attempts > 0 && (
+                            <Button size="sm" variant="destructive" disabled={busy === job.id} onClick={() => act(job, "cancel")}>
+                              Cancel
+                            </Button>
+                          )}
+                        </div>
+                      )}
+                    </TableCell>
+                  </TableRow>
+                ))
+              )}
+            </TableBody>
+          </Table>
+        </CardContent>
+      </Card>
+
+      <div className="flex items-center justify-between mt-4">
+        <p className="text-sm text-muted-foreground">
+          {jobs.data.length} jobs shown, newest first
+        </p>
+        <div className="flex gap-2">
+          <Button variant="outline" size="sm" onClick={() => visit()}>
+            Newest
+          </Button>
+          <Button variant="outline" size="sm" disabled={!jobs.has_more} onClick={() => visit(jobs.next_cursor)}>
+            <IconChevronRight className="h-4 w-4" />
+          </Button>
+        </div>
+      </div>
+    </div>
+  )
+}
+
+JobsPage.layout = (page: ReactNode) => <MainLayout children={page} />
+
+export default JobsPage
@@ -1,5 +1,6 @@
 import { router } from "@inertiajs/react"
 import MainLayout from "@/views/layouts/Main"
+import { useStatusStream } from "@/views/hooks/use-status-stream"
 import { ReactNode } from "react"
 import { Button } from "@/views/components/ui/button"
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/views/components/ui/card"
@@ -141,6 +142,8 @@ const formatRelativeTime = (dateString: string) => {
 }
 
 function MonitorDetailPage({ monitor, summary, tracker_data, recent_events }: MonitorDetailProps) {
+  useStatusStream([monitor.id])
+
   const handleBack = () => {
     router.visit("/monitors")
   }
@@ -12,7 +12,7 @@ import { Switch } from "@/views/components/ui/switch"
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/views/components/ui/card"
 
 import { Badge } from "@/views/components/ui/badge"
-import { IconArrowLeft, IconCheck, IconX, IconLoader2 } from "@tabler/icons-react"
+import { IconArrowLeft, IconCheck, IconX, IconLoader2, IconPlayerPlay } from "@tabler/icons-react"
 import { toast } from "sonner"
 
 interface Monitor {
@@ -44,6 +44,12 @@ interface MonitorFormData {
   check_interval: number
   timeout: number
   is_active: boolean
+  rules: string
+}
+
+interface PrometheusRule {
+  expr: string
+  status: string
 }
 
 const monitorTypes = [
@@ -51,16 +57,35 @@ const monitorTypes = [
   { value: "tcp", label: "TCP Port" },
   { value: "ping", label: "Ping" },
   { value: "dns", label: "DNS" },
+  { value: "prometheus", label: "Prometheus Metrics" },
   { value: "custom", label: "Custom" },
 ]
 
+// Prometheus rules are edited one per line as `expr => status`
+const formatRules = (rules: PrometheusRule[] = []) =>
+  rules.map((rule) => `${rule.expr} => ${rule.status}`).join("\n")
+
+const parseRules = (text: string): PrometheusRule[] =>
+  text
+    .split("\n")
+    .map((line) => line.trim())
+    .filter(Boolean)
+    .map((line) => {
+      const arrow = line.lastIndexOf("=>")
+      return arrow === -1
+        ? { expr: line, status: "major_outage" }
+        : { expr: line.slice(0, arrow).trim(), status: line.slice(arrow + 2).trim() }
+    })
+
 function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
   const [isSubmitting, setIsSubmitting] = useState(false)
+  const [isTesting, setIsTesting] = useState(false)
   const [selectedType, setSelectedType] = useState(monitor?.monitor_type || "http")
 
   const {
     register,
     handleSubmit,
+    getValues,
     setValue,
     watch,
     formState: { errors, isDirty },
@@ -74,9 +99,16 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
       check_interval: monitor?.check_interval || 300,
       timeout: monitor?.timeout || 30,
       is_active: monitor?.is_active ?? true,
+      rules: formatRules(monitor?.metadata?.rules),
     },
   })
 
+  // Only prometheus monitors carry settings in metadata; leave others untouched
+  const metadataFor = (data: MonitorFormData) =>
+    data.monitor_type === "prometheus"
+      ? { metadata: { ...monitor?.metadata, rules: parseRules(data.rules) } }
+      : {}
+
   const watchedIsActive = watch("is_active")
 
   const onSubmit = async (data: MonitorFormData) => {
@@ -93,10 +125,11 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
         check_interval: data.check_interval,
         timeout: data.timeout,
         is_active: data.is_active,
+        ...metadataFor(data),
       }
 
       if (isNew) {
-        router.post("/api/monitors", payload, {
+        router.post("/api/v1/monitors", payload, {
           onSuccess: () => {
             toast.success("Monitor created successfully!")
             router.visit("/monitors")
@@ -107,7 +140,7 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
           },
         })
       } else {
-        router.put(`/api/monitors/${monitor?.id}`, payload, {
+        router.put(`/api/v1/monitors/${monitor?.id}`, payload, {
           onSuccess: () => {
             toast.success("Monitor updated successfully!")
             router.visit("/monitors")
@@ -126,6 +159,46 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
     }
   }
 
+  // Runs the check against the current form values without saving them
+  const handleTest = async () => {
+    setIsTesting(true)
+
+    try {
+      const data = getValues()
+      const response = await fetch("/api/v1/monitors/check", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({
+          name: data.name,
+          display_name: data.display_name,
+          description: data.description,
+          url: data.url,
+          monitor_type: data.monitor_type,
+          check_interval: Number(data.check_interval),
+          timeout: Number(data.timeout),
+          is_active: data.is_active,
+          ...metadataFor(data),
+        }),
+      })
+      const body = await response.json()
+
+      if (!response.ok) {
+        const details = body.error?.details?.map((d: { message: string }) => d.message).join(", ")
+        toast.error(details || body.error?.message || "Failed to test monitor")
+      } else if (body.error_message) {
+        toast.error(`Check ${body.status}: ${body.error_message}`)
+      } else {
+        const code = body.status_code ? `HTTP ${body.status_code}, ` : ""
+        toast.success(`Check succeeded: ${body.status} (${code}${body.response_time}ms)`)
+      }
+    } catch (error) {
+      toast.error("An unexpected error occurred")
+      console.error(error)
+    } finally {
+      setIsTesting(false)
+    }
+  }
+
   const handleCancel = () => {
     if (isDirty) {
       if (confirm("You have unsaved changes. Are you sure you want to leave?")) {
@@ -143,7 +216,7 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
       setIsSubmitting(true)
       
       try {
-        await router.delete(`/api/monitors/${monitor.id}`, {
+        await router.delete(`/api/v1/monitors/${monitor.id}`, {
           onSuccess: () => {
             toast.success("Monitor deleted successfully!")
             router.visit("/monitors")
@@ -300,7 +373,8 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
 
               <div className="space-y-2">
                 <Label htmlFor="url">
-                  {selectedType === "http" ? "URL *" : 
+                  {selectedType === "http" ? "URL *" :
+                   selectedType === "prometheus" ? "Metrics URL *" : 
                    selectedType === "tcp" ? "Host:Port *" :
                    selectedType === "ping" ? "Host/IP *" :
                    selectedType === "dns" ? "Domain *" : "Target *"}
@@ -312,6 +386,7 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
                   })}
                   placeholder={
                     selectedType === "http" ? "https://example.com" :
+                    selectedType === "prometheus" ? "http://api.internal:9090/metrics" :
                     selectedType === "tcp" ? "example.com:80" :
                     selectedType === "ping" ? "example.com" :
                     selectedType === "dns" ? "example.com" : "Target to monitor"
@@ -323,6 +398,24 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
               </div>
             </div>
 
+            {selectedType === "prometheus" && (
+              <div className="space-y-2">
+                <Label htmlFor="rules">Rules</Label>
+                <Textarea
+                  id="rules"
+                  {...register("rules")}
+                  placeholder={'up{job="api"} < 1 => major_outage\nqueue_depth{queue="high"} > 1000 => degraded'}
+                  rows={4}
+                  className="font-mono text-sm"
+                />
+                <p className="text-xs text-muted-foreground">
+                  One rule per line: <code>metric{"{"}label="value"{"}"} &lt;op&gt; number =&gt; status</code>.
+                  A rule fires when a matching sample meets the comparison or no sample matches.
+                  Status is degraded, partial_outage or major_outage (the default).
+                </p>
+              </div>
+            )}
+
             <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
               <div className="space-y-2">
                 <Label htmlFor="check_interval">Check Interval (seconds) *</Label>
@@ -387,6 +480,20 @@ function MonitorEditPage({ monitor, isNew = false }: MonitorEditProps) {
           </div>
           
           <div className="flex items-center gap-3">
+            <Button
+              type="button"
+              variant="outline"
+              onClick={handleTest}
+              disabled={isSubmitting || isTesting}
+              className="gap-2"
+            >
+              {isTesting ? (
+                <IconLoader2 className="h-4 w-4 animate-spin" />
+              ) : (
+                <IconPlayerPlay className="h-4 w-4" />
+              )}
+              Test Configuration
+            </Button>
             <Button
               type="button"
               variant="outline"
@@ -0,0 +1,184 @@
+import MainLayout from "@/views/layouts/Main"
+import { ReactNode } from "react"
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/views/components/ui/card"
+import { Badge } from "@/views/components/ui/badge"
+import { Progress } from "@/views/components/ui/progress"
+import { Table, TableBody, TableCell, TableHead, TableHeader, TableRow } from "@/views/components/ui/table"
+import { IconTarget } from "@tabler/icons-react"
+
+type SloAlert = "ok" | "warning" | "critical"
+
+interface Slo {
+  id: number
+  name: string
+  description?: string
+  monitor_id?: number
+  service_group_id?: number
+  target: number
+  window_days: number
+  latency_p95_ms?: number
+  alert_email?: string
+  alert_status: SloAlert
+  alert_changed_at?: string
+}
+
+interface SloReportEntry {
+  slo: Slo
+  subject: string
+  monitor_ids: number[]
+  checks: { total: number; good: number }
+  sli?: number
+  error_budget_remaining: number
+  burn_rate_1h?: number
+  burn_rate_6h?: number
+  alert: SloAlert
+}
+
+interface SloReportProps {
+  slos: SloReportEntry[]
+  burnRates: { critical: number; warning: number }
+  error?: string
+}
+
+const alertVariant = (alert: SloAlert) => {
+  switch (alert) {
+    case "critical":
+      return "destructive" as const
+    case "warning":
+      return "default" as const
+    default:
+      return "secondary" as const
+  }
+}
+
+const formatPercent = (value?: number) => (value === undefined || value === null ? "—" : `${value.toFixed(3)}%`)
+
+const formatBurnRate = (value?: number) => (value === undefined || value === null ? "—" : `${value.toFixed(1)}×`)
+
+function SloReportPage({ slos, burnRates, error }: SloReportProps) {
+  const breached = slos.filter((entry) => entry.error_budget_remaining <= 0).length
+  const alerting = slos.filter((entry) => entry.alert !== "ok").length
+
+  return (
+    <div className="container mx-auto px-4 py-6">
+      <div className="flex items-center justify-between mb-6">
+        <div>
+          <h1 className="text-2xl font-bold">SLO Report</h1>
+          <p className="text-muted-foreground">
+            Objectives, error budget left over each window and how fast it is burning
+          </p>
+        </div>
+        <IconTarget className="h-8 w-8 text-muted-foreground" />
+      </div>
+
+      <div className="grid grid-cols-1 md:grid-cols-3 gap-4 mb-6">
+        <Card>
+          <CardHeader>
+            <CardDescription>Objectives</CardDescription>
+            <CardTitle className="text-3xl">{slos.length}</CardTitle>
+          </CardHeader>
+        </Card>
+        <Card>
+          <CardHeader>
+            <CardDescription>Burning too fast</CardDescription>
+            <CardTitle className="text-3xl">{alerting}</CardTitle>
+          </CardHeader>
+        </Card>
+        <Card>
+          <CardHeader>
+            <CardDescription>Budget spent</CardDescription>
+            <CardTitle className="text-3xl">{breached}</CardTitle>
+          </CardHeader>
+        </Card>
+      </div>
+
+      {error && <p className="text-destructive mb-4">{error}</p>}
+
+      <Card>
+        <CardHeader>
+          <CardTitle>Objectives</CardTitle>
+          <CardDescription>
+            Critical when the last hour burns at {burnRates.critical}× and the last six hours at{" "}
+            {burnRates.warning}×; a warning when either does alone or the budget is spent
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="p-0">
+          <Table>
+            <TableHeader>
+              <TableRow>
+                <TableHead>SLO</TableHead>
+                <TableHead>Covers</TableHead>
+                <TableHead>Objective</TableHead>
+                <TableHead>SLI</TableHead>
+                <TableHead className="w-48">Error budget left</TableHead>
+                <TableHead>Burn 1h</TableHead>
+                <TableHead>Burn 6h</TableHead>
+                <TableHead>Alert</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {slos.length === 0 ? (
+                <TableRow>
+                  <TableCell colSpan={8} className="text-center text-muted-foreground py-8">
+                    No SLOs yet. Create one with POST /api/v1/slos.
+                  </TableCell>
+                </TableRow>
+              ) : (
+                slos.map((entry) => {
+                  const remaining = entry.error_budget_remaining * 100
+                  return (
+                    <TableRow key={entry.slo.id}>
+                      <TableCell>
+                        <div className="font-medium">{entry.slo.name}</div>
+                        {entry.slo.description && (
+                          <div className="text-xs text-muted-foreground">{entry.slo.description}</div>
+                        )}
+                      </TableCell>
+                      <TableCell>
+                        {entry.subject || "—"}
+                        <div className="text-xs text-muted-foreground">
+                          {entry.slo.service_group_id ? "Group" : "Monitor"} · {entry.monitor_ids.length} monitor
+                          {entry.monitor_ids.length === 1 ? "" : "s"}
+                        </div>
+                      </TableCell>
+                      <TableCell className="whitespace-nowrap">
+                        {entry.slo.target}% over {entry.slo.window_days}d
+                        {entry.slo.latency_p95_ms && (
+                          <div className="text-xs text-muted-foreground">p95 ≤ {entry.slo.latency_p95_ms}ms</div>
+                        )}
+                      </TableCell>
+                      <TableCell>
+                        {formatPercent(entry.sli)}
+                        <div className="text-xs text-muted-foreground">
+                          {entry.checks.good} / {entry.checks.total} good
+                        </div>
+                      </TableCell>
+                      <TableCell>
+                        <Progress value={Math.max(0, Math.min(100, remaining))} />
+                        <div className="text-xs text-muted-foreground mt-1">{remaining.toFixed(1)}%</div>
+                      </TableCell>
+                      <TableCell>{formatBurnRate(entry.burn_rate_1h)}</TableCell>
+                      <TableCell>{formatBurnRate(entry.burn_rate_6h)}</TableCell>
+                      <TableCell>
+                        <Badge variant={alertVariant(entry.alert)}>{entry.alert}</Badge>
+                        {entry.slo.alert_changed_at && (
+                          <div className="text-xs text-muted-foreground mt-1">
+                            since {new Date(entry.slo.alert_changed_at).toLocaleString()}
+                          </div>
+                        )}
+                      </TableCell>
+                    </TableRow>
+                  )
+                })
+              )}
+            </TableBody>
+          </Table>
+        </CardContent>
+      </Card>
+    </div>
+  )
+}
+
+SloReportPage.layout = (page: ReactNode) => <MainLayout children={page} />
+
+export default SloReportPage
@@ -0,0 +1,62 @@
+import React from 'react';
+import { Head, useForm } from '@inertiajs/react';
+import { Lock } from 'lucide-react';
+import { Button } from '@/views/components/ui/button';
+import { Card } from '@/views/components/ui/card';
+import { Input } from '@/views/components/ui/input';
+import { Label } from '@/views/components/ui/label';
+
+interface StatusPageLockedProps {
+  page: {
+    slug: string;
+    title: string;
+    logo_url?: string;
+  };
+  error?: string;
+}
+
+export default function StatusPageLocked({ page, error }: StatusPageLockedProps) {
+  const { data, setData, post, processing } = useForm({ password: '' });
+
+  const submit = (e: React.FormEvent) => {
+    e.preventDefault();
+    post(`/s/${page.slug}/unlock`);
+  };
+
+  return (
+    <>
+      <Head title={page.title} />
+
+      <div className="min-h-screen bg-background flex items-center justify-center px-4">
+        <Card className="w-full max-w-sm p-6">
+          <div className="flex flex-col items-center gap-3 mb-6 text-center">
+            {page.logo_url ? (
+              <img src={page.logo_url} alt="" className="h-10 w-auto" />
+            ) : (
+              <Lock className="size-8 text-muted-foreground" />
+            )}
+            <h1 className="text-xl font-semibold text-foreground">{page.title}</h1>
+            <p className="text-sm text-muted-foreground">This status page is password protected.</p>
+          </div>
+
+          <form onSubmit={submit} className="space-y-4">
+            <div className="space-y-2">
+              <Label htmlFor="password">Password</Label>
+              <Input
+                id="password"
+                type="password"
+                autoFocus
+                value={data.password}
+                onChange={(e) => setData('password', e.target.value)}
+              />
+              {error && <p className="text-sm text-red-600">{error}</p>}
+            </div>
+            <Button type="submit" className="w-full" disabled={processing}>
+              View status
+            </Button>
+          </form>
+        </Card>
+      </div>
+    </>
+  );
+}
@@ -34,5 +34,9 @@ export default defineConfig({
   build: {
     outDir: 'dist',
     manifest: true,
+    rollupOptions: {
+      // The Inertia app, and the API reference page served by `routes::api_docs`
+      input: ['src/main.tsx', 'src/api-docs.ts'],
+    },
   },
 });
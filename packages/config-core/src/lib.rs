@@ -0,0 +1,603 @@
+//! Typed configuration shared by the app and the worker.
+//!
+//! Settings are read from a TOML file, `config.toml` in the working directory
+//! or the path in `RUSTGENIE_CONFIG`, then overridden by environment variables
+//! (and `.env`), so existing deployments keep working. [`Config::load`]
+//! validates the result and reports every problem at once.
+//!
+//! Logging and trace export stay on the standard `RUST_LOG` and
+//! `OTEL_EXPORTER_OTLP_*` variables.
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error;
+use url::Url;
+
+/// Environment variable naming the config file to read instead of `config.toml`
+pub const CONFIG_PATH_VAR: &str = "RUSTGENIE_CONFIG";
+
+const DEFAULT_PATH: &str = "config.toml";
+
+/// Shown in place of passwords and secret keys
+const REDACTED: &str = "********";
+
+/// Environment variables that override the file, and the setting each sets.
+/// Applied in order, so `OIDC_REDIRECT_URL` wins over `APP_URL`.
+pub const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("DATABASE_URL", "database.url"),
+    ("DATABASE_MAX_CONNECTIONS", "database.max_connections"),
+    ("APP_MODE", "server.mode"),
+    ("APP_DIST_DIR", "server.dist_dir"),
+    ("APP_BIND_ADDR", "server.bind"),
+    ("APP_URL", "server.app_url"),
+    ("OIDC_REDIRECT_URL", "server.app_url"),
+    ("VITE_PORT", "server.vite_port"),
+    ("TRUSTED_PROXIES", "server.trusted_proxies"),
+    ("OIDC_ISSUER_URL", "auth.issuer_url"),
+    ("OIDC_CLIENT_ID", "auth.client_id"),
+    ("OIDC_CLIENT_SECRET", "auth.client_secret"),
+    ("SESSION_EXPIRY", "auth.session_expiry_secs"),
+    ("SSR_ENABLED", "ssr.enabled"),
+    ("SSR_URL", "ssr.url"),
+    ("SSR_TIMEOUT_MS", "ssr.timeout_ms"),
+    ("WORKER_CONCURRENCY", "worker.concurrency"),
+    ("WORKER_HEALTH_PORT", "worker.health_port"),
+    ("WORKER_DEMO_JOB", "worker.demo_job"),
+    ("EXPORT_DIR", "export.dir"),
+    ("EXPORT_BUCKET", "export.bucket"),
+    ("EXPORT_ENDPOINT", "export.endpoint"),
+    ("EXPORT_REGION", "export.region"),
+    ("EXPORT_ACCESS_KEY_ID", "export.access_key_id"),
+    ("EXPORT_SECRET_ACCESS_KEY", "export.secret_access_key"),
+    ("EXPORT_PREFIX", "export.prefix"),
+    ("EXPORT_DEBOUNCE", "export.debounce_secs"),
+];
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Failed to read {path}: {source}")]
+    Read { path: PathBuf, source: std::io::Error },
+
+    #[error("Failed to parse {path}: {source}")]
+    Parse { path: PathBuf, source: toml::de::Error },
+
+    #[error("{var} (sets {key}): {message}")]
+    Env { var: &'static str, key: &'static str, message: String },
+
+    #[error("Invalid configuration:{}", .0.iter().map(|problem| format!("\n  - {}", problem)).collect::<String>())]
+    Invalid(Vec<String>),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub server: ServerConfig,
+    pub auth: AuthConfig,
+    pub ssr: SsrConfig,
+    pub worker: WorkerConfig,
+    pub export: ExportConfig,
+    /// The file the settings were read from, if any
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// `postgres://` connection URL; required
+    pub url: String,
+    pub max_connections: u32,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            max_connections: 10,
+        }
+    }
+}
+
+/// Where the app's frontend comes from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AppMode {
+    /// Pages load scripts from the Vite dev server, with hot reloading
+    #[default]
+    Development,
+    /// Pages load the hashed bundles built into `dist_dir`
+    Production,
+}
+
+impl FromStr for AppMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "development" => Ok(Self::Development),
+            "production" => Ok(Self::Production),
+            _ => Err("expected development or production".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub mode: AppMode,
+    /// Output directory of `vite build`
+    pub dist_dir: PathBuf,
+    /// Address the app listens on
+    pub bind: SocketAddr,
+    /// Public URL of the app, used for OIDC redirects and secure cookies
+    pub app_url: String,
+    /// Port of the Vite development server
+    pub vite_port: u16,
+    /// Reverse proxies whose `X-Forwarded-For` and `X-Real-IP` headers are
+    /// believed. Requests from anywhere else are logged with the address
+    /// they came from.
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            mode: AppMode::Development,
+            dist_dir: PathBuf::from("dist"),
+            bind: SocketAddr::from(([127, 0, 0, 1], 8000)),
+            app_url: "http://localhost:8000".to_string(),
+            vite_port: 5173,
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    pub issuer_url: String,
+    pub client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    /// Sessions end after this many seconds without a request
+    pub session_expiry_secs: u64,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            issuer_url: "https://accounts.google.com".to_string(),
+            client_id: "dummy_client_id".to_string(),
+            client_secret: None,
+            session_expiry_secs: 3600,
+        }
+    }
+}
+
+/// Server-side rendering of Inertia pages by the Node process `npm run ssr`
+/// starts. Pages render in the browser whenever it does not answer in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SsrConfig {
+    pub enabled: bool,
+    /// Base URL of the SSR server
+    pub url: String,
+    /// Milliseconds to wait for a render before falling back
+    pub timeout_ms: u64,
+    /// Page components to render on the server
+    pub pages: Vec<String>,
+}
+
+impl Default for SsrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "http://127.0.0.1:13714".to_string(),
+            timeout_ms: 500,
+            pages: vec!["EnhancedStatusPage".to_string()],
+        }
+    }
+}
+
+impl SsrConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkerConfig {
+    /// Jobs run at the same time
+    pub concurrency: usize,
+    /// Port of the health and metrics server
+    pub health_port: u16,
+    /// Queue an example `send_email` job on startup
+    pub demo_job: bool,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 5,
+            health_port: 8001,
+            demo_job: false,
+        }
+    }
+}
+
+/// A static copy of the status page that the worker rewrites after every
+/// change, for hosting elsewhere while the app is down. Set `dir` or
+/// `bucket`; with neither, nothing is exported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    /// Directory to write the files to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+    /// S3-compatible bucket to upload the files to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<String>,
+    /// Endpoint of a non-AWS store such as MinIO; AWS when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    pub region: String,
+    /// Credentials for the bucket; taken from the instance role when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_key_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret_access_key: Option<String>,
+    /// Prepended to every file name, e.g. `status/`
+    pub prefix: String,
+    /// Seconds to wait after a change, so a burst of changes is exported once
+    pub debounce_secs: u64,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            bucket: None,
+            endpoint: None,
+            region: "us-east-1".to_string(),
+            access_key_id: None,
+            secret_access_key: None,
+            prefix: String::new(),
+            debounce_secs: 5,
+        }
+    }
+}
+
+impl ExportConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some() || self.bucket.is_some()
+    }
+
+    pub fn debounce(&self) -> Duration {
+        Duration::from_secs(self.debounce_secs)
+    }
+}
+
+impl ServerConfig {
+    /// The Vite manifest mapping entry points to hashed bundles
+    pub fn manifest_path(&self) -> PathBuf {
+        self.dist_dir.join(".vite").join("manifest.json")
+    }
+}
+
+impl Config {
+    /// Read the config file and environment, and validate the result
+    pub fn load() -> Result<Self, ConfigError> {
+        dotenv::dotenv().ok();
+
+        // An explicit path must exist; the default file is optional
+        let path = match env::var_os(CONFIG_PATH_VAR) {
+            Some(path) => Some(PathBuf::from(path)),
+            None => Some(PathBuf::from(DEFAULT_PATH)).filter(|path| path.exists()),
+        };
+        Self::load_from(path.as_deref(), |var| env::var(var).ok())
+    }
+
+    /// [`Config::load`] for binaries: prints the problems and exits if the
+    /// configuration is unusable
+    pub fn load_or_exit() -> Self {
+        Self::load().unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        })
+    }
+
+    fn load_from(path: Option<&Path>, env: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let mut config = match path {
+            Some(path) => {
+                let text = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+                Self::parse(&text, path)?
+            }
+            None => Self::default(),
+        };
+
+        config.apply_env(env)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn parse(text: &str, path: &Path) -> Result<Self, ConfigError> {
+        let mut config: Self = toml::from_str(text).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        config.source = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    fn apply_env(&mut self, env: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        for (var, key) in ENV_OVERRIDES {
+            if let Some(value) = env(var) {
+                self.set(key, &value)
+                    .map_err(|message| ConfigError::Env { var, key, message })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "database.url" => self.database.url = value.to_string(),
+            "database.max_connections" => self.database.max_connections = parse(value)?,
+            "server.mode" => self.server.mode = parse(value)?,
+            "server.dist_dir" => self.server.dist_dir = PathBuf::from(value),
+            "server.bind" => self.server.bind = parse(value)?,
+            // OIDC_REDIRECT_URL holds the callback route rather than the app itself
+            "server.app_url" => self.server.app_url = value.trim_end_matches("/auth/callback").to_string(),
+            "server.vite_port" => self.server.vite_port = parse(value)?,
+            "server.trusted_proxies" => {
+                self.server.trusted_proxies = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|ip| !ip.is_empty())
+                    .map(parse)
+                    .collect::<Result<_, _>>()?
+            }
+            "auth.issuer_url" => self.auth.issuer_url = value.to_string(),
+            "auth.client_id" => self.auth.client_id = value.to_string(),
+            "auth.client_secret" => self.auth.client_secret = Some(value.to_string()),
+            "auth.session_expiry_secs" => self.auth.session_expiry_secs = parse(value)?,
+            "ssr.enabled" => self.ssr.enabled = parse(value)?,
+            "ssr.url" => self.ssr.url = value.to_string(),
+            "ssr.timeout_ms" => self.ssr.timeout_ms = parse(value)?,
+            "worker.concurrency" => self.worker.concurrency = parse(value)?,
+            "worker.health_port" => self.worker.health_port = parse(value)?,
+            "worker.demo_job" => self.worker.demo_job = parse(value)?,
+            "export.dir" => self.export.dir = Some(PathBuf::from(value)),
+            "export.bucket" => self.export.bucket = Some(value.to_string()),
+            "export.endpoint" => self.export.endpoint = Some(value.to_string()),
+            "export.region" => self.export.region = value.to_string(),
+            "export.access_key_id" => self.export.access_key_id = Some(value.to_string()),
+            "export.secret_access_key" => self.export.secret_access_key = Some(value.to_string()),
+            "export.prefix" => self.export.prefix = value.to_string(),
+            "export.debounce_secs" => self.export.debounce_secs = parse(value)?,
+            _ => unreachable!("{} is not a setting", key),
+        }
+        Ok(())
+    }
+
+    /// Check the settings together, collecting every problem
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if self.database.url.is_empty() {
+            problems.push("database.url is not set (or set DATABASE_URL)".to_string());
+        } else if !Url::parse(&self.database.url).is_ok_and(|url| ["postgres", "postgresql"].contains(&url.scheme())) {
+            problems.push("database.url must be a postgres:// URL".to_string());
+        }
+        if self.database.max_connections == 0 {
+            problems.push("database.max_connections must be at least 1".to_string());
+        }
+
+        if self.server.mode == AppMode::Production && !self.server.manifest_path().is_file() {
+            problems.push(format!(
+                "server.mode is production but {} is missing; run `npm run build` first",
+                self.server.manifest_path().display()
+            ));
+        }
+        if !Url::parse(&self.server.app_url).is_ok_and(|url| ["http", "https"].contains(&url.scheme())) {
+            problems.push(format!("server.app_url must be an http(s) URL, got {:?}", self.server.app_url));
+        }
+
+        if Url::parse(&self.auth.issuer_url).is_err() {
+            problems.push(format!("auth.issuer_url must be a URL, got {:?}", self.auth.issuer_url));
+        }
+        if self.auth.client_id.is_empty() {
+            problems.push("auth.client_id must not be empty".to_string());
+        }
+        if self.auth.session_expiry_secs == 0 {
+            problems.push("auth.session_expiry_secs must be at least 1".to_string());
+        }
+
+        if self.ssr.enabled {
+            if !Url::parse(&self.ssr.url).is_ok_and(|url| ["http", "https"].contains(&url.scheme())) {
+                problems.push(format!("ssr.url must be an http(s) URL, got {:?}", self.ssr.url));
+            }
+            if self.ssr.timeout_ms == 0 {
+                problems.push("ssr.timeout_ms must be at least 1".to_string());
+            }
+        }
+
+        if self.worker.concurrency == 0 {
+            problems.push("worker.concurrency must be at least 1".to_string());
+        }
+
+        if self.export.dir.is_some() && self.export.bucket.is_some() {
+            problems.push("export.dir and export.bucket are both set; choose one".to_string());
+        }
+        if let Some(endpoint) = &self.export.endpoint {
+            if !Url::parse(endpoint).is_ok_and(|url| ["http", "https"].contains(&url.scheme())) {
+                problems.push(format!("export.endpoint must be an http(s) URL, got {:?}", endpoint));
+            }
+        }
+        if self.export.access_key_id.is_some() != self.export.secret_access_key.is_some() {
+            problems.push("export.access_key_id and export.secret_access_key must be set together".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems))
+        }
+    }
+
+    /// The effective settings as TOML, with the database password, the OIDC
+    /// client secret and the export secret key masked
+    pub fn redacted(&self) -> String {
+        let mut config = self.clone();
+        if let Ok(mut url) = Url::parse(&config.database.url) {
+            if url.password().is_some() && url.set_password(Some(REDACTED)).is_ok() {
+                config.database.url = url.to_string();
+            }
+        }
+        if config.auth.client_secret.is_some() {
+            config.auth.client_secret = Some(REDACTED.to_string());
+        }
+        if config.export.secret_access_key.is_some() {
+            config.export.secret_access_key = Some(REDACTED.to_string());
+        }
+
+        toml::to_string(&config).expect("configuration serializes to TOML")
+    }
+}
+
+fn parse<T>(value: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse().map_err(|e| format!("{:?} is not valid: {}", value, e))
+}
+
+/// Handle `<binary> config check`, which prints the effective configuration
+/// with secrets redacted, or why it is invalid. Returns the exit code, or
+/// `None` when the arguments are not a config command.
+pub fn run_command(mut args: impl Iterator<Item = String>) -> Option<i32> {
+    if args.next().as_deref() != Some("config") {
+        return None;
+    }
+
+    match args.next().as_deref() {
+        Some("check") => match Config::load() {
+            Ok(config) => {
+                let source = config.source.as_ref().map_or("defaults".to_string(), |path| path.display().to_string());
+                println!("# Effective configuration: {} and the environment\n", source);
+                print!("{}", config.redacted());
+                Some(0)
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                Some(1)
+            }
+        },
+        _ => {
+            eprintln!("Usage: config check");
+            Some(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const FILE: &str = r#"
+        [database]
+        url = "postgres://postgres:hunter2@db:5432/rustgenie"
+
+        [worker]
+        concurrency = 2
+        demo_job = true
+    "#;
+
+    fn load(file: &str, vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let mut config = Config::parse(file, Path::new("config.toml"))?;
+        config.apply_env(|var| vars.get(var).cloned())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    #[test]
+    fn the_environment_overrides_the_file() {
+        let config = load(FILE, &[
+            ("WORKER_CONCURRENCY", "8"),
+            ("OIDC_REDIRECT_URL", "https://status.example.com/auth/callback"),
+            ("TRUSTED_PROXIES", "10.0.0.2, ::1"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.database.url, "postgres://postgres:hunter2@db:5432/rustgenie");
+        assert_eq!(config.worker.concurrency, 8);
+        assert!(config.worker.demo_job);
+        assert_eq!(config.server.app_url, "https://status.example.com");
+        assert_eq!(config.server.bind, SocketAddr::from(([127, 0, 0, 1], 8000)));
+        assert_eq!(config.server.trusted_proxies, ["10.0.0.2".parse::<IpAddr>().unwrap(), IpAddr::from([0, 0, 0, 0, 0, 0, 0, 1])]);
+
+        let error = load(FILE, &[("WORKER_HEALTH_PORT", "eighty")]).unwrap_err();
+        assert!(error.to_string().starts_with("WORKER_HEALTH_PORT (sets worker.health_port)"));
+    }
+
+    #[test]
+    fn the_example_file_is_valid() {
+        let config = load(include_str!("../../../config.example.toml"), &[]).unwrap();
+        let defaults = Config::default();
+
+        assert_eq!(config.server.bind, defaults.server.bind);
+        assert_eq!(config.worker.concurrency, defaults.worker.concurrency);
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        match load("[worker]\nconcurrency = 0", &[("APP_URL", "localhost:8000")]).unwrap_err() {
+            ConfigError::Invalid(problems) => assert_eq!(problems.len(), 3, "{:?}", problems),
+            other => panic!("expected validation errors, got {}", other),
+        }
+
+        let error = load(FILE, &[("APP_MODE", "production"), ("APP_DIST_DIR", "/nonexistent")]).unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/.vite/manifest.json is missing"), "{}", error);
+
+        let error = load(FILE, &[("EXPORT_DIR", "public"), ("EXPORT_BUCKET", "status")]).unwrap_err();
+        assert!(error.to_string().contains("export.dir and export.bucket are both set"), "{}", error);
+
+        // Misspelled settings are rejected rather than ignored
+        assert!(matches!(load("[server]\nport = 80", &[]), Err(ConfigError::Parse { .. })));
+    }
+
+    #[test]
+    fn secrets_are_redacted() {
+        let config = load(FILE, &[
+            ("OIDC_CLIENT_SECRET", "s3cret"),
+            ("EXPORT_BUCKET", "status"),
+            ("EXPORT_ACCESS_KEY_ID", "minio"),
+            ("EXPORT_SECRET_ACCESS_KEY", "minio-s3cret"),
+        ])
+        .unwrap();
+        let redacted = config.redacted();
+
+        assert!(!redacted.contains("hunter2") && !redacted.contains("s3cret"), "{}", redacted);
+        assert!(redacted.contains("secret_access_key = \"********\""));
+        assert!(redacted.contains("postgres://postgres:********@db:5432/rustgenie"));
+        assert!(redacted.contains("client_secret = \"********\""));
+
+        // The output is itself a valid config file
+        let reparsed = Config::parse(&redacted, Path::new("check.toml")).unwrap();
+        assert_eq!(reparsed.worker.concurrency, 2);
+    }
+}
@@ -3,7 +3,8 @@ use db_core::reset::reset_database;
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Resetting database...");
-    reset_database().await?;
+    let config = config_core::Config::load_or_exit();
+    reset_database(&config.database).await?;
     println!("Database reset complete!");
     Ok(())
 }
\ No newline at end of file
@@ -4,7 +4,8 @@ use db_core::init_pool;
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Running database seeds...");
-    let pool = init_pool().await?;
+    let config = config_core::Config::load_or_exit();
+    let pool = init_pool(&config.database).await?;
     run_all_seeds(pool).await?;
     println!("Database seeding complete!");
     Ok(())
@@ -1,6 +1,7 @@
+use config_core::DatabaseConfig;
 use sqlx::PgPool;
 use sqlx::migrate::Migrator;
-use std::env;
+use sqlx::postgres::PgPoolOptions;
 use std::sync::Arc;
 use crate::error::DbError;
 
@@ -11,31 +12,15 @@ pub type DbPool = Arc<PgPool>;
 static MIGRATOR: Migrator = sqlx::migrate!("src/migrations");
 
 /// Initialize the database connection pool
-pub async fn init_db_pool() -> Result<DbPool, DbError> {
-    dotenv::dotenv().ok();
-    
-    let database_url = env::var("DATABASE_URL").map_err(|_| {
-        DbError::ConnectionError("DATABASE_URL environment variable not set".to_string())
-    })?;
-    
-    let pool = PgPool::connect(&database_url)
-        .await
-        .map_err(|e| DbError::ConnectionError(e.to_string()))?;
+pub async fn init_db_pool(config: &DatabaseConfig) -> Result<DbPool, DbError> {
+    let pool = connect(config).await?;
     
     Ok(Arc::new(pool))
 }
 
 /// Initialize the database pool and run migrations
-pub async fn init_pool() -> Result<DbPool, DbError> {
-    dotenv::dotenv().ok();
-    
-    let database_url = env::var("DATABASE_URL").map_err(|_| {
-        DbError::ConnectionError("DATABASE_URL environment variable not set".to_string())
-    })?;
-    
-    let pool = PgPool::connect(&database_url)
-        .await
-        .map_err(|e| DbError::ConnectionError(e.to_string()))?;
+pub async fn init_pool(config: &DatabaseConfig) -> Result<DbPool, DbError> {
+    let pool = connect(config).await?;
     
     // Run migrations before wrapping in Arc
     MIGRATOR.run(&pool)
@@ -45,3 +30,10 @@ pub async fn init_pool() -> Result<DbPool, DbError> {
     Ok(Arc::new(pool))
 }
 
+async fn connect(config: &DatabaseConfig) -> Result<PgPool, DbError> {
+    PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .connect(&config.url)
+        .await
+        .map_err(|e| DbError::ConnectionError(e.to_string()))
+}
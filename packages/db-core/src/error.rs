@@ -13,12 +13,25 @@ pub enum DbError {
 
     #[error("Resource not found")]
     NotFound,
+
+    #[error("Resource already exists: {0}")]
+    Conflict(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
 }
 
 impl From<sqlx::Error> for DbError {
     fn from(err: sqlx::Error) -> Self {
         match err {
             sqlx::Error::RowNotFound => DbError::NotFound,
+            sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+                DbError::Conflict(db_err.constraint().unwrap_or_else(|| db_err.message()).to_string())
+            }
+            sqlx::Error::Database(ref db_err) if db_err.is_foreign_key_violation() => DbError::InvalidInput(format!(
+                "Referenced resource does not exist ({})",
+                db_err.constraint().unwrap_or_else(|| db_err.message())
+            )),
             _ => DbError::QueryError(err.to_string()),
         }
     }
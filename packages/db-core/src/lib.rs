@@ -3,6 +3,8 @@ pub mod error;
 pub mod models;
 pub mod repositories;
 pub mod migrations;
+pub mod notifications;
+pub mod pagination;
 pub mod seeds;
 pub mod reset;
 pub mod time_serde;
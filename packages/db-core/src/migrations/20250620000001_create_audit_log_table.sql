@@ -0,0 +1,32 @@
+-- Create append-only audit log for configuration changes
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    actor_type VARCHAR(20) NOT NULL CHECK (actor_type IN ('user', 'api_key', 'anonymous', 'system')),
+    actor_id VARCHAR(255),
+    action VARCHAR(50) NOT NULL, -- create, update, delete
+    entity_type VARCHAR(50) NOT NULL, -- monitor, incident, ...
+    entity_id VARCHAR(255),
+    before_state JSONB,
+    after_state JSONB,
+    changes JSONB, -- field-level diff: { "field": { "before": ..., "after": ... } }
+    ip_address VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for the common audit filters
+CREATE INDEX idx_audit_log_created_at ON audit_log(created_at DESC);
+CREATE INDEX idx_audit_log_entity ON audit_log(entity_type, entity_id, created_at DESC);
+CREATE INDEX idx_audit_log_actor ON audit_log(actor_type, actor_id, created_at DESC);
+
+-- Reject any attempt to rewrite history
+CREATE OR REPLACE FUNCTION prevent_audit_log_mutation()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_audit_log_mutation();
@@ -0,0 +1,14 @@
+-- Monitor names are used as stable identifiers by the API and Terraform provider
+
+-- Earlier versions allowed duplicate names. The oldest monitor keeps the name
+-- and later ones get their id appended, trimmed to fit the column.
+UPDATE monitors
+SET name = LEFT(monitors.name, 255 - LENGTH(monitors.id::TEXT) - 1) || '-' || monitors.id
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY name ORDER BY created_at, id) AS position
+    FROM monitors
+) ranked
+WHERE ranked.id = monitors.id AND ranked.position > 1;
+
+DROP INDEX IF EXISTS idx_monitors_name;
+CREATE UNIQUE INDEX idx_monitors_name ON monitors(name);
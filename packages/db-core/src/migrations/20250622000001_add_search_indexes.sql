@@ -0,0 +1,13 @@
+-- Full-text search indexes backing the `q` filter on list endpoints.
+-- The expressions must match the ones used in the repositories exactly.
+CREATE INDEX IF NOT EXISTS idx_monitors_search ON monitors
+    USING GIN (to_tsvector('simple', name || ' ' || display_name || ' ' || COALESCE(description, '')));
+
+CREATE INDEX IF NOT EXISTS idx_incidents_search ON incidents
+    USING GIN (to_tsvector('simple', title || ' ' || message));
+
+CREATE INDEX IF NOT EXISTS idx_documents_search ON documents
+    USING GIN (to_tsvector('simple', header));
+
+-- Keyset pagination orders incidents by start time
+CREATE INDEX IF NOT EXISTS idx_incidents_started_at ON incidents(started_at DESC, id DESC);
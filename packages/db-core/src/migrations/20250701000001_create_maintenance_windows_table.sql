@@ -0,0 +1,32 @@
+-- Create maintenance windows announced on the status page ahead of planned work
+CREATE TABLE IF NOT EXISTS maintenance_windows (
+    id SERIAL PRIMARY KEY,
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    affected_monitors INTEGER[] NOT NULL DEFAULT '{}',
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT maintenance_windows_ends_after_start CHECK (ends_at > starts_at)
+);
+
+-- Create index for windows that have not ended yet
+CREATE INDEX idx_maintenance_windows_ends_at ON maintenance_windows(ends_at, starts_at);
+
+-- Create index for affected monitors
+CREATE INDEX idx_maintenance_windows_affected_monitors ON maintenance_windows USING GIN(affected_monitors);
+
+-- Create updated_at trigger
+CREATE OR REPLACE FUNCTION update_maintenance_windows_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_maintenance_windows_updated_at_trigger
+    BEFORE UPDATE ON maintenance_windows
+    FOR EACH ROW
+    EXECUTE FUNCTION update_maintenance_windows_updated_at();
@@ -0,0 +1,31 @@
+-- Create configurable public status pages, each showing a selection of monitors
+CREATE TABLE IF NOT EXISTS status_pages (
+    id SERIAL PRIMARY KEY,
+    slug VARCHAR(64) NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    logo_url VARCHAR(500),
+    custom_domain VARCHAR(255) UNIQUE,
+    monitor_ids INTEGER[] NOT NULL DEFAULT '{}', -- in display order
+    service_groups TEXT[] NOT NULL DEFAULT '{}', -- shown after monitor_ids
+    visibility VARCHAR(20) NOT NULL DEFAULT 'public' CHECK (visibility IN ('public', 'password', 'sso')),
+    password_hash TEXT,
+    show_internal_details BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT status_pages_password_set CHECK (visibility <> 'password' OR password_hash IS NOT NULL)
+);
+
+-- Create updated_at trigger
+CREATE OR REPLACE FUNCTION update_status_pages_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_status_pages_updated_at_trigger
+    BEFORE UPDATE ON status_pages
+    FOR EACH ROW
+    EXECUTE FUNCTION update_status_pages_updated_at();
@@ -0,0 +1,69 @@
+-- Create service groups, replacing the metadata->>'service_group' convention
+CREATE TABLE IF NOT EXISTS service_groups (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL UNIQUE,
+    description TEXT,
+    parent_id INTEGER REFERENCES service_groups(id) ON DELETE SET NULL,
+    position INTEGER NOT NULL DEFAULT 0, -- display order among siblings
+    weight INTEGER NOT NULL DEFAULT 1 CHECK (weight > 0), -- share of the parent's weighted status
+    status_rollup VARCHAR(20) NOT NULL DEFAULT 'worst' CHECK (status_rollup IN ('worst', 'weighted')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT service_groups_not_own_parent CHECK (parent_id <> id)
+);
+
+CREATE INDEX idx_service_groups_parent_id ON service_groups(parent_id);
+CREATE INDEX idx_service_groups_position ON service_groups(position, id);
+
+-- Create updated_at trigger
+CREATE OR REPLACE FUNCTION update_service_groups_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_service_groups_updated_at_trigger
+    BEFORE UPDATE ON service_groups
+    FOR EACH ROW
+    EXECUTE FUNCTION update_service_groups_updated_at();
+
+-- Assign monitors to groups by foreign key
+ALTER TABLE monitors
+    ADD COLUMN service_group_id INTEGER REFERENCES service_groups(id) ON DELETE SET NULL,
+    ADD COLUMN weight INTEGER NOT NULL DEFAULT 1 CHECK (weight > 0);
+
+CREATE INDEX idx_monitors_service_group_id ON monitors(service_group_id);
+
+-- Turn existing metadata groups into rows, ordered by their highest priority.
+-- metadata.service_group is left in place for v1 clients that read it.
+INSERT INTO service_groups (name, position)
+SELECT grouped.name, (ROW_NUMBER() OVER (ORDER BY grouped.priority, grouped.name))::INTEGER - 1
+FROM (
+    SELECT metadata->>'service_group' AS name,
+           -- priority is free-form metadata; anything but a plain number sorts last
+           MIN(CASE WHEN metadata->>'priority' ~ '^-?[0-9]{1,9}$' THEN (metadata->>'priority')::INTEGER ELSE 2147483647 END) AS priority
+    FROM monitors
+    WHERE metadata->>'service_group' IS NOT NULL AND metadata->>'service_group' <> ''
+    GROUP BY metadata->>'service_group'
+) grouped
+ON CONFLICT (name) DO NOTHING;
+
+UPDATE monitors
+SET service_group_id = service_groups.id
+FROM service_groups
+WHERE monitors.metadata->>'service_group' = service_groups.name;
+
+-- Status pages select groups by ID rather than by name
+ALTER TABLE status_pages ADD COLUMN service_group_ids INTEGER[] NOT NULL DEFAULT '{}';
+
+UPDATE status_pages
+SET service_group_ids = ARRAY(
+    SELECT service_groups.id
+    FROM unnest(status_pages.service_groups) WITH ORDINALITY AS selected(name, ordinal)
+    JOIN service_groups ON service_groups.name = selected.name
+    ORDER BY selected.ordinal
+);
+
+ALTER TABLE status_pages DROP COLUMN service_groups;
@@ -0,0 +1,10 @@
+-- Secret tokens for status badges. A monitor or service group with a token
+-- is private: its badge is only served to requests carrying the token.
+CREATE TABLE IF NOT EXISTS badge_tokens (
+    id SERIAL PRIMARY KEY,
+    token VARCHAR(64) NOT NULL UNIQUE,
+    monitor_id INTEGER UNIQUE REFERENCES monitors(id) ON DELETE CASCADE,
+    service_group_id INTEGER UNIQUE REFERENCES service_groups(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT badge_tokens_one_subject CHECK ((monitor_id IS NULL) <> (service_group_id IS NULL))
+);
@@ -0,0 +1,35 @@
+-- Service level objectives for a monitor or a service group and its subgroups
+CREATE TABLE IF NOT EXISTS slos (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    monitor_id INTEGER REFERENCES monitors(id) ON DELETE CASCADE,
+    service_group_id INTEGER REFERENCES service_groups(id) ON DELETE CASCADE,
+    target DOUBLE PRECISION NOT NULL CHECK (target > 0 AND target < 100), -- percentage of good checks
+    window_days INTEGER NOT NULL DEFAULT 30 CHECK (window_days BETWEEN 1 AND 90),
+    latency_p95_ms INTEGER CHECK (latency_p95_ms > 0), -- checks in hours with a slower p95 count as bad
+    alert_email VARCHAR(255),
+    alert_status VARCHAR(20) NOT NULL DEFAULT 'ok' CHECK (alert_status IN ('ok', 'warning', 'critical')),
+    alert_changed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT slos_one_subject CHECK ((monitor_id IS NULL) <> (service_group_id IS NULL))
+);
+
+CREATE INDEX idx_slos_monitor_id ON slos(monitor_id);
+CREATE INDEX idx_slos_service_group_id ON slos(service_group_id);
+CREATE INDEX idx_slos_name ON slos(name, id);
+
+-- Create updated_at trigger
+CREATE OR REPLACE FUNCTION update_slos_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_slos_updated_at_trigger
+    BEFORE UPDATE ON slos
+    FOR EACH ROW
+    EXECUTE FUNCTION update_slos_updated_at();
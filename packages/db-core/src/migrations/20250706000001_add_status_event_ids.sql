@@ -0,0 +1,4 @@
+-- Status events had no key. Time and monitor repeat when two results for a
+-- monitor are recorded at once, so lists paged by them could skip or repeat
+-- rows. The id breaks ties in time order.
+ALTER TABLE status_events ADD COLUMN IF NOT EXISTS id BIGSERIAL;
@@ -0,0 +1,7 @@
+-- Deleting a token used to make the badge public again, so revoking a
+-- leaked token published what it protected. Privacy is now its own flag:
+-- revoking clears the token and leaves the badge private, and only turning
+-- the flag off makes it public. A subject without a row keeps the default,
+-- which shows badges only for monitors on a public status page.
+ALTER TABLE badge_tokens ADD COLUMN IF NOT EXISTS private BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE badge_tokens ALTER COLUMN token DROP NOT NULL;
@@ -0,0 +1,97 @@
+use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::FromRow;
+
+/// A single, immutable record of a configuration change
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub actor_type: String,
+    pub actor_id: Option<String>,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub before_state: Option<JsonValue>,
+    pub after_state: Option<JsonValue>,
+    pub changes: Option<JsonValue>,
+    pub ip_address: Option<String>,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAuditLogEntry {
+    pub actor_type: String,
+    pub actor_id: Option<String>,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub before_state: Option<JsonValue>,
+    pub after_state: Option<JsonValue>,
+    pub changes: Option<JsonValue>,
+    pub ip_address: Option<String>,
+}
+
+/// Optional filters for listing audit log entries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditLogFilter {
+    pub actor_type: Option<String>,
+    pub actor_id: Option<String>,
+    pub action: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    #[serde(default, with = "crate::time_serde::option")]
+    pub from: Option<OffsetDateTime>,
+    #[serde(default, with = "crate::time_serde::option")]
+    pub to: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ActorType {
+    User,
+    ApiKey,
+    Anonymous,
+    System,
+}
+
+impl From<String> for ActorType {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "user" => ActorType::User,
+            "api_key" => ActorType::ApiKey,
+            "system" => ActorType::System,
+            _ => ActorType::Anonymous,
+        }
+    }
+}
+
+impl From<ActorType> for String {
+    fn from(at: ActorType) -> Self {
+        match at {
+            ActorType::User => "user".to_string(),
+            ActorType::ApiKey => "api_key".to_string(),
+            ActorType::Anonymous => "anonymous".to_string(),
+            ActorType::System => "system".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl From<AuditAction> for String {
+    fn from(action: AuditAction) -> Self {
+        match action {
+            AuditAction::Create => "create".to_string(),
+            AuditAction::Update => "update".to_string(),
+            AuditAction::Delete => "delete".to_string(),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Whether a monitor's or service group's badge is private, and the token
+/// that unlocks it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct BadgeToken {
+    pub id: i32,
+    /// Left out when serialized, so it never reaches the audit log
+    #[serde(skip_serializing, default)]
+    pub token: Option<String>,
+    /// A private badge without a token is served to nobody
+    pub private: bool,
+    pub monitor_id: Option<i32>,
+    pub service_group_id: Option<i32>,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub created_at: OffsetDateTime,
+}
+
+/// What a badge shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeSubject {
+    Monitor(i32),
+    ServiceGroup(i32),
+}
+
+impl BadgeSubject {
+    /// The `badge_tokens` column that refers to the subject, and its ID
+    pub(crate) fn column(self) -> (&'static str, i32) {
+        match self {
+            BadgeSubject::Monitor(id) => ("monitor_id", id),
+            BadgeSubject::ServiceGroup(id) => ("service_group_id", id),
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use time::OffsetDateTime;
+use crate::pagination::{SortColumn, SortDirection, SortKind};
 
 /// Represents a document in the system
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct Document {
     /// Unique identifier for the document
     pub id: i32,
@@ -28,10 +30,12 @@ pub struct Document {
     
     /// When the document was created
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::TimeTuple>"))]
     pub created_at: Option<OffsetDateTime>,
     
     /// When the document was last updated
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::TimeTuple>"))]
     pub updated_at: Option<OffsetDateTime>,
 }
 
@@ -56,3 +60,44 @@ pub struct UpdateDocument {
     pub limit_value: Option<String>,
     pub reviewer: Option<String>,
 }
+
+/// Optional filters for listing documents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DocumentFilter {
+    pub status: Option<String>,
+    pub type_name: Option<String>,
+    pub reviewer: Option<String>,
+    /// Full-text search over the header
+    pub q: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentSort {
+    #[default]
+    Id,
+    Header,
+    Status,
+    TypeName,
+    Reviewer,
+}
+
+impl DocumentSort {
+    pub fn column(self) -> SortColumn {
+        let (column, kind) = match self {
+            DocumentSort::Id => ("id", SortKind::Int),
+            DocumentSort::Header => ("header", SortKind::Text),
+            DocumentSort::Status => ("status", SortKind::Text),
+            DocumentSort::TypeName => ("type_name", SortKind::Text),
+            DocumentSort::Reviewer => ("reviewer", SortKind::Text),
+        };
+
+        SortColumn {
+            column,
+            field: column,
+            kind,
+            default_direction: SortDirection::Asc,
+        }
+    }
+}
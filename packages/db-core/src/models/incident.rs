@@ -1,8 +1,10 @@
 use time::OffsetDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use crate::pagination::{SortColumn, SortDirection, SortKind};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct Incident {
     pub id: i32,
     pub title: String,
@@ -10,34 +12,111 @@ pub struct Incident {
     pub severity: String,
     pub affected_monitors: Vec<i32>,
     #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
     pub created_at: OffsetDateTime,
     #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
     pub updated_at: OffsetDateTime,
     #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
     pub started_at: OffsetDateTime,
     #[serde(with = "crate::time_serde::option")]
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::Timestamp>"))]
     pub resolved_at: Option<OffsetDateTime>,
     pub is_resolved: bool,
     pub metadata: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct CreateIncident {
     pub title: String,
     pub message: String,
     pub severity: String,
     pub affected_monitors: Vec<i32>,
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::TimeTuple>"))]
     pub started_at: Option<OffsetDateTime>,
     pub metadata: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct UpdateIncident {
     pub title: Option<String>,
     pub message: Option<String>,
     pub severity: Option<String>,
     pub affected_monitors: Option<Vec<i32>>,
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::TimeTuple>"))]
     pub resolved_at: Option<OffsetDateTime>,
     pub is_resolved: Option<bool>,
     pub metadata: Option<serde_json::Value>,
-}
\ No newline at end of file
+}
+
+/// Number of incidents with a given severity and resolution
+#[derive(Debug, Clone, FromRow)]
+pub struct IncidentCount {
+    pub severity: String,
+    pub is_resolved: bool,
+    pub count: i64,
+}
+
+/// Optional filters for listing incidents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IncidentFilter {
+    pub severity: Option<String>,
+    pub is_resolved: Option<bool>,
+    /// Only incidents affecting this monitor
+    pub monitor_id: Option<i32>,
+    /// Full-text search over title and message
+    pub q: Option<String>,
+    #[serde(default, with = "crate::time_serde::option")]
+    pub from: Option<OffsetDateTime>,
+    #[serde(default, with = "crate::time_serde::option")]
+    pub to: Option<OffsetDateTime>,
+    /// Only incidents affecting one of these monitors or no monitor in
+    /// particular, as a status page showing them lists
+    #[serde(skip)]
+    pub shown_with: Option<Vec<i32>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentSort {
+    #[default]
+    StartedAt,
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+impl IncidentSort {
+    pub fn column(self) -> SortColumn {
+        match self {
+            IncidentSort::StartedAt => SortColumn {
+                column: "started_at",
+                field: "started_at",
+                kind: SortKind::Time,
+                default_direction: SortDirection::Desc,
+            },
+            IncidentSort::CreatedAt => SortColumn {
+                column: "created_at",
+                field: "created_at",
+                kind: SortKind::Time,
+                default_direction: SortDirection::Desc,
+            },
+            IncidentSort::UpdatedAt => SortColumn {
+                column: "updated_at",
+                field: "updated_at",
+                kind: SortKind::Time,
+                default_direction: SortDirection::Desc,
+            },
+            IncidentSort::Title => SortColumn {
+                column: "title",
+                field: "title",
+                kind: SortKind::Text,
+                default_direction: SortDirection::Asc,
+            },
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::pagination::{SortColumn, SortDirection, SortKind};
+
+/// Planned work announced on the status page
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct MaintenanceWindow {
+    pub id: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub affected_monitors: Vec<i32>,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub starts_at: OffsetDateTime,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub ends_at: OffsetDateTime,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub updated_at: OffsetDateTime,
+}
+
+impl MaintenanceWindow {
+    pub fn is_in_progress(&self, now: OffsetDateTime) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CreateMaintenanceWindow {
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub affected_monitors: Vec<i32>,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub starts_at: OffsetDateTime,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub ends_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct UpdateMaintenanceWindow {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub affected_monitors: Option<Vec<i32>>,
+    #[serde(default, with = "crate::time_serde::option")]
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::Timestamp>"))]
+    pub starts_at: Option<OffsetDateTime>,
+    #[serde(default, with = "crate::time_serde::option")]
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::Timestamp>"))]
+    pub ends_at: Option<OffsetDateTime>,
+}
+
+/// Maintenance windows are listed by start, latest first
+pub const MAINTENANCE_SORT: SortColumn = SortColumn {
+    column: "starts_at",
+    field: "starts_at",
+    kind: SortKind::Time,
+    default_direction: SortDirection::Desc,
+};
@@ -1,5 +1,11 @@
+pub mod badge_token;
 pub mod document;
 pub mod monitor;
 pub mod status_event;
 pub mod incident;
+pub mod maintenance_window;
+pub mod service_group;
+pub mod slo;
+pub mod status_page;
 pub mod user;
+pub mod audit_log;
@@ -2,8 +2,10 @@ use time::OffsetDateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use sqlx::FromRow;
+use crate::pagination::{SortColumn, SortDirection, SortKind};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct Monitor {
     pub id: i32,
     pub name: String,
@@ -15,13 +17,19 @@ pub struct Monitor {
     pub timeout: i32,
     pub is_active: bool,
     pub metadata: Option<JsonValue>,
+    pub service_group_id: Option<i32>,
+    /// Share of its group's status when the group is `weighted`
+    pub weight: i32,
     #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
     pub created_at: OffsetDateTime,
     #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
     pub updated_at: OffsetDateTime,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct CreateMonitor {
     pub name: String,
     pub display_name: String,
@@ -32,9 +40,13 @@ pub struct CreateMonitor {
     pub timeout: i32,
     pub is_active: bool,
     pub metadata: Option<JsonValue>,
+    pub service_group_id: Option<i32>,
+    #[serde(default = "crate::models::service_group::default_weight")]
+    pub weight: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct UpdateMonitor {
     pub name: Option<String>,
     pub display_name: Option<String>,
@@ -45,6 +57,70 @@ pub struct UpdateMonitor {
     pub timeout: Option<i32>,
     pub is_active: Option<bool>,
     pub metadata: Option<JsonValue>,
+    /// 0 removes the monitor from its group
+    pub service_group_id: Option<i32>,
+    pub weight: Option<i32>,
+}
+
+/// Optional filters for listing monitors
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonitorFilter {
+    pub name: Option<String>,
+    pub monitor_type: Option<String>,
+    pub is_active: Option<bool>,
+    /// Status of the most recent check, `unknown` for monitors never checked
+    pub status: Option<String>,
+    /// Name of the group monitors belong to directly
+    pub service_group: Option<String>,
+    pub service_group_id: Option<i32>,
+    /// Full-text search over name, display name and description
+    pub q: Option<String>,
+    #[serde(default, with = "crate::time_serde::option")]
+    pub from: Option<OffsetDateTime>,
+    #[serde(default, with = "crate::time_serde::option")]
+    pub to: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorSort {
+    #[default]
+    Name,
+    CreatedAt,
+    UpdatedAt,
+    CheckInterval,
+}
+
+impl MonitorSort {
+    pub fn column(self) -> SortColumn {
+        match self {
+            MonitorSort::Name => SortColumn {
+                column: "name",
+                field: "name",
+                kind: SortKind::Text,
+                default_direction: SortDirection::Asc,
+            },
+            MonitorSort::CreatedAt => SortColumn {
+                column: "created_at",
+                field: "created_at",
+                kind: SortKind::Time,
+                default_direction: SortDirection::Desc,
+            },
+            MonitorSort::UpdatedAt => SortColumn {
+                column: "updated_at",
+                field: "updated_at",
+                kind: SortKind::Time,
+                default_direction: SortDirection::Desc,
+            },
+            MonitorSort::CheckInterval => SortColumn {
+                column: "check_interval",
+                field: "check_interval",
+                kind: SortKind::Int,
+                default_direction: SortDirection::Asc,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
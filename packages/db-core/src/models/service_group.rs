@@ -0,0 +1,97 @@
+use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::pagination::{SortColumn, SortDirection, SortKind};
+
+/// A component of the status page that monitors and other groups belong to
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct ServiceGroup {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    /// The group this one is nested in
+    pub parent_id: Option<i32>,
+    /// Display order among groups with the same parent
+    pub position: i32,
+    /// Share of the parent's status when it is `weighted`
+    pub weight: i32,
+    /// `worst` or `weighted`
+    pub status_rollup: String,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CreateServiceGroup {
+    pub name: String,
+    pub description: Option<String>,
+    pub parent_id: Option<i32>,
+    #[serde(default)]
+    pub position: i32,
+    #[serde(default = "default_weight")]
+    pub weight: i32,
+    #[serde(default = "default_status_rollup")]
+    pub status_rollup: String,
+}
+
+/// Fields left out are unchanged; a `parent_id` of 0 moves the group to the
+/// top level
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct UpdateServiceGroup {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub parent_id: Option<i32>,
+    pub position: Option<i32>,
+    pub weight: Option<i32>,
+    pub status_rollup: Option<String>,
+}
+
+pub(crate) fn default_weight() -> i32 {
+    1
+}
+
+fn default_status_rollup() -> String {
+    StatusRollup::Worst.into()
+}
+
+/// How a group's status follows from its monitors and subgroups
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum StatusRollup {
+    /// The most severe status of any member
+    Worst,
+    /// The members' severities averaged by weight
+    Weighted,
+}
+
+impl From<String> for StatusRollup {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "weighted" => StatusRollup::Weighted,
+            _ => StatusRollup::Worst,
+        }
+    }
+}
+
+impl From<StatusRollup> for String {
+    fn from(rollup: StatusRollup) -> Self {
+        match rollup {
+            StatusRollup::Worst => "worst".to_string(),
+            StatusRollup::Weighted => "weighted".to_string(),
+        }
+    }
+}
+
+/// Service groups are listed in display order
+pub const SERVICE_GROUP_SORT: SortColumn = SortColumn {
+    column: "position",
+    field: "position",
+    kind: SortKind::Int,
+    default_direction: SortDirection::Asc,
+};
@@ -0,0 +1,267 @@
+use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::pagination::{SortColumn, SortDirection, SortKind};
+
+/// Burn rate that pages: at this rate a 30-day budget lasts about two days,
+/// and an hour of it spends 2% of the budget
+pub const CRITICAL_BURN_RATE: f64 = 14.4;
+/// Burn rate that warns: at this rate a 30-day budget lasts five days, and
+/// six hours of it spend 5% of the budget
+pub const WARNING_BURN_RATE: f64 = 6.0;
+
+/// A service level objective: the share of checks of a monitor, or of every
+/// monitor in a service group and its subgroups, that must be good over a
+/// rolling window
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct Slo {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub monitor_id: Option<i32>,
+    pub service_group_id: Option<i32>,
+    /// Percentage of good checks, such as 99.9
+    pub target: f64,
+    pub window_days: i32,
+    /// Checks in hours whose p95 response time is slower than this count as
+    /// bad, even when they were operational
+    pub latency_p95_ms: Option<i32>,
+    /// Where burn-rate alerts are sent
+    pub alert_email: Option<String>,
+    /// `ok`, `warning` or `critical`, as last raised by the worker
+    pub alert_status: String,
+    #[serde(with = "crate::time_serde::option")]
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::Timestamp>"))]
+    pub alert_changed_at: Option<OffsetDateTime>,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub updated_at: OffsetDateTime,
+}
+
+impl Slo {
+    /// Share of checks allowed to be bad, such as 0.001 for 99.9%
+    pub fn error_budget(&self) -> f64 {
+        1.0 - self.target / 100.0
+    }
+}
+
+/// Set exactly one of `monitor_id` and `service_group_id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CreateSlo {
+    pub name: String,
+    pub description: Option<String>,
+    pub monitor_id: Option<i32>,
+    pub service_group_id: Option<i32>,
+    pub target: f64,
+    #[serde(default = "default_window_days")]
+    pub window_days: i32,
+    pub latency_p95_ms: Option<i32>,
+    pub alert_email: Option<String>,
+}
+
+/// Fields left out are unchanged; an empty `description` or `alert_email`
+/// clears it, as does a `latency_p95_ms` of 0
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct UpdateSlo {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub target: Option<f64>,
+    pub window_days: Option<i32>,
+    pub latency_p95_ms: Option<i32>,
+    pub alert_email: Option<String>,
+}
+
+fn default_window_days() -> i32 {
+    30
+}
+
+/// SLOs are listed by name
+pub const SLO_SORT: SortColumn = SortColumn {
+    column: "name",
+    field: "name",
+    kind: SortKind::Text,
+    default_direction: SortDirection::Asc,
+};
+
+/// How urgently an SLO's error budget is burning, from least to most
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SloAlert {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl From<String> for SloAlert {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "warning" => SloAlert::Warning,
+            "critical" => SloAlert::Critical,
+            _ => SloAlert::Ok,
+        }
+    }
+}
+
+impl From<SloAlert> for String {
+    fn from(alert: SloAlert) -> Self {
+        match alert {
+            SloAlert::Ok => "ok".to_string(),
+            SloAlert::Warning => "warning".to_string(),
+            SloAlert::Critical => "critical".to_string(),
+        }
+    }
+}
+
+/// Checks counted toward an SLO over some time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct SliCounts {
+    pub total: i64,
+    pub good: i64,
+}
+
+impl SliCounts {
+    pub fn bad(&self) -> i64 {
+        self.total - self.good
+    }
+
+    /// Share of bad checks; `None` without checks
+    fn error_rate(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.bad() as f64 / self.total as f64)
+    }
+}
+
+/// Where an SLO stands over its window and how fast it is burning its budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct SloStatus {
+    pub slo: Slo,
+    /// The monitors whose checks count
+    pub monitor_ids: Vec<i32>,
+    /// Checks over the whole window
+    pub checks: SliCounts,
+    /// Percentage of good checks over the window; `None` without checks
+    pub sli: Option<f64>,
+    /// Share of the window's error budget left: 1 untouched, 0 spent and
+    /// below 0 once the objective is missed
+    pub error_budget_remaining: f64,
+    /// Error rate over the last hour as a multiple of the error budget. At 1
+    /// the budget runs out exactly at the end of the window.
+    pub burn_rate_1h: Option<f64>,
+    /// The same over the last six hours
+    pub burn_rate_6h: Option<f64>,
+    /// What the burn rates call for now; `slo.alert_status` is what was last
+    /// raised
+    pub alert: SloAlert,
+}
+
+impl SloStatus {
+    pub fn new(
+        slo: Slo,
+        monitor_ids: Vec<i32>,
+        checks: SliCounts,
+        last_hour: SliCounts,
+        last_six_hours: SliCounts,
+    ) -> Self {
+        let budget = slo.error_budget();
+        let burn_rate = |counts: SliCounts| counts.error_rate().map(|rate| rate / budget);
+
+        let sli = checks.error_rate().map(|rate| (1.0 - rate) * 100.0);
+        let error_budget_remaining = checks.error_rate().map_or(1.0, |rate| 1.0 - rate / budget);
+        let burn_rate_1h = burn_rate(last_hour);
+        let burn_rate_6h = burn_rate(last_six_hours);
+
+        Self {
+            alert: alert(burn_rate_1h, burn_rate_6h, error_budget_remaining),
+            slo,
+            monitor_ids,
+            checks,
+            sli,
+            error_budget_remaining,
+            burn_rate_1h,
+            burn_rate_6h,
+        }
+    }
+}
+
+/// Multiwindow burn-rate alerting. A fast burn over the last hour is
+/// critical once the last six hours confirm it; a sustained burn over six
+/// hours, a short spike or a spent budget warns.
+fn alert(burn_rate_1h: Option<f64>, burn_rate_6h: Option<f64>, error_budget_remaining: f64) -> SloAlert {
+    let burn_rate_1h = burn_rate_1h.unwrap_or(0.0);
+    let burn_rate_6h = burn_rate_6h.unwrap_or(0.0);
+
+    if burn_rate_1h >= CRITICAL_BURN_RATE && burn_rate_6h >= WARNING_BURN_RATE {
+        SloAlert::Critical
+    } else if burn_rate_6h >= WARNING_BURN_RATE || burn_rate_1h >= CRITICAL_BURN_RATE || error_budget_remaining <= 0.0 {
+        SloAlert::Warning
+    } else {
+        SloAlert::Ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slo(target: f64) -> Slo {
+        Slo {
+            id: 1,
+            name: "API availability".to_string(),
+            description: None,
+            monitor_id: Some(1),
+            service_group_id: None,
+            target,
+            window_days: 30,
+            latency_p95_ms: None,
+            alert_email: None,
+            alert_status: "ok".to_string(),
+            alert_changed_at: None,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn counts(total: i64, bad: i64) -> SliCounts {
+        SliCounts { total, good: total - bad }
+    }
+
+    #[test]
+    fn budget_and_burn_rate_follow_the_target() {
+        // 99% allows 100 bad checks in 10,000; 25 of them are spent
+        let status = SloStatus::new(slo(99.0), vec![1], counts(10_000, 25), counts(60, 3), counts(360, 6));
+
+        assert!((status.sli.unwrap() - 99.75).abs() < 1e-9);
+        assert!((status.error_budget_remaining - 0.75).abs() < 1e-9);
+        assert!((status.burn_rate_1h.unwrap() - 5.0).abs() < 1e-9);
+        assert!((status.burn_rate_6h.unwrap() - 5.0 / 3.0).abs() < 1e-9);
+        assert_eq!(status.alert, SloAlert::Ok);
+    }
+
+    #[test]
+    fn no_checks_leave_the_budget_untouched() {
+        let status = SloStatus::new(slo(99.9), vec![], SliCounts::default(), SliCounts::default(), SliCounts::default());
+
+        assert_eq!(status.sli, None);
+        assert_eq!(status.error_budget_remaining, 1.0);
+        assert_eq!(status.burn_rate_1h, None);
+        assert_eq!(status.alert, SloAlert::Ok);
+    }
+
+    #[test]
+    fn alerts_need_both_windows_to_page() {
+        // A spike in the last hour alone only warns
+        assert_eq!(alert(Some(20.0), Some(3.0), 0.9), SloAlert::Warning);
+        assert_eq!(alert(Some(20.0), Some(8.0), 0.9), SloAlert::Critical);
+        assert_eq!(alert(Some(2.0), Some(6.5), 0.9), SloAlert::Warning);
+        assert_eq!(alert(Some(0.0), Some(0.0), -0.2), SloAlert::Warning);
+        assert_eq!(alert(None, None, 1.0), SloAlert::Ok);
+    }
+}
@@ -2,10 +2,14 @@ use time::OffsetDateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use sqlx::FromRow;
+use crate::pagination::{SortColumn, SortDirection, SortKind};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct StatusEvent {
+    pub id: i64,
     #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
     pub time: OffsetDateTime,
     pub monitor_id: i32,
     pub status: String,
@@ -14,10 +18,12 @@ pub struct StatusEvent {
     pub error_message: Option<String>,
     pub metadata: Option<JsonValue>,
     #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
     pub created_at: OffsetDateTime,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct CreateStatusEvent {
     pub monitor_id: i32,
     pub status: String,
@@ -27,6 +33,55 @@ pub struct CreateStatusEvent {
     pub metadata: Option<JsonValue>,
 }
 
+/// Optional filters for listing status events
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatusEventFilter {
+    pub monitor_id: Option<i32>,
+    pub status: Option<String>,
+    #[serde(default, with = "crate::time_serde::option")]
+    pub from: Option<OffsetDateTime>,
+    #[serde(default, with = "crate::time_serde::option")]
+    pub to: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum StatusEventSort {
+    #[default]
+    Time,
+    Status,
+}
+
+impl StatusEventSort {
+    pub fn column(self) -> SortColumn {
+        match self {
+            StatusEventSort::Time => SortColumn {
+                column: "time",
+                field: "time",
+                kind: SortKind::Time,
+                default_direction: SortDirection::Desc,
+            },
+            StatusEventSort::Status => SortColumn {
+                column: "status",
+                field: "status",
+                kind: SortKind::Text,
+                default_direction: SortDirection::Asc,
+            },
+        }
+    }
+}
+
+/// Bucket size for aggregated statistics
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum StatsGranularity {
+    #[default]
+    Hour,
+    Day,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum StatusType {
     Operational,
@@ -64,8 +119,10 @@ impl From<StatusType> for String {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct StatusHourlyStat {
     #[serde(with = "crate::time_serde::option")]
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::Timestamp>"))]
     pub bucket: Option<OffsetDateTime>,
     pub monitor_id: Option<i32>,
     pub check_count: Option<i64>,
@@ -78,8 +135,10 @@ pub struct StatusHourlyStat {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct StatusDailyStat {
     #[serde(with = "crate::time_serde::option")]
+    #[cfg_attr(feature = "openapi", schemars(with = "Option<crate::time_serde::Timestamp>"))]
     pub bucket: Option<OffsetDateTime>,
     pub monitor_id: Option<i32>,
     pub check_count: Option<i64>,
@@ -91,10 +150,12 @@ pub struct StatusDailyStat {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct MonitorStatusSummary {
     pub monitor_id: i32,
     pub current_status: String,
     #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
     pub last_check_time: OffsetDateTime,
     pub uptime_24h: f64,
     pub uptime_7d: f64,
@@ -102,4 +163,20 @@ pub struct MonitorStatusSummary {
     pub uptime_90d: f64,
     pub avg_response_time_24h: Option<i32>,
     pub incident_count_24h: i64,
+}
+
+/// An active monitor's latest status and uptime, as the metrics endpoint
+/// reports it
+#[derive(Debug, Clone, FromRow)]
+pub struct MonitorMetrics {
+    pub monitor_id: i32,
+    pub name: String,
+    /// `unknown` before the first check
+    pub current_status: String,
+    /// Of the latest check, in milliseconds
+    pub response_time: Option<i32>,
+    /// Percentages, 100 without checks in the window
+    pub uptime_24h: f64,
+    pub uptime_7d: f64,
+    pub uptime_30d: f64,
 }
\ No newline at end of file
@@ -0,0 +1,115 @@
+use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use crate::pagination::{SortColumn, SortDirection, SortKind};
+
+/// A public status page showing a selection of monitors, at `/s/{slug}` or
+/// on its own domain
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct StatusPage {
+    pub id: i32,
+    pub slug: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+    /// Host name that serves this page at `/`
+    pub custom_domain: Option<String>,
+    /// Monitors to show, in display order
+    pub monitor_ids: Vec<i32>,
+    /// Service groups whose monitors, including those of their subgroups,
+    /// follow `monitor_ids`
+    pub service_group_ids: Vec<i32>,
+    /// `public`, `password` or `sso`
+    pub visibility: String,
+    #[serde(skip)]
+    pub password_hash: Option<String>,
+    /// Show monitor URLs and check metadata, which are hidden by default
+    pub show_internal_details: bool,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "crate::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "crate::time_serde::Timestamp"))]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CreateStatusPage {
+    pub slug: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+    pub custom_domain: Option<String>,
+    #[serde(default)]
+    pub monitor_ids: Vec<i32>,
+    #[serde(default)]
+    pub service_group_ids: Vec<i32>,
+    #[serde(default = "default_visibility")]
+    pub visibility: String,
+    /// Required for `password` pages; stored hashed
+    pub password: Option<String>,
+    #[serde(default)]
+    pub show_internal_details: bool,
+}
+
+/// Fields left out are unchanged; an empty `description`, `logo_url` or
+/// `custom_domain` clears it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct UpdateStatusPage {
+    pub slug: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+    pub custom_domain: Option<String>,
+    pub monitor_ids: Option<Vec<i32>>,
+    pub service_group_ids: Option<Vec<i32>>,
+    pub visibility: Option<String>,
+    pub password: Option<String>,
+    pub show_internal_details: Option<bool>,
+}
+
+fn default_visibility() -> String {
+    StatusPageVisibility::Public.into()
+}
+
+/// Who may view a status page
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum StatusPageVisibility {
+    Public,
+    /// Anyone with the page's password
+    Password,
+    /// Signed-in users only
+    Sso,
+}
+
+impl From<String> for StatusPageVisibility {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "public" => StatusPageVisibility::Public,
+            "password" => StatusPageVisibility::Password,
+            // Fail closed on anything unexpected
+            _ => StatusPageVisibility::Sso,
+        }
+    }
+}
+
+impl From<StatusPageVisibility> for String {
+    fn from(visibility: StatusPageVisibility) -> Self {
+        match visibility {
+            StatusPageVisibility::Public => "public".to_string(),
+            StatusPageVisibility::Password => "password".to_string(),
+            StatusPageVisibility::Sso => "sso".to_string(),
+        }
+    }
+}
+
+/// Status pages are listed by slug
+pub const STATUS_PAGE_SORT: SortColumn = SortColumn {
+    column: "slug",
+    field: "slug",
+    kind: SortKind::Text,
+    default_direction: SortDirection::Asc,
+};
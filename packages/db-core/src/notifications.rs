@@ -0,0 +1,194 @@
+//! Change notifications published over Postgres `LISTEN/NOTIFY`.
+//!
+//! Repositories publish after a successful write so that every process
+//! writing to the database (the app and the worker) feeds the same channel.
+//! Publishing is best effort: a failed notification is logged and never
+//! fails the write it describes. [`listen`] is the receiving end.
+
+use crate::models::incident::Incident;
+use crate::models::maintenance_window::MaintenanceWindow;
+use crate::models::status_event::StatusEvent;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::future::Future;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Channel every change is published on
+pub const CHANNEL: &str = "rustgenie_changes";
+
+/// Postgres rejects payloads of 8000 bytes or more
+const MAX_PAYLOAD: usize = 7900;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    StatusEvent {
+        event: StatusEvent,
+        /// Status of the monitor's latest event before this one
+        previous_status: Option<String>,
+    },
+    Incident {
+        action: ChangeAction,
+        incident: Incident,
+    },
+    Maintenance {
+        action: ChangeAction,
+        window: MaintenanceWindow,
+    },
+}
+
+impl Change {
+    /// Serialize for `pg_notify`, dropping free-form metadata and
+    /// descriptions when the payload would be too large to send
+    fn payload(&self) -> Option<String> {
+        let payload = serde_json::to_string(self).ok()?;
+        if payload.len() < MAX_PAYLOAD {
+            return Some(payload);
+        }
+
+        let mut trimmed = self.clone();
+        match &mut trimmed {
+            Change::StatusEvent { event, .. } => event.metadata = None,
+            Change::Incident { incident, .. } => incident.metadata = None,
+            Change::Maintenance { window, .. } => window.description = None,
+        }
+        serde_json::to_string(&trimmed)
+            .ok()
+            .filter(|payload| payload.len() < MAX_PAYLOAD)
+    }
+}
+
+pub async fn publish(pool: &PgPool, change: &Change) {
+    let Some(payload) = change.payload() else {
+        warn!("Change notification too large to publish");
+        return;
+    };
+
+    let result = sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(CHANNEL)
+        .bind(payload)
+        .execute(pool)
+        .await;
+
+    if let Err(e) = result {
+        warn!("Failed to publish change notification: {}", e);
+    }
+}
+
+/// Pass every change published on [`CHANNEL`] to `handle`, one at a time,
+/// for the life of the process. A lost connection is reopened, and changes
+/// published while it was down are missed. `name` labels the log lines.
+pub async fn listen<F, Fut>(pool: &PgPool, name: &str, mut handle: F)
+where
+    F: FnMut(Change) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    loop {
+        let mut listener = match PgListener::connect_with(pool).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("{} could not connect: {}", name, e);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        if let Err(e) = listener.listen(CHANNEL).await {
+            error!("{} could not listen on {}: {}", name, CHANNEL, e);
+            tokio::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        info!("{} listening on {}", name, CHANNEL);
+
+        loop {
+            // recv reconnects by itself; an error here means that failed too
+            match listener.recv().await {
+                Ok(notification) => match serde_json::from_str::<Change>(notification.payload()) {
+                    Ok(change) => handle(change).await,
+                    Err(e) => warn!("Ignoring malformed change notification: {}", e),
+                },
+                Err(e) => {
+                    error!("{} lost its connection: {}", name, e);
+                    break;
+                }
+            }
+        }
+
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// A status event for `monitor_id` that follows one with `previous_status`
+#[cfg(any(test, feature = "test-support"))]
+pub fn status_change(monitor_id: i32, status: &str, previous_status: Option<&str>) -> Change {
+    let now = time::OffsetDateTime::now_utc();
+    Change::StatusEvent {
+        event: StatusEvent {
+            id: 1,
+            time: now,
+            monitor_id,
+            status: status.to_string(),
+            response_time: None,
+            status_code: None,
+            error_message: None,
+            metadata: None,
+            created_at: now,
+        },
+        previous_status: previous_status.map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_metadata_is_dropped_from_the_payload() {
+        let mut change = status_change(1, "operational", None);
+        if let Change::StatusEvent { event, .. } = &mut change {
+            event.metadata = Some(serde_json::json!({ "body": "x".repeat(MAX_PAYLOAD) }));
+        }
+
+        let payload = change.payload().unwrap();
+        let decoded: Change = serde_json::from_str(&payload).unwrap();
+
+        assert!(payload.len() < MAX_PAYLOAD);
+        assert!(matches!(decoded, Change::StatusEvent { event, .. } if event.metadata.is_none()));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn published_changes_reach_listeners() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+        let (sender, mut received) = tokio::sync::mpsc::unbounded_channel();
+        let listening = pool.clone();
+        tokio::spawn(async move {
+            listen(&listening, "Test listener", |change| {
+                let _ = sender.send(change);
+                std::future::ready(())
+            })
+            .await
+        });
+
+        // Publish until the listener has subscribed and passes one on
+        let change = status_change(-1, "degraded", Some("operational"));
+        let first = loop {
+            publish(&pool, &change).await;
+            if let Ok(Some(first)) = tokio::time::timeout(Duration::from_millis(200), received.recv()).await {
+                break first;
+            }
+        };
+        assert!(matches!(first, Change::StatusEvent { event, .. } if event.monitor_id == -1 && event.status == "degraded"));
+    }
+}
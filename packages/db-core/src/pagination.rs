@@ -0,0 +1,280 @@
+//! Keyset (cursor) pagination shared by the list repositories.
+//!
+//! A cursor records the sort column value and tie-breaker key of the last row
+//! on a page. The next page is everything strictly after that pair in sort
+//! order, so pages stay stable while rows are inserted, unlike OFFSET.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::{Postgres, QueryBuilder};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::error::DbError;
+
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    fn sql(self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    fn comparison(self) -> &'static str {
+        match self {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        }
+    }
+}
+
+/// SQL type of a sort column, needed to bind a decoded cursor value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKind {
+    Int,
+    Text,
+    Time,
+}
+
+/// A column a list can be ordered by. `field` is the name the column has in
+/// the serialized model, which is where the next cursor value is read from.
+#[derive(Debug, Clone, Copy)]
+pub struct SortColumn {
+    pub column: &'static str,
+    pub field: &'static str,
+    pub kind: SortKind,
+    /// Used when the client does not ask for a direction
+    pub default_direction: SortDirection,
+}
+
+/// Tie-breaker for tables keyed by a serial `id`
+pub const ID_KEY: SortColumn = SortColumn {
+    column: "id",
+    field: "id",
+    kind: SortKind::Int,
+    default_direction: SortDirection::Asc,
+};
+
+/// Position of the last row of a page, opaque to clients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    #[serde(rename = "s")]
+    pub sort: String,
+    #[serde(rename = "v")]
+    pub value: JsonValue,
+    #[serde(rename = "k")]
+    pub key: i64,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self, DbError> {
+        let invalid = || DbError::InvalidInput("cursor is malformed".to_string());
+        let bytes = URL_SAFE_NO_PAD.decode(encoded).map_err(|_| invalid())?;
+        serde_json::from_slice(&bytes).map_err(|_| invalid())
+    }
+}
+
+/// Page size and position requested by a client
+#[derive(Debug, Clone, Default)]
+pub struct PageRequest {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub direction: Option<SortDirection>,
+}
+
+impl PageRequest {
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema), schemars(rename = "{T}Page"))]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// Applies a sort column, tie-breaker and cursor to a `QueryBuilder` and turns
+/// the fetched rows into a [`Page`].
+pub struct Keyset {
+    sort: SortColumn,
+    key: SortColumn,
+    direction: SortDirection,
+    limit: i64,
+    after: Option<(JsonValue, i64)>,
+}
+
+impl Keyset {
+    /// `key` must be an integer column that, together with `sort`, orders rows
+    /// deterministically (normally the primary key).
+    pub fn new(sort: SortColumn, key: SortColumn, page: &PageRequest) -> Result<Self, DbError> {
+        let after = match &page.cursor {
+            Some(encoded) => {
+                let cursor = Cursor::decode(encoded)?;
+                if cursor.sort != sort.field {
+                    return Err(DbError::InvalidInput(format!(
+                        "cursor was issued for sort '{}', not '{}'",
+                        cursor.sort, sort.field
+                    )));
+                }
+                Some((cursor.value, cursor.key))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            sort,
+            key,
+            direction: page.direction.unwrap_or(sort.default_direction),
+            limit: page.limit(),
+            after,
+        })
+    }
+
+    /// Append the `AND (sort, key) > (value, key)` condition for the cursor.
+    /// The query must already have an open `WHERE` clause.
+    pub fn push_condition(&self, query: &mut QueryBuilder<'_, Postgres>) -> Result<(), DbError> {
+        let Some((value, key)) = &self.after else {
+            return Ok(());
+        };
+
+        query.push(format!(
+            " AND ({}, {}) {} (",
+            self.sort.column,
+            self.key.column,
+            self.direction.comparison()
+        ));
+        push_value(query, self.sort.kind, value)?;
+        query.push(", ").push_bind(*key).push(")");
+
+        Ok(())
+    }
+
+    /// Append `ORDER BY` and a `LIMIT` one past the page size, so
+    /// [`Keyset::into_page`] can tell whether another page exists.
+    pub fn push_order(&self, query: &mut QueryBuilder<'_, Postgres>) {
+        let direction = self.direction.sql();
+        query.push(format!(
+            " ORDER BY {} {}, {} {} LIMIT ",
+            self.sort.column, direction, self.key.column, direction
+        ));
+        query.push_bind(self.limit + 1);
+    }
+
+    pub fn into_page<T: Serialize>(&self, mut rows: Vec<T>) -> Page<T> {
+        let has_more = rows.len() as i64 > self.limit;
+        rows.truncate(self.limit as usize);
+
+        let next_cursor = if has_more {
+            rows.last().and_then(|row| self.cursor_for(row))
+        } else {
+            None
+        };
+
+        Page {
+            data: rows,
+            next_cursor,
+            has_more,
+        }
+    }
+
+    fn cursor_for<T: Serialize>(&self, row: &T) -> Option<String> {
+        let row = serde_json::to_value(row).ok()?;
+        let cursor = Cursor {
+            sort: self.sort.field.to_string(),
+            value: row.get(self.sort.field)?.clone(),
+            key: row.get(self.key.field)?.as_i64()?,
+        };
+        Some(cursor.encode())
+    }
+}
+
+fn push_value(query: &mut QueryBuilder<'_, Postgres>, kind: SortKind, value: &JsonValue) -> Result<(), DbError> {
+    let invalid = || DbError::InvalidInput("cursor value does not match the sort column".to_string());
+
+    match kind {
+        SortKind::Int => {
+            query.push_bind(value.as_i64().ok_or_else(invalid)?);
+        }
+        SortKind::Text => {
+            query.push_bind(value.as_str().ok_or_else(invalid)?.to_string());
+        }
+        SortKind::Time => {
+            let time = value
+                .as_str()
+                .and_then(|s| OffsetDateTime::parse(s, &Rfc3339).ok())
+                .ok_or_else(invalid)?;
+            query.push_bind(time);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NAME: SortColumn = SortColumn {
+        column: "name",
+        field: "name",
+        kind: SortKind::Text,
+        default_direction: SortDirection::Asc,
+    };
+
+    #[test]
+    fn cursor_round_trips_through_encoding() {
+        let cursor = Cursor { sort: "name".to_string(), value: json!("api"), key: 7 };
+
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(Cursor::decode("not a cursor").is_err());
+    }
+
+    #[test]
+    fn into_page_trims_the_lookahead_row_and_points_at_the_last_row() {
+        let page = PageRequest { limit: Some(2), ..Default::default() };
+        let keyset = Keyset::new(NAME, ID_KEY, &page).unwrap();
+        let rows = vec![
+            json!({ "id": 1, "name": "a" }),
+            json!({ "id": 2, "name": "b" }),
+            json!({ "id": 3, "name": "c" }),
+        ];
+
+        let page = keyset.into_page(rows);
+
+        assert_eq!(page.data.len(), 2);
+        assert!(page.has_more);
+        let cursor = Cursor::decode(page.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!((cursor.value, cursor.key), (json!("b"), 2));
+    }
+
+    #[test]
+    fn rejects_a_cursor_issued_for_another_sort() {
+        let cursor = Cursor { sort: "created_at".to_string(), value: json!("x"), key: 1 };
+        let page = PageRequest { cursor: Some(cursor.encode()), ..Default::default() };
+
+        assert!(Keyset::new(NAME, ID_KEY, &page).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::error::DbError;
+use crate::models::audit_log::{AuditLogEntry, AuditLogFilter, CreateAuditLogEntry};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::instrument;
+
+/// Repository for the append-only audit log. Entries can only be inserted and read.
+pub struct AuditLogRepository;
+
+impl AuditLogRepository {
+    #[instrument(name = "AuditLogRepository::create", skip_all, fields(db.system = "postgresql"))]
+    pub async fn create(pool: &PgPool, entry: CreateAuditLogEntry) -> Result<AuditLogEntry, DbError> {
+        let result = sqlx::query_as::<_, AuditLogEntry>(
+            r#"
+            INSERT INTO audit_log (
+                actor_type, actor_id, action, entity_type, entity_id,
+                before_state, after_state, changes, ip_address
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#
+        )
+        .bind(&entry.actor_type)
+        .bind(&entry.actor_id)
+        .bind(&entry.action)
+        .bind(&entry.entity_type)
+        .bind(&entry.entity_id)
+        .bind(&entry.before_state)
+        .bind(&entry.after_state)
+        .bind(&entry.changes)
+        .bind(&entry.ip_address)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// List entries matching the filter, newest first
+    #[instrument(name = "AuditLogRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(
+        pool: &PgPool,
+        filter: &AuditLogFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditLogEntry>, DbError> {
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM audit_log");
+        push_filter(&mut query, filter);
+        query
+            .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let results = query
+            .build_query_as::<AuditLogEntry>()
+            .fetch_all(pool)
+            .await?;
+
+        Ok(results)
+    }
+
+    /// Count entries matching the filter
+    #[instrument(name = "AuditLogRepository::count", skip_all, fields(db.system = "postgresql"))]
+    pub async fn count(pool: &PgPool, filter: &AuditLogFilter) -> Result<i64, DbError> {
+        let mut query = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM audit_log");
+        push_filter(&mut query, filter);
+
+        let (count,): (i64,) = query.build_query_as().fetch_one(pool).await?;
+
+        Ok(count)
+    }
+
+    /// Full history of a single entity, oldest first
+    #[instrument(name = "AuditLogRepository::list_for_entity", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list_for_entity(
+        pool: &PgPool,
+        entity_type: &str,
+        entity_id: &str,
+    ) -> Result<Vec<AuditLogEntry>, DbError> {
+        let results = sqlx::query_as::<_, AuditLogEntry>(
+            r#"
+            SELECT * FROM audit_log
+            WHERE entity_type = $1 AND entity_id = $2
+            ORDER BY created_at, id
+            "#
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(results)
+    }
+}
+
+fn push_filter<'a>(query: &mut QueryBuilder<'a, Postgres>, filter: &'a AuditLogFilter) {
+    query.push(" WHERE TRUE");
+
+    if let Some(actor_type) = &filter.actor_type {
+        query.push(" AND actor_type = ").push_bind(actor_type);
+    }
+    if let Some(actor_id) = &filter.actor_id {
+        query.push(" AND actor_id = ").push_bind(actor_id);
+    }
+    if let Some(action) = &filter.action {
+        query.push(" AND action = ").push_bind(action);
+    }
+    if let Some(entity_type) = &filter.entity_type {
+        query.push(" AND entity_type = ").push_bind(entity_type);
+    }
+    if let Some(entity_id) = &filter.entity_id {
+        query.push(" AND entity_id = ").push_bind(entity_id);
+    }
+    if let Some(from) = filter.from {
+        query.push(" AND created_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        query.push(" AND created_at <= ").push_bind(to);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn entries_cannot_be_changed_or_removed() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+        let entry = AuditLogRepository::create(&pool, CreateAuditLogEntry {
+            actor_type: "system".to_string(),
+            actor_id: None,
+            action: "create".to_string(),
+            entity_type: "audit_log_test".to_string(),
+            entity_id: None,
+            before_state: None,
+            after_state: None,
+            changes: None,
+            ip_address: None,
+        })
+        .await
+        .unwrap();
+
+        let update = sqlx::query("UPDATE audit_log SET actor_id = 'someone else' WHERE id = $1")
+            .bind(entry.id)
+            .execute(&pool)
+            .await;
+        assert!(update.unwrap_err().to_string().contains("append-only"));
+
+        let delete = sqlx::query("DELETE FROM audit_log WHERE id = $1").bind(entry.id).execute(&pool).await;
+        assert!(delete.unwrap_err().to_string().contains("append-only"));
+
+        let actor_id: Option<String> = sqlx::query_scalar("SELECT actor_id FROM audit_log WHERE id = $1")
+            .bind(entry.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(actor_id, None);
+    }
+}
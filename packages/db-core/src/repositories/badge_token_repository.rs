@@ -0,0 +1,82 @@
+use crate::error::DbError;
+use crate::models::badge_token::{BadgeSubject, BadgeToken};
+use sqlx::PgPool;
+use tracing::instrument;
+
+pub struct BadgeTokenRepository;
+
+impl BadgeTokenRepository {
+    /// Whether the subject's badge is private and its token, or `None` when
+    /// its badge was never set either way
+    #[instrument(name = "BadgeTokenRepository::find", skip_all, fields(db.system = "postgresql"))]
+    pub async fn find(pool: &PgPool, subject: BadgeSubject) -> Result<Option<BadgeToken>, DbError> {
+        let (column, id) = subject.column();
+        let token = sqlx::query_as::<_, BadgeToken>(&format!("SELECT * FROM badge_tokens WHERE {} = $1", column))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(token)
+    }
+
+    /// Make the subject's badge private with `token`, replacing any token
+    /// it had
+    #[instrument(name = "BadgeTokenRepository::replace", skip_all, fields(db.system = "postgresql"))]
+    pub async fn replace(pool: &PgPool, subject: BadgeSubject, token: &str) -> Result<BadgeToken, DbError> {
+        let (column, id) = subject.column();
+        let token = sqlx::query_as::<_, BadgeToken>(&format!(
+            r#"
+            INSERT INTO badge_tokens (token, {column}, private)
+            VALUES ($1, $2, TRUE)
+            ON CONFLICT ({column}) DO UPDATE SET token = EXCLUDED.token, private = TRUE, created_at = NOW()
+            RETURNING *
+            "#
+        ))
+        .bind(token)
+        .bind(id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Clear the subject's token. The badge stays private, so it is served
+    /// to nobody until a new token is issued or it is made public.
+    #[instrument(name = "BadgeTokenRepository::revoke", skip_all, fields(db.system = "postgresql"))]
+    pub async fn revoke(pool: &PgPool, subject: BadgeSubject) -> Result<BadgeToken, DbError> {
+        let (column, id) = subject.column();
+        let token = sqlx::query_as::<_, BadgeToken>(&format!(
+            "UPDATE badge_tokens SET token = NULL WHERE {} = $1 AND token IS NOT NULL RETURNING *",
+            column
+        ))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        token.ok_or(DbError::NotFound)
+    }
+
+    /// Make the subject's badge private or public. A private badge keeps its
+    /// token; making it public drops the token, so old URLs stop working if
+    /// it is made private again.
+    #[instrument(name = "BadgeTokenRepository::set_private", skip_all, fields(db.system = "postgresql"))]
+    pub async fn set_private(pool: &PgPool, subject: BadgeSubject, private: bool) -> Result<BadgeToken, DbError> {
+        let (column, id) = subject.column();
+        let token = sqlx::query_as::<_, BadgeToken>(&format!(
+            r#"
+            INSERT INTO badge_tokens ({column}, private)
+            VALUES ($1, $2)
+            ON CONFLICT ({column}) DO UPDATE
+            SET private = EXCLUDED.private,
+                token = CASE WHEN EXCLUDED.private THEN badge_tokens.token END
+            RETURNING *
+            "#
+        ))
+        .bind(id)
+        .bind(private)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(token)
+    }
+}
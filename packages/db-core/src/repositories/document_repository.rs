@@ -1,6 +1,9 @@
 use crate::{DbPool, DbError};
-use crate::models::document::{Document, CreateDocument, UpdateDocument};
+use crate::models::document::{Document, CreateDocument, DocumentFilter, DocumentSort, UpdateDocument};
+use crate::pagination::{Keyset, Page, PageRequest, ID_KEY};
+use sqlx::{Postgres, QueryBuilder};
 use time::OffsetDateTime;
+use tracing::instrument;
 
 /// Repository for document-related database operations
 pub struct DocumentRepository {
@@ -14,6 +17,7 @@ impl DocumentRepository {
     }
     
     /// Get all documents
+    #[instrument(name = "DocumentRepository::get_all", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_all(&self) -> Result<Vec<Document>, DbError> {
         sqlx::query_as::<_, Document>(
             "SELECT id, header, type_name, status, target, limit_value, reviewer, created_at, updated_at FROM documents"
@@ -24,6 +28,7 @@ impl DocumentRepository {
     }
     
     /// Get a document by ID
+    #[instrument(name = "DocumentRepository::get_by_id", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_by_id(&self, id: i32) -> Result<Option<Document>, DbError> {
         sqlx::query_as::<_, Document>(
             "SELECT id, header, type_name, status, target, limit_value, reviewer, created_at, updated_at FROM documents WHERE id = $1"
@@ -35,6 +40,7 @@ impl DocumentRepository {
     }
     
     /// Create a new document
+    #[instrument(name = "DocumentRepository::create", skip_all, fields(db.system = "postgresql"))]
     pub async fn create(&self, document: CreateDocument) -> Result<Document, DbError> {
         let now = OffsetDateTime::now_utc();
         
@@ -57,6 +63,7 @@ impl DocumentRepository {
     }
     
     /// Update an existing document
+    #[instrument(name = "DocumentRepository::update", skip_all, fields(db.system = "postgresql"))]
     pub async fn update(&self, id: i32, document: UpdateDocument) -> Result<Document, DbError> {
         // First check if the document exists
         let existing = self.get_by_id(id).await?;
@@ -96,6 +103,7 @@ impl DocumentRepository {
     }
     
     /// Delete a document by ID
+    #[instrument(name = "DocumentRepository::delete", skip_all, fields(db.system = "postgresql"))]
     pub async fn delete(&self, id: i32) -> Result<(), DbError> {
         let result = sqlx::query("DELETE FROM documents WHERE id = $1")
             .bind(id)
@@ -111,6 +119,7 @@ impl DocumentRepository {
     }
     
     /// Get documents by status
+    #[instrument(name = "DocumentRepository::get_by_status", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_by_status(&self, status: &str) -> Result<Vec<Document>, DbError> {
         sqlx::query_as::<_, Document>(
             "SELECT id, header, type_name, status, target, limit_value, reviewer, created_at, updated_at 
@@ -123,6 +132,7 @@ impl DocumentRepository {
     }
     
     /// Get documents by type
+    #[instrument(name = "DocumentRepository::get_by_type", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_by_type(&self, type_name: &str) -> Result<Vec<Document>, DbError> {
         sqlx::query_as::<_, Document>(
             "SELECT id, header, type_name, status, target, limit_value, reviewer, created_at, updated_at 
@@ -135,6 +145,7 @@ impl DocumentRepository {
     }
     
     /// Get documents by reviewer
+    #[instrument(name = "DocumentRepository::get_by_reviewer", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_by_reviewer(&self, reviewer: &str) -> Result<Vec<Document>, DbError> {
         sqlx::query_as::<_, Document>(
             "SELECT id, header, type_name, status, target, limit_value, reviewer, created_at, updated_at 
@@ -145,4 +156,45 @@ impl DocumentRepository {
         .await
         .map_err(|e| DbError::QueryError(e.to_string()))
     }
+    
+    /// Page through documents matching the filter
+    #[instrument(name = "DocumentRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(
+        &self,
+        filter: &DocumentFilter,
+        sort: DocumentSort,
+        page: &PageRequest,
+    ) -> Result<Page<Document>, DbError> {
+        let keyset = Keyset::new(sort.column(), ID_KEY, page)?;
+
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT id, header, type_name, status, target, limit_value, reviewer, created_at, updated_at \
+             FROM documents WHERE TRUE"
+        );
+        if let Some(status) = &filter.status {
+            query.push(" AND status = ").push_bind(status);
+        }
+        if let Some(type_name) = &filter.type_name {
+            query.push(" AND type_name = ").push_bind(type_name);
+        }
+        if let Some(reviewer) = &filter.reviewer {
+            query.push(" AND reviewer = ").push_bind(reviewer);
+        }
+        if let Some(q) = &filter.q {
+            query
+                .push(" AND to_tsvector('simple', header) @@ websearch_to_tsquery('simple', ")
+                .push_bind(q)
+                .push(")");
+        }
+        keyset.push_condition(&mut query)?;
+        keyset.push_order(&mut query);
+
+        let documents = query
+            .build_query_as::<Document>()
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| DbError::QueryError(e.to_string()))?;
+
+        Ok(keyset.into_page(documents))
+    }
 }
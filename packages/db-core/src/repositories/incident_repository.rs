@@ -1,11 +1,15 @@
 use crate::error::DbError;
-use crate::models::incident::{Incident, CreateIncident, UpdateIncident};
-use sqlx::PgPool;
+use crate::models::incident::{Incident, CreateIncident, IncidentCount, IncidentFilter, IncidentSort, UpdateIncident};
+use crate::notifications::{self, Change, ChangeAction};
+use crate::pagination::{Keyset, Page, PageRequest, ID_KEY};
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use time::OffsetDateTime;
+use tracing::instrument;
 
 pub struct IncidentRepository;
 
 impl IncidentRepository {
+    #[instrument(name = "IncidentRepository::create", skip_all, fields(db.system = "postgresql"))]
     pub async fn create(pool: &PgPool, incident: CreateIncident) -> Result<Incident, DbError> {
         let incident = sqlx::query_as!(
             Incident,
@@ -30,9 +34,16 @@ impl IncidentRepository {
         .fetch_one(pool)
         .await?;
 
+        notifications::publish(pool, &Change::Incident {
+            action: ChangeAction::Created,
+            incident: incident.clone(),
+        })
+        .await;
+
         Ok(incident)
     }
 
+    #[instrument(name = "IncidentRepository::update", skip_all, fields(db.system = "postgresql"))]
     pub async fn update(
         pool: &PgPool,
         id: i32,
@@ -69,20 +80,34 @@ impl IncidentRepository {
         .fetch_one(pool)
         .await?;
 
+        notifications::publish(pool, &Change::Incident {
+            action: ChangeAction::Updated,
+            incident: incident.clone(),
+        })
+        .await;
+
         Ok(incident)
     }
 
+    #[instrument(name = "IncidentRepository::delete", skip_all, fields(db.system = "postgresql"))]
     pub async fn delete(pool: &PgPool, id: i32) -> Result<(), DbError> {
-        sqlx::query!(
-            "DELETE FROM incidents WHERE id = $1",
-            id
-        )
-        .execute(pool)
-        .await?;
+        // The deleted row is returned so listeners know which monitors it affected
+        let incident = sqlx::query_as::<_, Incident>("DELETE FROM incidents WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        notifications::publish(pool, &Change::Incident {
+            action: ChangeAction::Deleted,
+            incident,
+        })
+        .await;
 
         Ok(())
     }
 
+    #[instrument(name = "IncidentRepository::find_by_id", skip_all, fields(db.system = "postgresql"))]
     pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<Incident>, DbError> {
         let incident = sqlx::query_as!(
             Incident,
@@ -102,6 +127,7 @@ impl IncidentRepository {
         Ok(incident)
     }
 
+    #[instrument(name = "IncidentRepository::list_active", skip_all, fields(db.system = "postgresql"))]
     pub async fn list_active(pool: &PgPool) -> Result<Vec<Incident>, DbError> {
         let incidents = sqlx::query_as!(
             Incident,
@@ -121,23 +147,72 @@ impl IncidentRepository {
         Ok(incidents)
     }
 
-    pub async fn list_all(pool: &PgPool, limit: i64) -> Result<Vec<Incident>, DbError> {
-        let incidents = sqlx::query_as!(
-            Incident,
+    /// Page through incidents matching the filter, resolved or not
+    #[instrument(name = "IncidentRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(
+        pool: &PgPool,
+        filter: &IncidentFilter,
+        sort: IncidentSort,
+        page: &PageRequest,
+    ) -> Result<Page<Incident>, DbError> {
+        let keyset = Keyset::new(sort.column(), ID_KEY, page)?;
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM incidents");
+        push_filter(&mut query, filter);
+        keyset.push_condition(&mut query)?;
+        keyset.push_order(&mut query);
+
+        let incidents = query.build_query_as::<Incident>().fetch_all(pool).await?;
+
+        Ok(keyset.into_page(incidents))
+    }
+
+    /// Count incidents by severity and whether they are resolved
+    #[instrument(name = "IncidentRepository::count_by_severity", skip_all, fields(db.system = "postgresql"))]
+    pub async fn count_by_severity(pool: &PgPool) -> Result<Vec<IncidentCount>, DbError> {
+        let counts = sqlx::query_as::<_, IncidentCount>(
             r#"
-            SELECT id, title, message, severity, 
-                affected_monitors as "affected_monitors!: Vec<i32>",
-                created_at, updated_at, started_at, resolved_at, 
-                is_resolved, metadata
+            SELECT severity, is_resolved, COUNT(*) AS count
             FROM incidents
-            ORDER BY created_at DESC
-            LIMIT $1
+            GROUP BY severity, is_resolved
+            ORDER BY severity, is_resolved
             "#,
-            limit
         )
         .fetch_all(pool)
         .await?;
 
-        Ok(incidents)
+        Ok(counts)
+    }
+}
+
+fn push_filter<'a>(query: &mut QueryBuilder<'a, Postgres>, filter: &'a IncidentFilter) {
+    query.push(" WHERE TRUE");
+
+    if let Some(severity) = &filter.severity {
+        query.push(" AND severity = ").push_bind(severity);
+    }
+    if let Some(is_resolved) = filter.is_resolved {
+        query.push(" AND is_resolved = ").push_bind(is_resolved);
+    }
+    if let Some(monitor_id) = filter.monitor_id {
+        query.push(" AND ").push_bind(monitor_id).push(" = ANY(affected_monitors)");
+    }
+    if let Some(q) = &filter.q {
+        query
+            .push(" AND to_tsvector('simple', title || ' ' || message) @@ websearch_to_tsquery('simple', ")
+            .push_bind(q)
+            .push(")");
+    }
+    if let Some(from) = filter.from {
+        query.push(" AND started_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        query.push(" AND started_at <= ").push_bind(to);
+    }
+    if let Some(monitor_ids) = &filter.shown_with {
+        query
+            .push(" AND (affected_monitors = '{}' OR affected_monitors && ")
+            .push_bind(monitor_ids)
+            .push(")");
     }
-}
\ No newline at end of file
+}
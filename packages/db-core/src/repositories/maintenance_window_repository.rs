@@ -0,0 +1,133 @@
+use crate::error::DbError;
+use crate::models::maintenance_window::{
+    CreateMaintenanceWindow, MaintenanceWindow, UpdateMaintenanceWindow, MAINTENANCE_SORT,
+};
+use crate::notifications::{self, Change, ChangeAction};
+use crate::pagination::{Keyset, Page, PageRequest, ID_KEY};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::instrument;
+
+pub struct MaintenanceWindowRepository;
+
+impl MaintenanceWindowRepository {
+    #[instrument(name = "MaintenanceWindowRepository::create", skip_all, fields(db.system = "postgresql"))]
+    pub async fn create(pool: &PgPool, window: CreateMaintenanceWindow) -> Result<MaintenanceWindow, DbError> {
+        let window = sqlx::query_as::<_, MaintenanceWindow>(
+            r#"
+            INSERT INTO maintenance_windows (title, description, affected_monitors, starts_at, ends_at)
+            VALUES ($1, $2, $3::INTEGER[], $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(window.title)
+        .bind(window.description)
+        .bind(window.affected_monitors)
+        .bind(window.starts_at)
+        .bind(window.ends_at)
+        .fetch_one(pool)
+        .await?;
+
+        notifications::publish(pool, &Change::Maintenance {
+            action: ChangeAction::Created,
+            window: window.clone(),
+        })
+        .await;
+
+        Ok(window)
+    }
+
+    #[instrument(name = "MaintenanceWindowRepository::update", skip_all, fields(db.system = "postgresql"))]
+    pub async fn update(
+        pool: &PgPool,
+        id: i32,
+        update: UpdateMaintenanceWindow,
+    ) -> Result<MaintenanceWindow, DbError> {
+        let window = sqlx::query_as::<_, MaintenanceWindow>(
+            r#"
+            UPDATE maintenance_windows
+            SET
+                title = COALESCE($2, title),
+                description = COALESCE($3, description),
+                affected_monitors = COALESCE($4::INTEGER[], affected_monitors),
+                starts_at = COALESCE($5, starts_at),
+                ends_at = COALESCE($6, ends_at)
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(update.title)
+        .bind(update.description)
+        .bind(update.affected_monitors)
+        .bind(update.starts_at)
+        .bind(update.ends_at)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        notifications::publish(pool, &Change::Maintenance {
+            action: ChangeAction::Updated,
+            window: window.clone(),
+        })
+        .await;
+
+        Ok(window)
+    }
+
+    #[instrument(name = "MaintenanceWindowRepository::delete", skip_all, fields(db.system = "postgresql"))]
+    pub async fn delete(pool: &PgPool, id: i32) -> Result<(), DbError> {
+        let window = sqlx::query_as::<_, MaintenanceWindow>("DELETE FROM maintenance_windows WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        notifications::publish(pool, &Change::Maintenance {
+            action: ChangeAction::Deleted,
+            window,
+        })
+        .await;
+
+        Ok(())
+    }
+
+    #[instrument(name = "MaintenanceWindowRepository::find_by_id", skip_all, fields(db.system = "postgresql"))]
+    pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<MaintenanceWindow>, DbError> {
+        let window = sqlx::query_as::<_, MaintenanceWindow>("SELECT * FROM maintenance_windows WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(window)
+    }
+
+    /// Windows in progress or still to come, soonest first
+    #[instrument(name = "MaintenanceWindowRepository::list_upcoming", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list_upcoming(pool: &PgPool) -> Result<Vec<MaintenanceWindow>, DbError> {
+        let windows = sqlx::query_as::<_, MaintenanceWindow>(
+            r#"
+            SELECT * FROM maintenance_windows
+            WHERE ends_at > NOW()
+            ORDER BY starts_at, id
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(windows)
+    }
+
+    /// Page through every window, past ones included
+    #[instrument(name = "MaintenanceWindowRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(pool: &PgPool, page: &PageRequest) -> Result<Page<MaintenanceWindow>, DbError> {
+        let keyset = Keyset::new(MAINTENANCE_SORT, ID_KEY, page)?;
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM maintenance_windows WHERE TRUE");
+        keyset.push_condition(&mut query)?;
+        keyset.push_order(&mut query);
+
+        let windows = query.build_query_as::<MaintenanceWindow>().fetch_all(pool).await?;
+
+        Ok(keyset.into_page(windows))
+    }
+}
@@ -1,11 +1,23 @@
+pub mod badge_token_repository;
 pub mod document_repository;
 pub mod monitor_repository;
 pub mod status_event_repository;
 pub mod incident_repository;
+pub mod maintenance_window_repository;
+pub mod service_group_repository;
+pub mod slo_repository;
+pub mod status_page_repository;
 pub mod user_repository;
+pub mod audit_log_repository;
 
+pub use badge_token_repository::BadgeTokenRepository;
 pub use document_repository::DocumentRepository;
 pub use monitor_repository::MonitorRepository;
 pub use status_event_repository::StatusEventRepository;
 pub use incident_repository::IncidentRepository;
+pub use maintenance_window_repository::MaintenanceWindowRepository;
+pub use service_group_repository::ServiceGroupRepository;
+pub use slo_repository::SloRepository;
+pub use status_page_repository::StatusPageRepository;
 pub use user_repository::UserRepository;
+pub use audit_log_repository::AuditLogRepository;
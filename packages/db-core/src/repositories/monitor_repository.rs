@@ -1,16 +1,19 @@
 use crate::error::DbError;
-use crate::models::monitor::{CreateMonitor, Monitor, UpdateMonitor};
-use sqlx::PgPool;
+use crate::models::monitor::{CreateMonitor, Monitor, MonitorFilter, MonitorSort, UpdateMonitor};
+use crate::pagination::{Keyset, Page, PageRequest, ID_KEY};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::instrument;
 
 pub struct MonitorRepository;
 
 impl MonitorRepository {
+    #[instrument(name = "MonitorRepository::create", skip_all, fields(db.system = "postgresql"))]
     pub async fn create(pool: &PgPool, monitor: CreateMonitor) -> Result<Monitor, DbError> {
         let result = sqlx::query_as!(
             Monitor,
             r#"
-            INSERT INTO monitors (name, display_name, description, url, monitor_type, check_interval, timeout, is_active, metadata)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO monitors (name, display_name, description, url, monitor_type, check_interval, timeout, is_active, metadata, service_group_id, weight)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             RETURNING *
             "#,
             monitor.name,
@@ -21,7 +24,9 @@ impl MonitorRepository {
             monitor.check_interval,
             monitor.timeout,
             monitor.is_active,
-            monitor.metadata
+            monitor.metadata,
+            monitor.service_group_id,
+            monitor.weight
         )
         .fetch_one(pool)
         .await?;
@@ -29,6 +34,7 @@ impl MonitorRepository {
         Ok(result)
     }
 
+    #[instrument(name = "MonitorRepository::find_by_id", skip_all, fields(db.system = "postgresql"))]
     pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<Monitor>, DbError> {
         let result = sqlx::query_as!(
             Monitor,
@@ -43,6 +49,7 @@ impl MonitorRepository {
         Ok(result)
     }
 
+    #[instrument(name = "MonitorRepository::find_by_name", skip_all, fields(db.system = "postgresql"))]
     pub async fn find_by_name(pool: &PgPool, name: &str) -> Result<Option<Monitor>, DbError> {
         let result = sqlx::query_as!(
             Monitor,
@@ -57,6 +64,7 @@ impl MonitorRepository {
         Ok(result)
     }
 
+    #[instrument(name = "MonitorRepository::list_active", skip_all, fields(db.system = "postgresql"))]
     pub async fn list_active(pool: &PgPool) -> Result<Vec<Monitor>, DbError> {
         let results = sqlx::query_as!(
             Monitor,
@@ -70,6 +78,7 @@ impl MonitorRepository {
         Ok(results)
     }
 
+    #[instrument(name = "MonitorRepository::list_all", skip_all, fields(db.system = "postgresql"))]
     pub async fn list_all(pool: &PgPool) -> Result<Vec<Monitor>, DbError> {
         let results = sqlx::query_as!(
             Monitor,
@@ -83,6 +92,27 @@ impl MonitorRepository {
         Ok(results)
     }
 
+    /// Page through monitors matching the filter
+    #[instrument(name = "MonitorRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(
+        pool: &PgPool,
+        filter: &MonitorFilter,
+        sort: MonitorSort,
+        page: &PageRequest,
+    ) -> Result<Page<Monitor>, DbError> {
+        let keyset = Keyset::new(sort.column(), ID_KEY, page)?;
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM monitors");
+        push_filter(&mut query, filter);
+        keyset.push_condition(&mut query)?;
+        keyset.push_order(&mut query);
+
+        let results = query.build_query_as::<Monitor>().fetch_all(pool).await?;
+
+        Ok(keyset.into_page(results))
+    }
+
+    #[instrument(name = "MonitorRepository::update", skip_all, fields(db.system = "postgresql"))]
     pub async fn update(pool: &PgPool, id: i32, monitor: UpdateMonitor) -> Result<Monitor, DbError> {
         let result = sqlx::query_as!(
             Monitor,
@@ -97,7 +127,9 @@ impl MonitorRepository {
                 check_interval = COALESCE($7, check_interval),
                 timeout = COALESCE($8, timeout),
                 is_active = COALESCE($9, is_active),
-                metadata = COALESCE($10, metadata)
+                metadata = COALESCE($10, metadata),
+                service_group_id = NULLIF(COALESCE($11, service_group_id), 0),
+                weight = COALESCE($12, weight)
             WHERE id = $1
             RETURNING *
             "#,
@@ -110,7 +142,9 @@ impl MonitorRepository {
             monitor.check_interval,
             monitor.timeout,
             monitor.is_active,
-            monitor.metadata
+            monitor.metadata,
+            monitor.service_group_id,
+            monitor.weight
         )
         .fetch_one(pool)
         .await?;
@@ -118,8 +152,9 @@ impl MonitorRepository {
         Ok(result)
     }
 
+    #[instrument(name = "MonitorRepository::delete", skip_all, fields(db.system = "postgresql"))]
     pub async fn delete(pool: &PgPool, id: i32) -> Result<(), DbError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             DELETE FROM monitors WHERE id = $1
             "#,
@@ -128,6 +163,56 @@ impl MonitorRepository {
         .execute(pool)
         .await?;
 
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+fn push_filter<'a>(query: &mut QueryBuilder<'a, Postgres>, filter: &'a MonitorFilter) {
+    query.push(" WHERE TRUE");
+
+    if let Some(name) = &filter.name {
+        query.push(" AND name = ").push_bind(name);
+    }
+    if let Some(monitor_type) = &filter.monitor_type {
+        query.push(" AND monitor_type = ").push_bind(monitor_type);
+    }
+    if let Some(is_active) = filter.is_active {
+        query.push(" AND is_active = ").push_bind(is_active);
+    }
+    if let Some(status) = &filter.status {
+        query
+            .push(
+                " AND COALESCE((SELECT se.status FROM status_events se \
+                 WHERE se.monitor_id = monitors.id ORDER BY se.time DESC LIMIT 1), 'unknown') = ",
+            )
+            .push_bind(status);
+    }
+    if let Some(service_group) = &filter.service_group {
+        query
+            .push(" AND service_group_id IN (SELECT id FROM service_groups WHERE name = ")
+            .push_bind(service_group)
+            .push(")");
+    }
+    if let Some(service_group_id) = filter.service_group_id {
+        query.push(" AND service_group_id = ").push_bind(service_group_id);
+    }
+    if let Some(q) = &filter.q {
+        query
+            .push(
+                " AND to_tsvector('simple', name || ' ' || display_name || ' ' || COALESCE(description, '')) \
+                 @@ websearch_to_tsquery('simple', ",
+            )
+            .push_bind(q)
+            .push(")");
+    }
+    if let Some(from) = filter.from {
+        query.push(" AND created_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        query.push(" AND created_at <= ").push_bind(to);
+    }
+}
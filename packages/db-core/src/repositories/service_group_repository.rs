@@ -0,0 +1,108 @@
+use crate::error::DbError;
+use crate::models::service_group::{CreateServiceGroup, ServiceGroup, UpdateServiceGroup, SERVICE_GROUP_SORT};
+use crate::pagination::{Keyset, Page, PageRequest, ID_KEY};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::instrument;
+
+pub struct ServiceGroupRepository;
+
+impl ServiceGroupRepository {
+    #[instrument(name = "ServiceGroupRepository::create", skip_all, fields(db.system = "postgresql"))]
+    pub async fn create(pool: &PgPool, group: CreateServiceGroup) -> Result<ServiceGroup, DbError> {
+        let group = sqlx::query_as::<_, ServiceGroup>(
+            r#"
+            INSERT INTO service_groups (name, description, parent_id, position, weight, status_rollup)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(group.name)
+        .bind(group.description)
+        .bind(group.parent_id)
+        .bind(group.position)
+        .bind(group.weight)
+        .bind(group.status_rollup)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(group)
+    }
+
+    #[instrument(name = "ServiceGroupRepository::update", skip_all, fields(db.system = "postgresql"))]
+    pub async fn update(pool: &PgPool, id: i32, update: UpdateServiceGroup) -> Result<ServiceGroup, DbError> {
+        let group = sqlx::query_as::<_, ServiceGroup>(
+            r#"
+            UPDATE service_groups
+            SET
+                name = COALESCE($2, name),
+                description = COALESCE($3, description),
+                parent_id = NULLIF(COALESCE($4, parent_id), 0),
+                position = COALESCE($5, position),
+                weight = COALESCE($6, weight),
+                status_rollup = COALESCE($7, status_rollup)
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(update.name)
+        .bind(update.description)
+        .bind(update.parent_id)
+        .bind(update.position)
+        .bind(update.weight)
+        .bind(update.status_rollup)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        Ok(group)
+    }
+
+    /// Delete a group. Its monitors become ungrouped and its subgroups move
+    /// to the top level.
+    #[instrument(name = "ServiceGroupRepository::delete", skip_all, fields(db.system = "postgresql"))]
+    pub async fn delete(pool: &PgPool, id: i32) -> Result<(), DbError> {
+        let result = sqlx::query("DELETE FROM service_groups WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[instrument(name = "ServiceGroupRepository::find_by_id", skip_all, fields(db.system = "postgresql"))]
+    pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<ServiceGroup>, DbError> {
+        let group = sqlx::query_as::<_, ServiceGroup>("SELECT * FROM service_groups WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(group)
+    }
+
+    /// Every group in display order, parents and children alike
+    #[instrument(name = "ServiceGroupRepository::list_all", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list_all(pool: &PgPool) -> Result<Vec<ServiceGroup>, DbError> {
+        let groups = sqlx::query_as::<_, ServiceGroup>("SELECT * FROM service_groups ORDER BY position, name, id")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(groups)
+    }
+
+    #[instrument(name = "ServiceGroupRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(pool: &PgPool, page: &PageRequest) -> Result<Page<ServiceGroup>, DbError> {
+        let keyset = Keyset::new(SERVICE_GROUP_SORT, ID_KEY, page)?;
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM service_groups WHERE TRUE");
+        keyset.push_condition(&mut query)?;
+        keyset.push_order(&mut query);
+
+        let groups = query.build_query_as::<ServiceGroup>().fetch_all(pool).await?;
+
+        Ok(keyset.into_page(groups))
+    }
+}
@@ -0,0 +1,288 @@
+use crate::error::DbError;
+use crate::models::slo::{CreateSlo, SliCounts, Slo, SloAlert, SloStatus, UpdateSlo, SLO_SORT};
+use crate::pagination::{Keyset, Page, PageRequest, ID_KEY};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use time::{Duration, OffsetDateTime};
+use tracing::instrument;
+
+pub struct SloRepository;
+
+impl SloRepository {
+    #[instrument(name = "SloRepository::create", skip_all, fields(db.system = "postgresql"))]
+    pub async fn create(pool: &PgPool, slo: CreateSlo) -> Result<Slo, DbError> {
+        let slo = sqlx::query_as::<_, Slo>(
+            r#"
+            INSERT INTO slos (
+                name, description, monitor_id, service_group_id, target, window_days,
+                latency_p95_ms, alert_email
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(slo.name)
+        .bind(slo.description)
+        .bind(slo.monitor_id)
+        .bind(slo.service_group_id)
+        .bind(slo.target)
+        .bind(slo.window_days)
+        .bind(slo.latency_p95_ms)
+        .bind(slo.alert_email)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(slo)
+    }
+
+    #[instrument(name = "SloRepository::update", skip_all, fields(db.system = "postgresql"))]
+    pub async fn update(pool: &PgPool, id: i32, update: UpdateSlo) -> Result<Slo, DbError> {
+        let slo = sqlx::query_as::<_, Slo>(
+            r#"
+            UPDATE slos
+            SET
+                name = COALESCE($2, name),
+                description = NULLIF(COALESCE($3, description), ''),
+                target = COALESCE($4, target),
+                window_days = COALESCE($5, window_days),
+                latency_p95_ms = NULLIF(COALESCE($6, latency_p95_ms), 0),
+                alert_email = NULLIF(COALESCE($7, alert_email), '')
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(update.name)
+        .bind(update.description)
+        .bind(update.target)
+        .bind(update.window_days)
+        .bind(update.latency_p95_ms)
+        .bind(update.alert_email)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        Ok(slo)
+    }
+
+    #[instrument(name = "SloRepository::delete", skip_all, fields(db.system = "postgresql"))]
+    pub async fn delete(pool: &PgPool, id: i32) -> Result<(), DbError> {
+        let result = sqlx::query("DELETE FROM slos WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[instrument(name = "SloRepository::find_by_id", skip_all, fields(db.system = "postgresql"))]
+    pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<Slo>, DbError> {
+        let slo = sqlx::query_as::<_, Slo>("SELECT * FROM slos WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(slo)
+    }
+
+    /// Every SLO by name, for the report and the worker's evaluation
+    #[instrument(name = "SloRepository::list_all", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list_all(pool: &PgPool) -> Result<Vec<Slo>, DbError> {
+        let slos = sqlx::query_as::<_, Slo>("SELECT * FROM slos ORDER BY name, id")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(slos)
+    }
+
+    #[instrument(name = "SloRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(pool: &PgPool, page: &PageRequest) -> Result<Page<Slo>, DbError> {
+        let keyset = Keyset::new(SLO_SORT, ID_KEY, page)?;
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM slos WHERE TRUE");
+        keyset.push_condition(&mut query)?;
+        keyset.push_order(&mut query);
+
+        let slos = query.build_query_as::<Slo>().fetch_all(pool).await?;
+
+        Ok(keyset.into_page(slos))
+    }
+
+    /// Record an alert the worker raised or cleared. `alert_changed_at` only
+    /// moves when the status does.
+    #[instrument(name = "SloRepository::set_alert_status", skip_all, fields(db.system = "postgresql"))]
+    pub async fn set_alert_status(pool: &PgPool, id: i32, alert: SloAlert) -> Result<Slo, DbError> {
+        let slo = sqlx::query_as::<_, Slo>(
+            r#"
+            UPDATE slos
+            SET
+                alert_changed_at = CASE WHEN alert_status = $2 THEN alert_changed_at ELSE NOW() END,
+                alert_status = $2
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(String::from(alert))
+        .fetch_optional(pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        Ok(slo)
+    }
+
+    /// Active monitors the SLO covers: its monitor, or every monitor in its
+    /// service group and the group's subgroups
+    #[instrument(name = "SloRepository::monitor_ids", skip_all, fields(db.system = "postgresql"))]
+    pub async fn monitor_ids(pool: &PgPool, slo: &Slo) -> Result<Vec<i32>, DbError> {
+        // UNION rather than UNION ALL stops at a group already visited
+        let ids = sqlx::query_scalar::<_, i32>(
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT id FROM service_groups WHERE id = $2
+                UNION
+                SELECT service_groups.id FROM service_groups JOIN tree ON service_groups.parent_id = tree.id
+            )
+            SELECT id FROM monitors
+            WHERE is_active AND (id = $1 OR service_group_id IN (SELECT id FROM tree))
+            ORDER BY id
+            "#,
+        )
+        .bind(slo.monitor_id)
+        .bind(slo.service_group_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Good and total checks of `monitor_ids` from the hourly aggregate,
+    /// over the hours that start at or after `since`, so no check before it
+    /// counts and the window may come up to an hour short. A check is good
+    /// when it was operational and, with a latency objective, its hour's p95
+    /// response time met it.
+    #[instrument(name = "SloRepository::sli_counts", skip_all, fields(db.system = "postgresql"))]
+    pub async fn sli_counts(
+        pool: &PgPool,
+        monitor_ids: &[i32],
+        since: OffsetDateTime,
+        latency_p95_ms: Option<i32>,
+    ) -> Result<SliCounts, DbError> {
+        let counts = sqlx::query_as::<_, SliCounts>(
+            r#"
+            SELECT
+                COALESCE(SUM(check_count), 0)::BIGINT AS total,
+                COALESCE(SUM(
+                    CASE
+                        WHEN $3::INTEGER IS NULL OR p95_response_time IS NULL OR p95_response_time <= $3
+                        THEN operational_count
+                        ELSE 0
+                    END
+                ), 0)::BIGINT AS good
+            FROM status_hourly_stats
+            WHERE monitor_id = ANY($1) AND bucket >= $2
+            "#,
+        )
+        .bind(monitor_ids)
+        .bind(since)
+        .bind(latency_p95_ms)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Good and total checks of `monitor_ids` since `since`, counted from
+    /// the status events themselves. Burn-rate windows are too short to
+    /// round to whole hours, so this groups the events by hour the way the
+    /// aggregate does, but only those inside the window.
+    #[instrument(name = "SloRepository::recent_sli_counts", skip_all, fields(db.system = "postgresql"))]
+    pub async fn recent_sli_counts(
+        pool: &PgPool,
+        monitor_ids: &[i32],
+        since: OffsetDateTime,
+        latency_p95_ms: Option<i32>,
+    ) -> Result<SliCounts, DbError> {
+        let counts = sqlx::query_as::<_, SliCounts>(
+            r#"
+            SELECT
+                COALESCE(SUM(check_count), 0)::BIGINT AS total,
+                COALESCE(SUM(
+                    CASE
+                        WHEN $3::INTEGER IS NULL OR p95_response_time IS NULL OR p95_response_time <= $3
+                        THEN operational_count
+                        ELSE 0
+                    END
+                ), 0)::BIGINT AS good
+            FROM (
+                SELECT
+                    COUNT(*) AS check_count,
+                    COUNT(*) FILTER (WHERE status = 'operational') AS operational_count,
+                    percentile_cont(0.95) WITHIN GROUP (ORDER BY response_time)::INTEGER AS p95_response_time
+                FROM status_events
+                WHERE monitor_id = ANY($1) AND time > $2
+                GROUP BY monitor_id, time_bucket('1 hour', time)
+            ) hours
+            "#,
+        )
+        .bind(monitor_ids)
+        .bind(since)
+        .bind(latency_p95_ms)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// The SLO's standing over its window and its burn rates over the
+    /// last hour and six hours
+    #[instrument(name = "SloRepository::get_status", skip_all, fields(db.system = "postgresql"))]
+    pub async fn get_status(pool: &PgPool, slo: Slo) -> Result<SloStatus, DbError> {
+        let now = OffsetDateTime::now_utc();
+        let monitor_ids = Self::monitor_ids(pool, &slo).await?;
+
+        let checks = Self::sli_counts(pool, &monitor_ids, now - Duration::days(slo.window_days as i64), slo.latency_p95_ms).await?;
+        let last_hour = Self::recent_sli_counts(pool, &monitor_ids, now - Duration::hours(1), slo.latency_p95_ms).await?;
+        let last_six_hours = Self::recent_sli_counts(pool, &monitor_ids, now - Duration::hours(6), slo.latency_p95_ms).await?;
+
+        Ok(SloStatus::new(slo, monitor_ids, checks, last_hour, last_six_hours))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn the_last_hour_counts_only_its_own_checks() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+        let monitor_id: i32 = sqlx::query_scalar(
+            "INSERT INTO monitors (name, display_name) VALUES ('slo-burn-window-test', 'Burn') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        // Healthy 90 minutes ago, failing in the last 10
+        sqlx::query(
+            r#"
+            INSERT INTO status_events (time, monitor_id, status)
+            SELECT NOW() - INTERVAL '90 minutes', $1, 'operational' FROM generate_series(1, 9)
+            UNION ALL
+            SELECT NOW() - INTERVAL '10 minutes', $1, 'major_outage'
+            "#,
+        )
+        .bind(monitor_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let since = OffsetDateTime::now_utc() - Duration::hours(1);
+        let counts = SloRepository::recent_sli_counts(&pool, &[monitor_id], since, None).await.unwrap();
+
+        sqlx::query("DELETE FROM monitors WHERE id = $1").bind(monitor_id).execute(&pool).await.unwrap();
+        assert_eq!((counts.total, counts.good), (1, 0));
+    }
+}
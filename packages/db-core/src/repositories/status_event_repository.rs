@@ -1,34 +1,78 @@
 use crate::error::DbError;
 use crate::models::status_event::{
-    CreateStatusEvent, MonitorStatusSummary, StatusDailyStat, StatusEvent, StatusHourlyStat,
+    CreateStatusEvent, MonitorMetrics, MonitorStatusSummary, StatusDailyStat, StatusEvent, StatusEventFilter,
+    StatusEventSort, StatusHourlyStat,
 };
+use crate::notifications::{self, Change};
+use crate::pagination::{Keyset, Page, PageRequest, ID_KEY};
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
 use time::{OffsetDateTime, Duration};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::instrument;
 
 pub struct StatusEventRepository;
 
+/// A new event with the status its monitor had before it
+#[derive(sqlx::FromRow)]
+struct CreatedStatusEvent {
+    #[sqlx(flatten)]
+    event: StatusEvent,
+    previous_status: Option<String>,
+}
+
 impl StatusEventRepository {
+    #[instrument(name = "StatusEventRepository::create", skip_all, fields(db.system = "postgresql"))]
     pub async fn create(pool: &PgPool, event: CreateStatusEvent) -> Result<StatusEvent, DbError> {
-        let result = sqlx::query_as!(
-            StatusEvent,
+        let mut tx = pool.begin().await?;
+
+        // Events for the same monitor wait here, so each one sees the one
+        // recorded before it; a snapshot alone would let two miss each other
+        sqlx::query("SELECT 1 FROM monitors WHERE id = $1 FOR NO KEY UPDATE")
+            .bind(event.monitor_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // The previous status is read in the same statement so listeners can
+        // tell when the monitor changes state. clock_timestamp() rather than
+        // NOW(), which is when the transaction began, before the wait above.
+        let CreatedStatusEvent { event: result, previous_status } = sqlx::query_as::<_, CreatedStatusEvent>(
             r#"
-            INSERT INTO status_events (time, monitor_id, status, response_time, status_code, error_message, metadata)
-            VALUES (NOW(), $1, $2, $3, $4, $5, $6)
-            RETURNING *
+            WITH previous AS (
+                SELECT status FROM status_events
+                WHERE monitor_id = $1
+                ORDER BY time DESC, id DESC
+                LIMIT 1
+            ),
+            inserted AS (
+                INSERT INTO status_events (time, monitor_id, status, response_time, status_code, error_message, metadata)
+                VALUES (clock_timestamp(), $1, $2, $3, $4, $5, $6)
+                RETURNING *
+            )
+            SELECT inserted.*, (SELECT status FROM previous) AS previous_status
+            FROM inserted
             "#,
-            event.monitor_id,
-            event.status,
-            event.response_time,
-            event.status_code,
-            event.error_message,
-            event.metadata
         )
-        .fetch_one(pool)
+        .bind(event.monitor_id)
+        .bind(event.status)
+        .bind(event.response_time)
+        .bind(event.status_code)
+        .bind(event.error_message)
+        .bind(event.metadata)
+        .fetch_one(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
+        notifications::publish(pool, &Change::StatusEvent {
+            event: result.clone(),
+            previous_status,
+        })
+        .await;
+
         Ok(result)
     }
 
+    #[instrument(name = "StatusEventRepository::get_recent_events", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_recent_events(
         pool: &PgPool,
         monitor_id: i32,
@@ -51,6 +95,63 @@ impl StatusEventRepository {
         Ok(results)
     }
 
+    /// Page through events matching the filter, newest first unless asked otherwise
+    #[instrument(name = "StatusEventRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(
+        pool: &PgPool,
+        filter: &StatusEventFilter,
+        sort: StatusEventSort,
+        page: &PageRequest,
+    ) -> Result<Page<StatusEvent>, DbError> {
+        let keyset = Keyset::new(sort.column(), ID_KEY, page)?;
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM status_events WHERE TRUE");
+        if let Some(monitor_id) = filter.monitor_id {
+            query.push(" AND monitor_id = ").push_bind(monitor_id);
+        }
+        if let Some(status) = &filter.status {
+            query.push(" AND status = ").push_bind(status);
+        }
+        if let Some(from) = filter.from {
+            query.push(" AND time >= ").push_bind(from);
+        }
+        if let Some(to) = filter.to {
+            query.push(" AND time <= ").push_bind(to);
+        }
+        keyset.push_condition(&mut query)?;
+        keyset.push_order(&mut query);
+
+        let results = query.build_query_as::<StatusEvent>().fetch_all(pool).await?;
+
+        Ok(keyset.into_page(results))
+    }
+
+    /// Stream every event matching the filter, oldest first, without loading
+    /// the result set into memory. Used for exports of large ranges.
+    pub fn stream<'a>(
+        pool: &'a PgPool,
+        filter: &StatusEventFilter,
+    ) -> BoxStream<'a, Result<StatusEvent, DbError>> {
+        sqlx::query_as::<_, StatusEvent>(
+            r#"
+            SELECT * FROM status_events
+            WHERE ($1::INTEGER IS NULL OR monitor_id = $1)
+              AND ($2::TEXT IS NULL OR status = $2)
+              AND ($3::TIMESTAMPTZ IS NULL OR time >= $3)
+              AND ($4::TIMESTAMPTZ IS NULL OR time <= $4)
+            ORDER BY time, id
+            "#
+        )
+        .bind(filter.monitor_id)
+        .bind(filter.status.clone())
+        .bind(filter.from)
+        .bind(filter.to)
+        .fetch(pool)
+        .map_err(DbError::from)
+        .boxed()
+    }
+
+    #[instrument(name = "StatusEventRepository::get_events_in_range", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_events_in_range(
         pool: &PgPool,
         monitor_id: i32,
@@ -74,6 +175,7 @@ impl StatusEventRepository {
         Ok(results)
     }
 
+    #[instrument(name = "StatusEventRepository::get_latest_status", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_latest_status(
         pool: &PgPool,
         monitor_id: i32,
@@ -94,13 +196,14 @@ impl StatusEventRepository {
         Ok(result)
     }
 
+    /// Hourly aggregates with buckets starting in `[from, to]`, newest first
+    #[instrument(name = "StatusEventRepository::get_hourly_stats", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_hourly_stats(
         pool: &PgPool,
         monitor_id: i32,
-        hours: i32,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
     ) -> Result<Vec<StatusHourlyStat>, DbError> {
-        let start_time = OffsetDateTime::now_utc() - Duration::hours(hours as i64);
-        
         let results = sqlx::query_as!(
             StatusHourlyStat,
             r#"
@@ -115,11 +218,12 @@ impl StatusEventRepository {
                 max_response_time,
                 p95_response_time
             FROM status_hourly_stats
-            WHERE monitor_id = $1 AND bucket >= $2
+            WHERE monitor_id = $1 AND bucket >= $2 AND bucket <= $3
             ORDER BY bucket DESC
             "#,
             monitor_id,
-            start_time
+            from,
+            to
         )
         .fetch_all(pool)
         .await?;
@@ -127,13 +231,14 @@ impl StatusEventRepository {
         Ok(results)
     }
 
+    /// Daily aggregates with buckets starting in `[from, to]`, newest first
+    #[instrument(name = "StatusEventRepository::get_daily_stats", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_daily_stats(
         pool: &PgPool,
         monitor_id: i32,
-        days: i32,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
     ) -> Result<Vec<StatusDailyStat>, DbError> {
-        let start_time = OffsetDateTime::now_utc() - Duration::days(days as i64);
-        
         let results = sqlx::query_as!(
             StatusDailyStat,
             r#"
@@ -147,11 +252,12 @@ impl StatusEventRepository {
                 avg_response_time,
                 p95_response_time
             FROM status_daily_stats
-            WHERE monitor_id = $1 AND bucket >= $2
+            WHERE monitor_id = $1 AND bucket >= $2 AND bucket <= $3
             ORDER BY bucket DESC
             "#,
             monitor_id,
-            start_time
+            from,
+            to
         )
         .fetch_all(pool)
         .await?;
@@ -159,6 +265,49 @@ impl StatusEventRepository {
         Ok(results)
     }
 
+    /// Latest status and uptime of every active monitor in one query
+    #[instrument(name = "StatusEventRepository::get_monitor_metrics", skip_all, fields(db.system = "postgresql"))]
+    pub async fn get_monitor_metrics(pool: &PgPool) -> Result<Vec<MonitorMetrics>, DbError> {
+        let metrics = sqlx::query_as::<_, MonitorMetrics>(
+            r#"
+            SELECT
+                monitors.id AS monitor_id,
+                monitors.name,
+                COALESCE(latest.status, 'unknown') AS current_status,
+                latest.response_time,
+                COALESCE(uptime.uptime_24h, 100) AS uptime_24h,
+                COALESCE(uptime.uptime_7d, 100) AS uptime_7d,
+                COALESCE(uptime.uptime_30d, 100) AS uptime_30d
+            FROM monitors
+            LEFT JOIN LATERAL (
+                SELECT status, response_time FROM status_events
+                WHERE monitor_id = monitors.id
+                ORDER BY time DESC, id DESC
+                LIMIT 1
+            ) latest ON TRUE
+            LEFT JOIN (
+                SELECT
+                    monitor_id,
+                    COUNT(*) FILTER (WHERE status = 'operational' AND time >= NOW() - INTERVAL '24 hours')::FLOAT
+                        / NULLIF(COUNT(*) FILTER (WHERE time >= NOW() - INTERVAL '24 hours'), 0) * 100 AS uptime_24h,
+                    COUNT(*) FILTER (WHERE status = 'operational' AND time >= NOW() - INTERVAL '7 days')::FLOAT
+                        / NULLIF(COUNT(*) FILTER (WHERE time >= NOW() - INTERVAL '7 days'), 0) * 100 AS uptime_7d,
+                    COUNT(*) FILTER (WHERE status = 'operational')::FLOAT / NULLIF(COUNT(*), 0) * 100 AS uptime_30d
+                FROM status_events
+                WHERE time >= NOW() - INTERVAL '30 days'
+                GROUP BY monitor_id
+            ) uptime ON uptime.monitor_id = monitors.id
+            WHERE monitors.is_active
+            ORDER BY monitors.id
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(metrics)
+    }
+
+    #[instrument(name = "StatusEventRepository::get_monitor_summary", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_monitor_summary(
         pool: &PgPool,
         monitor_id: i32,
@@ -284,6 +433,7 @@ impl StatusEventRepository {
         })
     }
 
+    #[instrument(name = "StatusEventRepository::get_status_tracker_data", skip_all, fields(db.system = "postgresql"))]
     pub async fn get_status_tracker_data(
         pool: &PgPool,
         monitor_id: i32,
@@ -323,4 +473,92 @@ impl StatusEventRepository {
 
         Ok(results)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn pages_keep_events_recorded_at_the_same_instant() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+        let monitor_id: i32 = sqlx::query_scalar(
+            "INSERT INTO monitors (name, display_name) VALUES ('status-event-paging-test', 'Paging') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let mut inserted: Vec<i64> = sqlx::query_scalar(
+            "INSERT INTO status_events (time, monitor_id, status) SELECT NOW(), $1, 'operational' FROM generate_series(1, 3) RETURNING id",
+        )
+        .bind(monitor_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let filter = StatusEventFilter { monitor_id: Some(monitor_id), ..Default::default() };
+        let mut page = PageRequest { limit: Some(1), ..Default::default() };
+        let mut ids = Vec::new();
+        loop {
+            let events = StatusEventRepository::list(&pool, &filter, StatusEventSort::Time, &page).await.unwrap();
+            ids.extend(events.data.iter().map(|event| event.id));
+            match events.next_cursor {
+                Some(cursor) => page.cursor = Some(cursor),
+                None => break,
+            }
+        }
+
+        sqlx::query("DELETE FROM monitors WHERE id = $1").bind(monitor_id).execute(&pool).await.unwrap();
+        // Every event exactly once, none skipped for a repeat
+        let unique: std::collections::HashSet<i64> = ids.iter().copied().collect();
+        assert_eq!(unique.len(), ids.len(), "{:?}", ids);
+        ids.sort_unstable();
+        inserted.sort_unstable();
+        assert_eq!(ids, inserted);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn concurrent_events_each_see_the_one_before() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+        let monitor_id: i32 = sqlx::query_scalar(
+            "INSERT INTO monitors (name, display_name) VALUES ('status-event-previous-test', 'Previous') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let mut listener = sqlx::postgres::PgListener::connect_with(&pool).await.unwrap();
+        listener.listen(notifications::CHANNEL).await.unwrap();
+
+        let statuses = ["operational", "degraded", "major_outage", "partial_outage"];
+        futures::future::try_join_all((0..20).map(|i| {
+            StatusEventRepository::create(&pool, CreateStatusEvent {
+                monitor_id,
+                status: statuses[i % statuses.len()].to_string(),
+                response_time: None,
+                status_code: None,
+                error_message: None,
+                metadata: None,
+            })
+        }))
+        .await
+        .unwrap();
+
+        let mut changes = Vec::new();
+        while changes.len() < 20 {
+            let notification = listener.recv().await.unwrap();
+            if let Ok(Change::StatusEvent { event, previous_status }) = serde_json::from_str(notification.payload()) {
+                if event.monitor_id == monitor_id {
+                    changes.push((event, previous_status));
+                }
+            }
+        }
+        sqlx::query("DELETE FROM monitors WHERE id = $1").bind(monitor_id).execute(&pool).await.unwrap();
+
+        changes.sort_by_key(|(event, _)| event.id);
+        assert_eq!(changes[0].1, None);
+        for pair in changes.windows(2) {
+            assert_eq!(pair[1].1.as_deref(), Some(pair[0].0.status.as_str()));
+        }
+    }
+}
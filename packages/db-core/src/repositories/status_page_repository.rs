@@ -0,0 +1,153 @@
+use crate::error::DbError;
+use crate::models::status_page::{CreateStatusPage, StatusPage, UpdateStatusPage, STATUS_PAGE_SORT};
+use crate::pagination::{Keyset, Page, PageRequest, ID_KEY};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::instrument;
+
+pub struct StatusPageRepository;
+
+impl StatusPageRepository {
+    /// Create a page. The plain-text password in `page` is ignored; pass its
+    /// hash as `password_hash`.
+    #[instrument(name = "StatusPageRepository::create", skip_all, fields(db.system = "postgresql"))]
+    pub async fn create(
+        pool: &PgPool,
+        page: CreateStatusPage,
+        password_hash: Option<String>,
+    ) -> Result<StatusPage, DbError> {
+        let page = sqlx::query_as::<_, StatusPage>(
+            r#"
+            INSERT INTO status_pages (
+                slug, title, description, logo_url, custom_domain, monitor_ids,
+                service_group_ids, visibility, password_hash, show_internal_details
+            )
+            VALUES ($1, $2, $3, $4, $5, $6::INTEGER[], $7::INTEGER[], $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(page.slug)
+        .bind(page.title)
+        .bind(page.description)
+        .bind(page.logo_url)
+        .bind(page.custom_domain)
+        .bind(page.monitor_ids)
+        .bind(page.service_group_ids)
+        .bind(page.visibility)
+        .bind(password_hash)
+        .bind(page.show_internal_details)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(page)
+    }
+
+    /// Update a page; a `password_hash` replaces the stored one
+    #[instrument(name = "StatusPageRepository::update", skip_all, fields(db.system = "postgresql"))]
+    pub async fn update(
+        pool: &PgPool,
+        id: i32,
+        update: UpdateStatusPage,
+        password_hash: Option<String>,
+    ) -> Result<StatusPage, DbError> {
+        let page = sqlx::query_as::<_, StatusPage>(
+            r#"
+            UPDATE status_pages
+            SET
+                slug = COALESCE($2, slug),
+                title = COALESCE($3, title),
+                description = NULLIF(COALESCE($4, description), ''),
+                logo_url = NULLIF(COALESCE($5, logo_url), ''),
+                custom_domain = NULLIF(COALESCE($6, custom_domain), ''),
+                monitor_ids = COALESCE($7::INTEGER[], monitor_ids),
+                service_group_ids = COALESCE($8::INTEGER[], service_group_ids),
+                visibility = COALESCE($9, visibility),
+                password_hash = COALESCE($10, password_hash),
+                show_internal_details = COALESCE($11, show_internal_details)
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(update.slug)
+        .bind(update.title)
+        .bind(update.description)
+        .bind(update.logo_url)
+        .bind(update.custom_domain)
+        .bind(update.monitor_ids)
+        .bind(update.service_group_ids)
+        .bind(update.visibility)
+        .bind(password_hash)
+        .bind(update.show_internal_details)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        Ok(page)
+    }
+
+    #[instrument(name = "StatusPageRepository::delete", skip_all, fields(db.system = "postgresql"))]
+    pub async fn delete(pool: &PgPool, id: i32) -> Result<(), DbError> {
+        let result = sqlx::query("DELETE FROM status_pages WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[instrument(name = "StatusPageRepository::find_by_id", skip_all, fields(db.system = "postgresql"))]
+    pub async fn find_by_id(pool: &PgPool, id: i32) -> Result<Option<StatusPage>, DbError> {
+        let page = sqlx::query_as::<_, StatusPage>("SELECT * FROM status_pages WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(page)
+    }
+
+    #[instrument(name = "StatusPageRepository::find_by_slug", skip_all, fields(db.system = "postgresql"))]
+    pub async fn find_by_slug(pool: &PgPool, slug: &str) -> Result<Option<StatusPage>, DbError> {
+        let page = sqlx::query_as::<_, StatusPage>("SELECT * FROM status_pages WHERE slug = $1")
+            .bind(slug)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(page)
+    }
+
+    /// The page served on `domain`, which must already be lowercase
+    #[instrument(name = "StatusPageRepository::find_by_domain", skip_all, fields(db.system = "postgresql"))]
+    pub async fn find_by_domain(pool: &PgPool, domain: &str) -> Result<Option<StatusPage>, DbError> {
+        let page = sqlx::query_as::<_, StatusPage>("SELECT * FROM status_pages WHERE custom_domain = $1")
+            .bind(domain)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(page)
+    }
+
+    #[instrument(name = "StatusPageRepository::list_all", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list_all(pool: &PgPool) -> Result<Vec<StatusPage>, DbError> {
+        let pages = sqlx::query_as::<_, StatusPage>("SELECT * FROM status_pages ORDER BY id")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(pages)
+    }
+
+    #[instrument(name = "StatusPageRepository::list", skip_all, fields(db.system = "postgresql"))]
+    pub async fn list(pool: &PgPool, page: &PageRequest) -> Result<Page<StatusPage>, DbError> {
+        let keyset = Keyset::new(STATUS_PAGE_SORT, ID_KEY, page)?;
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM status_pages WHERE TRUE");
+        keyset.push_condition(&mut query)?;
+        keyset.push_order(&mut query);
+
+        let pages = query.build_query_as::<StatusPage>().fetch_all(pool).await?;
+
+        Ok(keyset.into_page(pages))
+    }
+}
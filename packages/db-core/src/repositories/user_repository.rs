@@ -3,6 +3,7 @@
 use sqlx::PgPool;
 use crate::error::DbError;
 use crate::models::user::{User, CreateUser, UpdateUser};
+use tracing::instrument;
 
 /// Repository for user database operations
 pub struct UserRepository<'a> {
@@ -16,6 +17,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Find user by ID
+    #[instrument(name = "UserRepository::find_by_id", skip_all, fields(db.system = "postgresql"))]
     pub async fn find_by_id(&self, id: i32) -> Result<Option<User>, DbError> {
         let user = sqlx::query_as::<_, User>(
             "SELECT * FROM users WHERE id = $1"
@@ -29,6 +31,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Find user by OIDC subject
+    #[instrument(name = "UserRepository::find_by_oidc_subject", skip_all, fields(db.system = "postgresql"))]
     pub async fn find_by_oidc_subject(&self, oidc_subject: &str) -> Result<Option<User>, DbError> {
         let user = sqlx::query_as::<_, User>(
             "SELECT * FROM users WHERE oidc_subject = $1"
@@ -42,6 +45,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Create a new user
+    #[instrument(name = "UserRepository::create", skip_all, fields(db.system = "postgresql"))]
     pub async fn create(&self, create_user: CreateUser) -> Result<User, DbError> {
         let user = sqlx::query_as::<_, User>(
             r#"
@@ -61,6 +65,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Update user's last login time
+    #[instrument(name = "UserRepository::update_last_login", skip_all, fields(db.system = "postgresql"))]
     pub async fn update_last_login(&self, id: i32) -> Result<(), DbError> {
         sqlx::query(
             "UPDATE users SET last_login_at = NOW() WHERE id = $1"
@@ -74,6 +79,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Update user information
+    #[instrument(name = "UserRepository::update", skip_all, fields(db.system = "postgresql"))]
     pub async fn update(&self, id: i32, update_user: UpdateUser) -> Result<User, DbError> {
         let user = sqlx::query_as::<_, User>(
             r#"
@@ -94,6 +100,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Find or create user by OIDC subject
+    #[instrument(name = "UserRepository::find_or_create_by_oidc_subject", skip_all, fields(db.system = "postgresql"))]
     pub async fn find_or_create_by_oidc_subject(
         &self,
         oidc_subject: &str,
@@ -118,6 +125,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// List all users (for admin purposes)
+    #[instrument(name = "UserRepository::list", skip_all, fields(db.system = "postgresql"))]
     pub async fn list(&self, limit: Option<i64>, offset: Option<i64>) -> Result<Vec<User>, DbError> {
         let limit = limit.unwrap_or(50);
         let offset = offset.unwrap_or(0);
@@ -135,6 +143,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Count total users
+    #[instrument(name = "UserRepository::count", skip_all, fields(db.system = "postgresql"))]
     pub async fn count(&self) -> Result<i64, DbError> {
         let count: (i64,) = sqlx::query_as(
             "SELECT COUNT(*) FROM users"
@@ -147,6 +156,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Delete user by ID
+    #[instrument(name = "UserRepository::delete", skip_all, fields(db.system = "postgresql"))]
     pub async fn delete(&self, id: i32) -> Result<(), DbError> {
         sqlx::query("DELETE FROM users WHERE id = $1")
             .bind(id)
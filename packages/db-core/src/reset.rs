@@ -1,15 +1,13 @@
+use config_core::DatabaseConfig;
 use sqlx::PgPool;
 use crate::error::DbError;
 
-pub async fn reset_database() -> Result<(), DbError> {
-    dotenv::dotenv().ok();
-    
-    let database_url = std::env::var("DATABASE_URL")
-        .map_err(|_| DbError::ConnectionError("DATABASE_URL not set".to_string()))?;
+pub async fn reset_database(config: &DatabaseConfig) -> Result<(), DbError> {
+    let database_url = &config.url;
     
     // Parse the URL to get the database name
-    let url = url::Url::parse(&database_url)
-        .map_err(|e| DbError::ConnectionError(format!("Invalid DATABASE_URL: {}", e)))?;
+    let url = url::Url::parse(database_url)
+        .map_err(|e| DbError::ConnectionError(format!("Invalid database URL: {}", e)))?;
     
     let db_name = url.path().trim_start_matches('/');
     
@@ -42,7 +40,7 @@ pub async fn reset_database() -> Result<(), DbError> {
     println!("Database {} dropped and recreated", db_name);
     
     // Now connect to the new database and run migrations
-    let pool = PgPool::connect(&database_url).await
+    let pool = PgPool::connect(database_url).await
         .map_err(|e| DbError::ConnectionError(e.to_string()))?;
     
     // Run migrations
@@ -63,6 +61,7 @@ mod tests {
     #[tokio::test]
     #[ignore] // Only run manually
     async fn test_reset_database() {
-        reset_database().await.unwrap();
+        let config = config_core::Config::load().unwrap();
+        reset_database(&config.database).await.unwrap();
     }
 }
\ No newline at end of file
@@ -8,6 +8,7 @@ pub async fn seed_monitors(pool: &PgPool) -> Result<(), DbError> {
     sqlx::query!("DELETE FROM status_events").execute(pool).await?;
     sqlx::query!("DELETE FROM monitors").execute(pool).await?;
     sqlx::query!("DELETE FROM incidents").execute(pool).await?;
+    sqlx::query("DELETE FROM service_groups").execute(pool).await?;
     
     // Seed CRM Application monitors with a mix of working and failing services
     let crm_monitors = vec![
@@ -133,6 +134,32 @@ pub async fn seed_monitors(pool: &PgPool) -> Result<(), DbError> {
         .await?;
     }
     
+    // Create the service groups named in the metadata above and move the
+    // monitors into them
+    let groups = [
+        ("CRM Application", "Customer relationship management"),
+        ("Test Services", "Endpoints used to exercise the monitors"),
+        ("External Services", "Third-party providers"),
+    ];
+    for (position, (name, description)) in groups.into_iter().enumerate() {
+        let group_id: i32 = sqlx::query_scalar(
+            "INSERT INTO service_groups (name, description, position) VALUES ($1, $2, $3) RETURNING id",
+        )
+        .bind(name)
+        .bind(description)
+        .bind(position as i32)
+        .fetch_one(pool)
+        .await?;
+
+        sqlx::query(
+            "UPDATE monitors SET service_group_id = $1 WHERE metadata->>'service_group' = $2",
+        )
+        .bind(group_id)
+        .bind(name)
+        .execute(pool)
+        .await?;
+    }
+    
     // Note: We'll fetch the actual monitor IDs after creation
     let db_monitor_id = sqlx::query!("SELECT id FROM monitors WHERE name = 'crm-database'")
         .fetch_optional(pool)
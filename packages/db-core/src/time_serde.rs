@@ -47,4 +47,50 @@ pub mod option {
             None => Ok(None),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Schema stand-in for timestamps serialized by this module, for use as
+/// `#[schemars(with = "crate::time_serde::Timestamp")]`
+#[cfg(feature = "openapi")]
+pub struct Timestamp;
+
+#[cfg(feature = "openapi")]
+impl schemars::JsonSchema for Timestamp {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Timestamp".into()
+    }
+
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string", "format": "date-time" })
+    }
+}
+
+/// Schema stand-in for timestamps left in the `time` crate's own serde
+/// format: `[year, day of year, hour, minute, second, nanosecond, offset
+/// hours, offset minutes, offset seconds]`
+#[cfg(feature = "openapi")]
+pub struct TimeTuple;
+
+#[cfg(feature = "openapi")]
+impl schemars::JsonSchema for TimeTuple {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "TimeTuple".into()
+    }
+
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "array",
+            "items": { "type": "integer" },
+            "minItems": 9,
+            "maxItems": 9,
+        })
+    }
+}
@@ -0,0 +1,288 @@
+//! Probes that check whether a monitored service is up.
+//!
+//! The worker runs them for every scheduled check and records the outcome;
+//! the app runs them directly to dry-run a monitor config before it is saved.
+
+pub mod prometheus;
+pub mod public;
+pub mod schedule;
+
+use db_core::models::monitor::Monitor;
+use db_core::models::status_event::CreateStatusEvent;
+use reqwest::{Client, Url};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use tracing::debug;
+
+/// Monitor types that have a probe. Other types are recorded by external
+/// reporters (heartbeats, the events API) and never checked by us.
+pub const SUPPORTED_TYPES: &[&str] = &["http", "tcp", "prometheus"];
+
+/// What to check, borrowed from a saved monitor or an unsaved config
+#[derive(Debug, Clone, Copy)]
+pub struct CheckTarget<'a> {
+    pub monitor_type: &'a str,
+    pub url: Option<&'a str>,
+    /// Seconds
+    pub timeout: i32,
+    /// Type-specific settings, such as the rules of a `prometheus` monitor
+    pub metadata: Option<&'a Value>,
+    /// Refuse non-public addresses (see [`public`]), for dry runs
+    pub public_only: bool,
+}
+
+impl<'a> From<&'a Monitor> for CheckTarget<'a> {
+    fn from(monitor: &'a Monitor) -> Self {
+        Self {
+            monitor_type: &monitor.monitor_type,
+            url: monitor.url.as_deref(),
+            timeout: monitor.timeout,
+            metadata: monitor.metadata.as_ref(),
+            public_only: false,
+        }
+    }
+}
+
+/// Result of one check. A failed probe is still an outcome: a `major_outage`
+/// with the reason in `error_message`.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CheckOutcome {
+    pub status: String,
+    /// Milliseconds, absent when the probe failed
+    pub response_time: Option<i32>,
+    pub status_code: Option<i32>,
+    pub error_message: Option<String>,
+    #[serde(with = "db_core::time_serde")]
+    #[cfg_attr(feature = "openapi", schemars(with = "db_core::time_serde::Timestamp"))]
+    pub checked_at: OffsetDateTime,
+    /// What the probe saw beyond the status, e.g. evaluated rules and their
+    /// sampled values for `prometheus` monitors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+impl CheckOutcome {
+    pub fn into_status_event(self, monitor_id: i32, monitor_type: &str) -> CreateStatusEvent {
+        let mut metadata = json!({
+            "checked_at": self.checked_at.format(&Rfc3339).ok(),
+            "monitor_type": monitor_type,
+        });
+        if let (Some(Value::Object(details)), Some(metadata)) = (self.details, metadata.as_object_mut()) {
+            metadata.extend(details);
+        }
+
+        CreateStatusEvent {
+            monitor_id,
+            status: self.status,
+            response_time: self.response_time,
+            status_code: self.status_code,
+            error_message: self.error_message,
+            metadata: Some(metadata),
+        }
+    }
+}
+
+/// What a probe that reached its target found
+struct Probe {
+    status: String,
+    /// Milliseconds
+    response_time: u64,
+    status_code: Option<i32>,
+    /// Why the status isn't operational, when the probe can tell
+    error_message: Option<String>,
+    details: Option<Value>,
+}
+
+impl Probe {
+    fn new(status: String, response_time: u64, status_code: Option<i32>) -> Self {
+        Self {
+            status,
+            response_time,
+            status_code,
+            error_message: None,
+            details: None,
+        }
+    }
+}
+
+/// Run the probe for the target's monitor type, or `None` when the type has
+/// no probe (see [`SUPPORTED_TYPES`]).
+pub async fn run(target: CheckTarget<'_>) -> Option<CheckOutcome> {
+    let result = match target.monitor_type {
+        "http" => check_http(target).await,
+        "tcp" => check_tcp(target).await,
+        "prometheus" => prometheus::check(target).await,
+        _ => return None,
+    };
+    let checked_at = OffsetDateTime::now_utc();
+
+    Some(match result {
+        Ok(probe) => CheckOutcome {
+            status: probe.status,
+            response_time: Some(probe.response_time as i32),
+            status_code: probe.status_code,
+            error_message: probe.error_message,
+            checked_at,
+            details: probe.details,
+        },
+        Err(error_message) => CheckOutcome {
+            status: "major_outage".to_string(),
+            response_time: None,
+            status_code: None,
+            error_message: Some(error_message),
+            checked_at,
+            details: None,
+        },
+    })
+}
+
+async fn check_http(target: CheckTarget<'_>) -> Result<Probe, String> {
+    let url = target.url.ok_or("No URL configured for HTTP monitor")?;
+
+    debug!("Performing HTTP check for URL: {}", url);
+
+    let client = http_client(&target, url)?;
+
+    let start_time = Instant::now();
+
+    let response = timeout(Duration::from_secs(target.timeout as u64), client.get(url).send())
+        .await
+        .map_err(|_| format!("Request timed out after {} seconds", target.timeout))?
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let response_time = start_time.elapsed().as_millis() as u64;
+    let status_code = response.status().as_u16() as i32;
+
+    debug!("HTTP response: status_code={}, response_time={}ms", status_code, response_time);
+
+    // Determine status based on response
+    let status = if response.status().is_success() {
+        if response_time > 3000 {
+            "degraded".to_string()
+        } else {
+            "operational".to_string()
+        }
+    } else if response.status().is_server_error() {
+        "major_outage".to_string()
+    } else if response.status().is_client_error() {
+        "partial_outage".to_string()
+    } else {
+        "degraded".to_string()
+    };
+
+    Ok(Probe::new(status, response_time, Some(status_code)))
+}
+
+async fn check_tcp(target: CheckTarget<'_>) -> Result<Probe, String> {
+    let url = target.url.ok_or("No URL configured for TCP monitor")?;
+
+    let (host, port) = parse_host_port(url.strip_prefix("tcp://").unwrap_or(url))?;
+
+    let start_time = Instant::now();
+
+    let result = if target.public_only {
+        let addrs = public::lookup(&host, port).await?;
+        timeout(Duration::from_secs(target.timeout as u64), TcpStream::connect(&addrs[..])).await
+    } else {
+        timeout(
+            Duration::from_secs(target.timeout as u64),
+            TcpStream::connect(format!("{}:{}", host, port)),
+        )
+        .await
+    };
+
+    let response_time = start_time.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(Ok(_)) => {
+            let status = if response_time > 1000 {
+                "degraded".to_string()
+            } else {
+                "operational".to_string()
+            };
+            Ok(Probe::new(status, response_time, None))
+        }
+        Ok(Err(e)) => Err(format!("TCP connection failed: {}", e)),
+        Err(_) => Err(format!("Connection timed out after {} seconds", target.timeout)),
+    }
+}
+
+/// An HTTP client for the target, limited to public addresses when it asks
+pub(crate) fn http_client(target: &CheckTarget<'_>, url: &str) -> Result<Client, String> {
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(target.timeout as u64))
+        .user_agent("StatusMonitor/1.0");
+
+    if target.public_only {
+        let url = Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+        public::check_url(&url)?;
+        builder = public::restrict(builder);
+    }
+
+    builder.build().map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+fn parse_host_port(url: &str) -> Result<(String, u16), String> {
+    if let Some(colon_pos) = url.rfind(':') {
+        let host = url[..colon_pos].to_string();
+        let port_str = &url[colon_pos + 1..];
+        let port = port_str.parse::<u16>().map_err(|_| format!("Invalid port: {}", port_str))?;
+        Ok((host, port))
+    } else {
+        Err("No port specified in URL".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tcp(url: &str) -> CheckTarget<'_> {
+        CheckTarget {
+            monitor_type: "tcp",
+            url: Some(url),
+            timeout: 2,
+            metadata: None,
+            public_only: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn tcp_checks_report_reachability() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("tcp://{}", listener.local_addr().unwrap());
+
+        let up = run(tcp(&url)).await.unwrap();
+        drop(listener);
+        let down = run(tcp(&url)).await.unwrap();
+
+        assert_eq!(up.status, "operational");
+        assert!(up.response_time.is_some());
+        assert_eq!(down.status, "major_outage");
+        assert!(down.error_message.unwrap().starts_with("TCP connection failed"));
+
+        let refused = run(CheckTarget { public_only: true, ..tcp(&url) }).await.unwrap();
+        assert_eq!(refused.status, "major_outage");
+        assert_eq!(refused.error_message.unwrap(), "127.0.0.1 is not a public address");
+    }
+
+    #[tokio::test]
+    async fn types_without_a_probe_are_not_checked() {
+        let target = CheckTarget {
+            monitor_type: "custom",
+            url: None,
+            timeout: 2,
+            metadata: None,
+            public_only: false,
+        };
+
+        assert!(run(target).await.is_none());
+        assert_eq!(parse_host_port("db.internal"), Err("No port specified in URL".to_string()));
+    }
+}
@@ -0,0 +1,464 @@
+//! Scrape a Prometheus or OpenMetrics endpoint and evaluate threshold rules
+//! against its samples.
+//!
+//! Rules live in the monitor's `metadata.rules`:
+//!
+//! ```json
+//! { "rules": [
+//!     { "expr": "up{job=\"api\"} < 1" },
+//!     { "expr": "queue_depth{queue!=\"low\"} > 1000", "status": "degraded" }
+//! ] }
+//! ```
+//!
+//! A rule fires when any sample matching its selector satisfies the
+//! comparison, or when no sample matches at all. The check reports the worst
+//! status among firing rules, and `operational` when none fire.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::time::Instant;
+use tracing::debug;
+
+use crate::{http_client, CheckTarget, Probe};
+
+/// Statuses a rule may report, from least to most severe
+pub const RULE_STATUSES: &[&str] = &["degraded", "partial_outage", "major_outage"];
+
+/// Matching samples recorded per rule, to keep status event metadata small
+const MAX_RECORDED_SAMPLES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    /// `metric{label="value",...} <op> <number>`, where `op` is one of
+    /// `<`, `<=`, `>`, `>=`, `==` or `!=`, and labels match with `=` or `!=`
+    pub expr: String,
+    /// Status reported while the rule fires
+    #[serde(default = "default_status")]
+    pub status: String,
+}
+
+fn default_status() -> String {
+    "major_outage".to_string()
+}
+
+/// Read and validate the rules of a monitor from its metadata. Having no
+/// rules is valid: the check then only requires a successful scrape.
+pub fn rules(metadata: Option<&Value>) -> Result<Vec<Rule>, String> {
+    let Some(rules) = metadata.and_then(|metadata| metadata.get("rules")) else {
+        return Ok(Vec::new());
+    };
+    let rules: Vec<Rule> =
+        serde_json::from_value(rules.clone()).map_err(|e| format!("metadata.rules is invalid: {}", e))?;
+
+    for rule in &rules {
+        Expr::parse(&rule.expr).map_err(|e| format!("Rule '{}' is invalid: {}", rule.expr, e))?;
+        if !RULE_STATUSES.contains(&rule.status.as_str()) {
+            return Err(format!(
+                "Rule '{}' has status '{}'; it must be one of: {}",
+                rule.expr,
+                rule.status,
+                RULE_STATUSES.join(", ")
+            ));
+        }
+    }
+
+    Ok(rules)
+}
+
+pub(crate) async fn check(target: CheckTarget<'_>) -> Result<Probe, String> {
+    let url = target.url.ok_or("No URL configured for Prometheus monitor")?;
+    let rules = rules(target.metadata)?;
+
+    debug!("Scraping Prometheus endpoint: {}", url);
+
+    let client = http_client(&target, url)?;
+
+    let start_time = Instant::now();
+
+    let response = client
+        .get(url)
+        .header("Accept", "application/openmetrics-text;q=0.9, text/plain;q=0.8")
+        .send()
+        .await
+        .map_err(|e| format!("Scrape failed: {}", e))?;
+    let status_code = response.status().as_u16() as i32;
+    if !response.status().is_success() {
+        return Err(format!("Scrape failed with HTTP {}", status_code));
+    }
+    let body = response.text().await.map_err(|e| format!("Failed to read scrape: {}", e))?;
+
+    let response_time = start_time.elapsed().as_millis() as u64;
+
+    let samples = parse_samples(&body)?;
+    let mut probe = evaluate(&rules, &samples);
+    probe.response_time = response_time;
+    probe.status_code = Some(status_code);
+    Ok(probe)
+}
+
+/// Apply every rule to the scraped samples
+fn evaluate(rules: &[Rule], samples: &[Sample]) -> Probe {
+    let mut status = "operational";
+    let mut messages = Vec::new();
+    let mut results = Vec::new();
+
+    for rule in rules {
+        // Rules were validated when read
+        let Ok(expr) = Expr::parse(&rule.expr) else { continue };
+
+        let matching: Vec<&Sample> = samples.iter().filter(|sample| expr.selects(sample)).collect();
+        let breaching: Vec<&Sample> = matching.iter().copied().filter(|sample| expr.op.holds(sample.value, expr.threshold)).collect();
+        let firing = matching.is_empty() || !breaching.is_empty();
+
+        if firing {
+            if severity(&rule.status) > severity(status) {
+                status = &rule.status;
+            }
+            messages.push(match breaching.first() {
+                Some(sample) => format!("{} (value {})", rule.expr, sample.value),
+                None => format!("{} (no samples)", rule.expr),
+            });
+        }
+
+        results.push(json!({
+            "expr": rule.expr,
+            "status": rule.status,
+            "firing": firing,
+            "samples": matching.iter().take(MAX_RECORDED_SAMPLES).map(|sample| sample.to_json()).collect::<Vec<_>>(),
+        }));
+    }
+
+    Probe {
+        status: status.to_string(),
+        response_time: 0,
+        status_code: None,
+        error_message: (!messages.is_empty()).then(|| format!("Rules firing: {}", messages.join("; "))),
+        details: Some(json!({ "rules": results })),
+    }
+}
+
+fn severity(status: &str) -> usize {
+    RULE_STATUSES.iter().position(|s| *s == status).map_or(0, |i| i + 1)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Op {
+    fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Op::Lt => value < threshold,
+            Op::Le => value <= threshold,
+            Op::Gt => value > threshold,
+            Op::Ge => value >= threshold,
+            Op::Eq => value == threshold,
+            Op::Ne => value != threshold,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Matcher {
+    name: String,
+    value: String,
+    equal: bool,
+}
+
+/// A parsed rule expression
+#[derive(Debug, PartialEq)]
+struct Expr {
+    metric: String,
+    matchers: Vec<Matcher>,
+    op: Op,
+    threshold: f64,
+}
+
+impl Expr {
+    fn parse(input: &str) -> Result<Self, String> {
+        let mut cursor = Cursor::new(input);
+        let metric = cursor.name()?;
+        let matchers = if cursor.peek() == Some('{') {
+            cursor
+                .labels()?
+                .into_iter()
+                .map(|(name, value, equal)| Matcher { name, value, equal })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        cursor.skip_whitespace();
+        let op = [("<=", Op::Le), (">=", Op::Ge), ("==", Op::Eq), ("!=", Op::Ne), ("<", Op::Lt), (">", Op::Gt)]
+            .into_iter()
+            .find(|(token, _)| cursor.eat(token))
+            .map(|(_, op)| op)
+            .ok_or("expected a comparison: <, <=, >, >=, == or !=")?;
+
+        cursor.skip_whitespace();
+        let threshold = parse_value(cursor.rest().trim()).ok_or("expected a number after the comparison")?;
+
+        Ok(Self { metric, matchers, op, threshold })
+    }
+
+    fn selects(&self, sample: &Sample) -> bool {
+        sample.name == self.metric
+            && self.matchers.iter().all(|matcher| {
+                let value = sample.label(&matcher.name).unwrap_or("");
+                (value == matcher.value) == matcher.equal
+            })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Sample {
+    name: String,
+    labels: Vec<(String, String)>,
+    value: f64,
+}
+
+impl Sample {
+    fn label(&self, name: &str) -> Option<&str> {
+        self.labels.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    fn to_json(&self) -> Value {
+        let labels: Map<String, Value> = self.labels.iter().map(|(n, v)| (n.clone(), Value::from(v.as_str()))).collect();
+        json!({ "labels": labels, "value": self.value })
+    }
+}
+
+/// Parse the samples of a text exposition (Prometheus 0.0.4 or OpenMetrics).
+/// Comments, metadata and timestamps are skipped.
+fn parse_samples(text: &str) -> Result<Vec<Sample>, String> {
+    let mut samples = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = |e: String| format!("Invalid metrics on line {}: {}", number + 1, e);
+        let mut cursor = Cursor::new(line);
+        let name = cursor.name().map_err(invalid)?;
+        let labels = if cursor.peek() == Some('{') {
+            let labels = cursor.labels().map_err(invalid)?;
+            labels.into_iter().map(|(name, value, _)| (name, value)).collect()
+        } else {
+            Vec::new()
+        };
+
+        let value = cursor.rest().split_whitespace().next().unwrap_or("");
+        let value = parse_value(value).ok_or_else(|| invalid(format!("'{}' is not a number", value)))?;
+
+        samples.push(Sample { name, labels, value });
+    }
+
+    Ok(samples)
+}
+
+/// Numbers as written by exporters, including `+Inf`, `-Inf` and `NaN`
+fn parse_value(value: &str) -> Option<f64> {
+    match value {
+        "+Inf" | "Inf" => Some(f64::INFINITY),
+        "-Inf" => Some(f64::NEG_INFINITY),
+        "NaN" => Some(f64::NAN),
+        _ => value.parse().ok(),
+    }
+}
+
+/// Just enough of a tokenizer for metric names and label sets
+struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.pos = self.input.len() - self.rest().trim_start().len();
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| !(c.is_ascii_alphabetic() || c == '_' || c == ':' || (i > 0 && c.is_ascii_digit())))
+            .map_or(rest.len(), |(i, _)| i);
+        if len == 0 {
+            return Err("expected a metric or label name".to_string());
+        }
+        self.pos += len;
+        Ok(rest[..len].to_string())
+    }
+
+    /// `{name="value",...}`, returning whether each label matched with `=`
+    fn labels(&mut self) -> Result<Vec<(String, String, bool)>, String> {
+        let mut labels = Vec::new();
+        self.eat("{");
+
+        loop {
+            self.skip_whitespace();
+            if self.eat("}") {
+                return Ok(labels);
+            }
+
+            let name = self.name()?;
+            self.skip_whitespace();
+            let equal = if self.eat("!=") {
+                false
+            } else if self.eat("=") {
+                true
+            } else {
+                return Err(format!("expected = or != after label {}", name));
+            };
+            self.skip_whitespace();
+            labels.push((name, self.quoted()?, equal));
+
+            self.skip_whitespace();
+            if !self.eat(",") && self.peek() != Some('}') {
+                return Err("expected , or } in label set".to_string());
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String, String> {
+        if !self.eat("\"") {
+            return Err("expected a quoted label value".to_string());
+        }
+
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                _ => value.push(c),
+            }
+        }
+
+        Err("unterminated label value".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRAPE: &str = r#"
+# HELP up Whether the target is up
+# TYPE up gauge
+up{job="api",instance="a:9090"} 1
+up{job="api",instance="b:9090"} 0 1700000000000
+up{job="db"} 1
+queue_depth{queue="high"} 1500
+queue_depth{queue="low",note="say \"hi\""} 9000
+process_start_time_seconds 1.7e9
+# EOF
+"#;
+
+    fn rule(expr: &str, status: &str) -> Rule {
+        Rule { expr: expr.to_string(), status: status.to_string() }
+    }
+
+    #[test]
+    fn parses_expressions_and_expositions() {
+        assert_eq!(
+            Expr::parse(r#"queue_depth{queue!="low", env="prod"} >= 1e3"#).unwrap(),
+            Expr {
+                metric: "queue_depth".to_string(),
+                matchers: vec![
+                    Matcher { name: "queue".to_string(), value: "low".to_string(), equal: false },
+                    Matcher { name: "env".to_string(), value: "prod".to_string(), equal: true },
+                ],
+                op: Op::Ge,
+                threshold: 1000.0,
+            }
+        );
+        assert!(Expr::parse("up").is_err());
+        assert!(Expr::parse(r#"up{job="api} < 1"#).is_err());
+
+        let samples = parse_samples(SCRAPE).unwrap();
+        assert_eq!(samples.len(), 6);
+        assert_eq!(samples[1].value, 0.0);
+        assert_eq!(samples[4].label("note"), Some(r#"say "hi""#));
+        assert!(parse_samples("up{job=\"api\"} yes").is_err());
+    }
+
+    #[test]
+    fn reports_the_worst_firing_rule_with_sampled_values() {
+        let samples = parse_samples(SCRAPE).unwrap();
+
+        let healthy = evaluate(&[rule(r#"up{job="db"} < 1"#, "major_outage")], &samples);
+        assert_eq!(healthy.status, "operational");
+        assert_eq!(healthy.error_message, None);
+
+        let probe = evaluate(
+            &[
+                rule(r#"queue_depth{queue!="low"} > 1000"#, "degraded"),
+                rule(r#"up{job="api"} < 1"#, "partial_outage"),
+                rule(r#"up{job="db"} < 1"#, "major_outage"),
+            ],
+            &samples,
+        );
+        assert_eq!(probe.status, "partial_outage");
+        assert_eq!(
+            probe.error_message.as_deref(),
+            Some(r#"Rules firing: queue_depth{queue!="low"} > 1000 (value 1500); up{job="api"} < 1 (value 0)"#)
+        );
+        let results = &probe.details.unwrap()["rules"];
+        assert_eq!(results[1]["firing"], true);
+        assert_eq!(results[1]["samples"][1]["labels"]["instance"], "b:9090");
+        assert_eq!(results[2]["firing"], false);
+
+        let missing = evaluate(&[rule("http_requests_total > 0", "degraded")], &samples);
+        assert_eq!(missing.status, "degraded");
+        assert_eq!(missing.error_message.as_deref(), Some("Rules firing: http_requests_total > 0 (no samples)"));
+    }
+
+    #[test]
+    fn validates_rules_from_metadata() {
+        let metadata = json!({ "rules": [{ "expr": "up < 1" }] });
+        assert_eq!(rules(Some(&metadata)).unwrap(), [rule("up < 1", "major_outage")]);
+        assert!(rules(None).unwrap().is_empty());
+
+        let bad_status = json!({ "rules": [{ "expr": "up < 1", "status": "operational" }] });
+        assert!(rules(Some(&bad_status)).unwrap_err().contains("must be one of"));
+        let bad_expr = json!({ "rules": [{ "expr": "up ~ 1" }] });
+        assert!(rules(Some(&bad_expr)).unwrap_err().starts_with("Rule 'up ~ 1' is invalid"));
+    }
+}
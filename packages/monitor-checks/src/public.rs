@@ -0,0 +1,130 @@
+//! Probes that may only reach the public internet.
+//!
+//! Dry runs check whatever a user types in from the app's own network, so
+//! they refuse loopback, private, link-local and other non-public addresses.
+//! Hostnames are filtered when they resolve rather than beforehand, so a
+//! name that changes its answer between the two cannot slip through.
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::{Attempt, Policy};
+use reqwest::{ClientBuilder, Url};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+/// Redirects followed before giving up, as reqwest does by default
+const MAX_REDIRECTS: usize = 10;
+
+/// Whether the address is on the public internet
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // Carrier-grade NAT, 100.64.0.0/10
+                || (a == 100 && (64..128).contains(&b))
+                // "This network" and reserved, 0.0.0.0/8 and 240.0.0.0/4
+                || a == 0
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public(IpAddr::V4(ip));
+            }
+            let [first, second, ..] = ip.segments();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // Unique local, fc00::/7
+                || (first & 0xfe00) == 0xfc00
+                // Link-local, fe80::/10
+                || (first & 0xffc0) == 0xfe80
+                // Documentation, 2001:db8::/32
+                || (first == 0x2001 && second == 0x0db8))
+        }
+    }
+}
+
+/// Resolve `host:port` to the public addresses it names
+pub(crate) async fn lookup(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .filter(|addr| is_public(addr.ip()))
+        .collect();
+
+    if addrs.is_empty() {
+        return Err(format!("{} is not a public address", host));
+    }
+    Ok(addrs)
+}
+
+/// Refuse a URL whose host is a non-public IP address. Hostnames are left to
+/// the resolver.
+pub(crate) fn check_url(url: &Url) -> Result<(), String> {
+    let host = url.host_str().unwrap_or_default();
+    let Ok(ip) = host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() else {
+        return Ok(());
+    };
+    if is_public(ip) {
+        Ok(())
+    } else {
+        Err(format!("{} is not a public address", ip))
+    }
+}
+
+/// Limit an HTTP client to public addresses, including where it is redirected
+pub(crate) fn restrict(builder: ClientBuilder) -> ClientBuilder {
+    builder
+        .dns_resolver(Arc::new(PublicResolver))
+        .redirect(Policy::custom(follow_public))
+}
+
+fn follow_public(attempt: Attempt) -> reqwest::redirect::Action {
+    if attempt.previous().len() >= MAX_REDIRECTS {
+        return attempt.error("too many redirects");
+    }
+    match check_url(attempt.url()) {
+        Ok(()) => attempt.follow(),
+        Err(e) => attempt.error(e),
+    }
+}
+
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = lookup(name.as_str(), 0).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn internal_addresses_are_not_public() {
+        for ip in ["127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1", "0.0.0.0", "::1", "fd00::1", "fe80::1", "::ffff:127.0.0.1"] {
+            assert!(!is_public(ip.parse().unwrap()), "{} should not be public", ip);
+        }
+        for ip in ["1.1.1.1", "8.8.8.8", "2606:4700:4700::1111"] {
+            assert!(is_public(ip.parse().unwrap()), "{} should be public", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn names_resolving_to_internal_addresses_are_refused() {
+        assert!(lookup("localhost", 80).await.is_err());
+        assert!(check_url(&"http://169.254.169.254/latest/meta-data".parse().unwrap()).is_err());
+        assert!(check_url(&"http://[::1]:8080/".parse().unwrap()).is_err());
+        assert!(check_url(&"https://example.com/".parse().unwrap()).is_ok());
+    }
+}
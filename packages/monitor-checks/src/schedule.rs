@@ -0,0 +1,32 @@
+//! Naming shared by everything that schedules checks. Each active monitor
+//! has exactly one pending `check_monitor` job under [`job_key`]; the job
+//! re-adds itself under the same key after every run.
+
+/// Task identifier of the check job
+pub const TASK: &str = "check_monitor";
+
+/// Status event metadata naming the check job that recorded the event, so
+/// whoever queued the job can tell its result from other checks
+pub const EVENT_JOB_ID: &str = "job_id";
+
+/// Key of a monitor's pending check job
+pub fn job_key(monitor_id: i32) -> String {
+    format!("monitor_{}_next", monitor_id)
+}
+
+/// Monitor a check job key belongs to, `None` for any other key
+pub fn monitor_id(job_key: &str) -> Option<i32> {
+    job_key.strip_prefix("monitor_")?.strip_suffix("_next")?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_keys_round_trip() {
+        assert_eq!(monitor_id(&job_key(42)), Some(42));
+        assert_eq!(monitor_id("monitor_42_initial"), None);
+        assert_eq!(monitor_id("reconcile_schedules"), None);
+    }
+}
@@ -0,0 +1,180 @@
+//! Shields-style SVG badges showing a monitor's or group's status or
+//! uptime, for READMEs and wikis.
+
+use crate::feed::xml;
+
+/// A label on the left and a message on the right, each on its own color
+#[derive(Debug, Clone)]
+pub struct Badge<'a> {
+    pub label: &'a str,
+    pub message: &'a str,
+    /// `#rgb` or `#rrggbb`, as returned by [`color`]
+    pub label_color: &'a str,
+    pub color: &'a str,
+}
+
+/// The label side's color unless one is chosen
+pub const LABEL_COLOR: &str = "#555";
+
+/// Shields' named colors
+const NAMED_COLORS: &[(&str, &str)] = &[
+    ("brightgreen", "#4c1"),
+    ("green", "#97ca00"),
+    ("yellowgreen", "#a4a61d"),
+    ("yellow", "#dfb317"),
+    ("orange", "#fe7d37"),
+    ("red", "#e05d44"),
+    ("blue", "#007ec6"),
+    ("lightgrey", "#9f9f9f"),
+    ("grey", "#555"),
+    ("gray", "#555"),
+    ("success", "#4c1"),
+    ("important", "#fe7d37"),
+    ("critical", "#e05d44"),
+    ("informational", "#007ec6"),
+    ("inactive", "#9f9f9f"),
+];
+
+/// A named color, or a hex color with or without its `#`. `None` for
+/// anything else, so nothing but a color ends up in the SVG.
+pub fn color(name: &str) -> Option<String> {
+    if let Some((_, hex)) = NAMED_COLORS.iter().find(|(named, _)| named.eq_ignore_ascii_case(name)) {
+        return Some(hex.to_string());
+    }
+
+    let hex = name.strip_prefix('#').unwrap_or(name);
+    let valid = matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    valid.then(|| format!("#{}", hex.to_ascii_lowercase()))
+}
+
+/// How a monitor or group status reads on a badge
+pub fn status_message(status: &str) -> &'static str {
+    match status {
+        "operational" => "operational",
+        "degraded" => "degraded",
+        "partial_outage" => "partial outage",
+        "major_outage" => "major outage",
+        "maintenance" => "maintenance",
+        _ => "unknown",
+    }
+}
+
+pub fn status_color(status: &str) -> &'static str {
+    match status {
+        "operational" => "#4c1",
+        "degraded" => "#dfb317",
+        "partial_outage" => "#fe7d37",
+        "major_outage" => "#e05d44",
+        "maintenance" => "#007ec6",
+        _ => "#9f9f9f",
+    }
+}
+
+/// Uptime with as many decimals as it takes to tell 99.9% from 100%
+pub fn uptime_message(percentage: f64) -> String {
+    if percentage >= 100.0 {
+        "100%".to_string()
+    } else if percentage >= 99.0 {
+        format!("{:.2}%", (percentage * 100.0).floor() / 100.0)
+    } else {
+        format!("{:.1}%", (percentage * 10.0).floor() / 10.0)
+    }
+}
+
+pub fn uptime_color(percentage: f64) -> &'static str {
+    match percentage {
+        p if p >= 99.9 => "#4c1",
+        p if p >= 99.0 => "#97ca00",
+        p if p >= 95.0 => "#dfb317",
+        p if p >= 90.0 => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+pub fn render(badge: &Badge) -> String {
+    let label_width = text_width(badge.label) + 10;
+    let message_width = text_width(badge.message) + 10;
+    let width = label_width + message_width;
+    let label_x = label_width as f64 / 2.0;
+    let message_x = label_width as f64 + message_width as f64 / 2.0;
+    let label = xml(badge.label);
+    let message = xml(badge.message);
+
+    format!(
+        concat!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">"##,
+            r##"<title>{label}: {message}</title>"##,
+            r##"<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>"##,
+            r##"<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>"##,
+            r##"<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="{label_color}"/>"##,
+            r##"<rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>"##,
+            r##"<rect width="{width}" height="20" fill="url(#s)"/></g>"##,
+            r##"<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">"##,
+            r##"<text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{label_x}" y="14">{label}</text>"##,
+            r##"<text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text><text x="{message_x}" y="14">{message}</text>"##,
+            r##"</g></svg>"##,
+        ),
+        width = width,
+        label_width = label_width,
+        message_width = message_width,
+        label_x = label_x,
+        message_x = message_x,
+        label = label,
+        message = message,
+        label_color = badge.label_color,
+        color = badge.color,
+    )
+}
+
+/// Roughly how wide `text` is in 11px Verdana. Close enough that the text
+/// never overflows its side, without shipping the font's metrics.
+fn text_width(text: &str) -> u32 {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'l' | 'j' | '.' | ',' | ':' | ';' | '!' | '\'' | '|' => 4,
+            ' ' | 'f' | 'r' | 't' | 'I' | '(' | ')' | '[' | ']' | '-' => 5,
+            'm' | 'w' | 'M' | 'W' | '%' | '@' => 11,
+            c if c.is_ascii_uppercase() || c.is_ascii_digit() => 8,
+            c if c.is_ascii() => 7,
+            _ => 9,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_colors_are_accepted() {
+        assert_eq!(color("brightgreen").as_deref(), Some("#4c1"));
+        assert_eq!(color("#ABCDEF").as_deref(), Some("#abcdef"));
+        assert_eq!(color("f80").as_deref(), Some("#f80"));
+        assert_eq!(color("\"/><script>"), None);
+        assert_eq!(color("12345"), None);
+    }
+
+    #[test]
+    fn uptime_keeps_the_decimals_that_matter() {
+        assert_eq!(uptime_message(100.0), "100%");
+        assert_eq!(uptime_message(99.999), "99.99%");
+        assert_eq!(uptime_message(97.46), "97.4%");
+        assert_eq!(uptime_color(99.95), "#4c1");
+        assert_eq!(uptime_color(42.0), "#e05d44");
+    }
+
+    #[test]
+    fn badges_escape_their_text() {
+        let svg = render(&Badge {
+            label: "R&D <api>",
+            message: status_message("partial_outage"),
+            label_color: LABEL_COLOR,
+            color: status_color("partial_outage"),
+        });
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<title>R&amp;D &lt;api&gt;: partial outage</title>"));
+        assert!(svg.contains("fill=\"#fe7d37\""));
+        assert!(!svg.contains("<api>"));
+    }
+}
@@ -0,0 +1,315 @@
+//! Static export of the status page, so it can be hosted somewhere that
+//! stays up when this deployment does not.
+//!
+//! A bundle is `index.html`, rendered by [`crate::html`], and `status.json`,
+//! the [`StatusPageData`] it was rendered from. It is written to a directory
+//! or uploaded to an S3-compatible bucket, as set in [`ExportConfig`].
+
+use crate::{StatusPageData, StatusPageFilter};
+use config_core::ExportConfig;
+use db_core::error::DbError;
+use object_store::aws::AmazonS3Builder;
+use object_store::local::LocalFileSystem;
+use object_store::path::Path;
+use object_store::{Attribute, Attributes, ObjectStore, PutOptions, PutPayload};
+use sqlx::PgPool;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{info, instrument};
+
+pub const HTML_FILE: &str = "index.html";
+pub const JSON_FILE: &str = "status.json";
+
+/// Short, so a CDN in front of the bucket passes changes on quickly
+const CACHE_CONTROL: &str = "public, max-age=60";
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error(transparent)]
+    Db(#[from] DbError),
+
+    #[error("Failed to create {path}: {source}")]
+    CreateDir { path: String, source: std::io::Error },
+
+    #[error("Failed to write {path}: {source}")]
+    Write { path: String, source: object_store::Error },
+
+    #[error("Invalid export destination: {0}")]
+    Destination(#[from] object_store::Error),
+}
+
+/// Writes bundles to the configured directory or bucket
+#[derive(Debug)]
+pub struct Exporter {
+    store: Arc<dyn ObjectStore>,
+    prefix: String,
+    /// Buckets take a content type and caching headers; directories cannot
+    with_headers: bool,
+    /// Where bundles go, for logs
+    destination: String,
+}
+
+impl Exporter {
+    /// The exporter for `config`, or `None` when export is not configured
+    pub fn from_config(config: &ExportConfig) -> Result<Option<Self>, ExportError> {
+        if let Some(dir) = &config.dir {
+            std::fs::create_dir_all(dir).map_err(|source| ExportError::CreateDir {
+                path: dir.display().to_string(),
+                source,
+            })?;
+
+            // Files are written to a temporary name and renamed into place, so
+            // a web server reading the directory never sees half a file
+            return Ok(Some(Self {
+                store: Arc::new(LocalFileSystem::new_with_prefix(dir)?),
+                prefix: config.prefix.clone(),
+                with_headers: false,
+                destination: format!("{}/{}", dir.display(), config.prefix),
+            }));
+        }
+
+        let Some(bucket) = &config.bucket else {
+            return Ok(None);
+        };
+
+        let mut builder = AmazonS3Builder::new()
+            .with_bucket_name(bucket)
+            .with_region(&config.region);
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.with_endpoint(endpoint).with_allow_http(endpoint.starts_with("http://"));
+        }
+        if let (Some(key_id), Some(secret)) = (&config.access_key_id, &config.secret_access_key) {
+            builder = builder.with_access_key_id(key_id).with_secret_access_key(secret);
+        }
+
+        Ok(Some(Self {
+            store: Arc::new(builder.build()?),
+            prefix: config.prefix.clone(),
+            with_headers: true,
+            destination: format!("s3://{}/{}", bucket, config.prefix),
+        }))
+    }
+
+    /// Load the current status page and export it. The copy shows what the
+    /// default page does: every active monitor but the private ones, without
+    /// internal details.
+    pub async fn export_current(&self, pool: &PgPool) -> Result<(), ExportError> {
+        let mut data = crate::load(pool, &StatusPageFilter::default()).await?;
+        data.hide_internal_details();
+        self.export(&data).await
+    }
+
+    /// Write the bundle for `data`. The page goes last, so it never links
+    /// readers to data older than itself.
+    #[instrument(name = "Exporter::export", skip_all, fields(destination = %self.destination))]
+    pub async fn export(&self, data: &StatusPageData) -> Result<(), ExportError> {
+        let json = serde_json::to_vec_pretty(data).expect("status page data serializes to JSON");
+        self.put(JSON_FILE, "application/json", json).await?;
+        self.put(HTML_FILE, "text/html; charset=utf-8", crate::html::render(data).into_bytes())
+            .await?;
+
+        info!("Exported status page to {}", self.destination);
+        Ok(())
+    }
+
+    async fn put(&self, file: &str, content_type: &'static str, body: Vec<u8>) -> Result<(), ExportError> {
+        let path = Path::from(format!("{}{}", self.prefix, file));
+
+        let mut attributes = Attributes::new();
+        if self.with_headers {
+            attributes.insert(Attribute::ContentType, content_type.into());
+            attributes.insert(Attribute::CacheControl, CACHE_CONTROL.into());
+        }
+        let options = PutOptions {
+            attributes,
+            ..Default::default()
+        };
+
+        self.store
+            .put_opts(&path, PutPayload::from(body), options)
+            .await
+            .map_err(|source| ExportError::Write {
+                path: path.to_string(),
+                source,
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MonitorWithStatus;
+    use axum::body::Bytes;
+    use axum::extract::{Path as UrlPath, State};
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::put;
+    use axum::Router;
+    use db_core::models::incident::Incident;
+    use db_core::models::maintenance_window::MaintenanceWindow;
+    use db_core::models::monitor::Monitor;
+    use db_core::models::status_event::StatusDailyStat;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use time::{Duration, OffsetDateTime};
+    use tokio::net::TcpListener;
+
+    fn status_page() -> StatusPageData {
+        let now = OffsetDateTime::now_utc();
+        let monitor = Monitor {
+            id: 1,
+            name: "api".to_string(),
+            display_name: "Public <API>".to_string(),
+            description: None,
+            url: Some("https://api.example.com/health".to_string()),
+            monitor_type: "http".to_string(),
+            check_interval: 60,
+            timeout: 10,
+            is_active: true,
+            metadata: None,
+            service_group_id: None,
+            weight: 1,
+            created_at: now,
+            updated_at: now,
+        };
+        let yesterday = StatusDailyStat {
+            bucket: Some(now - Duration::days(1)),
+            monitor_id: Some(1),
+            check_count: Some(1440),
+            operational_count: Some(1000),
+            incident_count: Some(440),
+            uptime_percentage: Some(69.4),
+            avg_response_time: Some(120),
+            p95_response_time: Some(300),
+        };
+
+        StatusPageData {
+            all_operational: false,
+            last_updated: now,
+            monitors: vec![MonitorWithStatus {
+                monitor,
+                current_status: "partial_outage".to_string(),
+                last_check_time: Some(now),
+                uptime_percentage: 99.12,
+                daily_stats: vec![yesterday],
+            }],
+            groups: vec![],
+            incidents: vec![Incident {
+                id: 7,
+                title: "Elevated API errors".to_string(),
+                message: "Some requests are failing.".to_string(),
+                severity: "critical".to_string(),
+                affected_monitors: vec![1],
+                created_at: now,
+                updated_at: now,
+                started_at: now - Duration::hours(1),
+                resolved_at: None,
+                is_resolved: false,
+                metadata: None,
+            }],
+            maintenance: vec![MaintenanceWindow {
+                id: 3,
+                title: "Database upgrade".to_string(),
+                description: Some("Writes pause for a few minutes.".to_string()),
+                affected_monitors: vec![1],
+                starts_at: now + Duration::days(2),
+                ends_at: now + Duration::days(2) + Duration::hours(1),
+                created_at: now,
+                updated_at: now,
+            }],
+        }
+    }
+
+    #[derive(Debug)]
+    struct Upload {
+        content_type: Option<String>,
+        signed: bool,
+        body: Bytes,
+    }
+
+    type Uploads = Arc<Mutex<HashMap<String, Upload>>>;
+
+    /// Stands in for MinIO: accepts path-style PUTs and keeps what it was sent
+    async fn object_store() -> (String, Uploads) {
+        let uploads = Uploads::default();
+        let app = Router::new()
+            .route(
+                "/{*key}",
+                put(|State(uploads): State<Uploads>, UrlPath(key): UrlPath<String>, headers: HeaderMap, body: Bytes| async move {
+                    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+                    let upload = Upload {
+                        content_type: header("content-type"),
+                        signed: header("authorization").is_some_and(|auth| auth.starts_with("AWS4-HMAC-SHA256")),
+                        body,
+                    };
+                    uploads.lock().unwrap().insert(key, upload);
+                    (StatusCode::OK, [("etag", "\"0123456789abcdef\"")])
+                }),
+            )
+            .with_state(uploads.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (endpoint, uploads)
+    }
+
+    #[test]
+    fn the_page_is_self_contained() {
+        let html = crate::html::render(&status_page());
+
+        assert!(html.contains("Public &lt;API&gt;") && html.contains("Partial Outage"), "{}", html);
+        assert!(html.contains("Elevated API errors") && html.contains("Database upgrade"));
+        assert_eq!(html.matches(r#"class="bar "#).count(), crate::HISTORY_DAYS as usize);
+        assert_eq!(html.matches(r#"class="bar partial_outage""#).count(), 1);
+        assert!(!html.contains("<script") && !html.contains("<link") && !html.contains("src="));
+    }
+
+    #[tokio::test]
+    async fn bundles_are_written_to_a_directory() {
+        let dir = std::env::temp_dir().join(format!("rustgenie-export-{}", std::process::id()));
+        let config = ExportConfig {
+            dir: Some(dir.clone()),
+            prefix: "status/".to_string(),
+            ..ExportConfig::default()
+        };
+
+        let exporter = Exporter::from_config(&config).unwrap().unwrap();
+        exporter.export(&status_page()).await.unwrap();
+
+        let html = std::fs::read_to_string(dir.join("status/index.html")).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&std::fs::read(dir.join("status/status.json")).unwrap()).unwrap();
+        assert!(html.contains("Elevated API errors"));
+        assert_eq!(json["maintenance"][0]["title"], "Database upgrade");
+
+        std::fs::remove_dir_all(dir).unwrap();
+        assert!(Exporter::from_config(&ExportConfig::default()).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn bundles_are_uploaded_to_s3_compatible_storage() {
+        let (endpoint, uploads) = object_store().await;
+        let config = ExportConfig {
+            bucket: Some("status-page".to_string()),
+            endpoint: Some(endpoint),
+            access_key_id: Some("minio".to_string()),
+            secret_access_key: Some("minio-secret".to_string()),
+            prefix: "public/".to_string(),
+            ..ExportConfig::default()
+        };
+
+        let exporter = Exporter::from_config(&config).unwrap().unwrap();
+        exporter.export(&status_page()).await.unwrap();
+
+        let uploads = uploads.lock().unwrap();
+        let html = &uploads["status-page/public/index.html"];
+        let json = &uploads["status-page/public/status.json"];
+        assert_eq!(html.content_type.as_deref(), Some("text/html; charset=utf-8"));
+        assert_eq!(json.content_type.as_deref(), Some("application/json"));
+        assert!(html.signed && json.signed);
+        assert!(String::from_utf8_lossy(&html.body).contains("Database upgrade"));
+
+        let data: serde_json::Value = serde_json::from_slice(&json.body).unwrap();
+        assert_eq!(data["incidents"][0]["id"], 7);
+    }
+}
@@ -0,0 +1,272 @@
+//! A status page's incidents as Atom and RSS feeds, and its maintenance as
+//! an iCalendar feed, for feed readers and calendar apps.
+
+use crate::statuspage::PageInfo;
+use crate::{incident_status, incident_updates, StatusPageData};
+use db_core::models::incident::Incident;
+use time::format_description::well_known::{Rfc2822, Rfc3339};
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+/// Atom feed of `incidents`, newest first
+pub fn atom(page: &PageInfo, data: &StatusPageData, incidents: &[Incident]) -> String {
+    let updated = incidents.iter().map(|incident| incident.updated_at).max().unwrap_or(page.updated_at);
+
+    let mut feed = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    feed.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    feed.push_str(&format!("  <id>{}</id>\n", xml(&page.url)));
+    feed.push_str(&format!("  <title>{} incidents</title>\n", xml(&page.name)));
+    feed.push_str(&format!("  <link href=\"{}\"/>\n", xml(&page.url)));
+    feed.push_str(&format!("  <link rel=\"self\" href=\"{}/feed.atom\"/>\n", xml(&page.url)));
+    feed.push_str(&format!("  <updated>{}</updated>\n", rfc3339(updated)));
+
+    for incident in incidents {
+        feed.push_str("  <entry>\n");
+        feed.push_str(&format!("    <id>{}#incident-{}</id>\n", xml(&page.url), incident.id));
+        feed.push_str(&format!("    <title>{}</title>\n", xml(&title(incident))));
+        feed.push_str(&format!("    <link href=\"{}\"/>\n", xml(&page.url)));
+        feed.push_str(&format!("    <published>{}</published>\n", rfc3339(incident.started_at)));
+        feed.push_str(&format!("    <updated>{}</updated>\n", rfc3339(incident.updated_at)));
+        feed.push_str(&format!("    <content type=\"html\">{}</content>\n", xml(&content(data, incident))));
+        feed.push_str("  </entry>\n");
+    }
+
+    feed.push_str("</feed>\n");
+    feed
+}
+
+/// RSS 2.0 feed of `incidents`, newest first
+pub fn rss(page: &PageInfo, data: &StatusPageData, incidents: &[Incident]) -> String {
+    let updated = incidents.iter().map(|incident| incident.updated_at).max().unwrap_or(page.updated_at);
+
+    let mut feed = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    feed.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n");
+    feed.push_str("  <channel>\n");
+    feed.push_str(&format!("    <title>{} incidents</title>\n", xml(&page.name)));
+    feed.push_str(&format!("    <link>{}</link>\n", xml(&page.url)));
+    feed.push_str(&format!("    <description>Incidents reported on {}</description>\n", xml(&page.name)));
+    feed.push_str(&format!(
+        "    <atom:link rel=\"self\" type=\"application/rss+xml\" href=\"{}/feed.rss\"/>\n",
+        xml(&page.url)
+    ));
+    feed.push_str(&format!("    <lastBuildDate>{}</lastBuildDate>\n", rfc2822(updated)));
+
+    for incident in incidents {
+        feed.push_str("    <item>\n");
+        feed.push_str(&format!("      <title>{}</title>\n", xml(&title(incident))));
+        feed.push_str(&format!("      <link>{}</link>\n", xml(&page.url)));
+        feed.push_str(&format!(
+            "      <guid isPermaLink=\"false\">{}#incident-{}</guid>\n",
+            xml(&page.url),
+            incident.id
+        ));
+        feed.push_str(&format!("      <pubDate>{}</pubDate>\n", rfc2822(incident.started_at)));
+        feed.push_str(&format!("      <description>{}</description>\n", xml(&content(data, incident))));
+        feed.push_str("    </item>\n");
+    }
+
+    feed.push_str("  </channel>\n");
+    feed.push_str("</rss>\n");
+    feed
+}
+
+/// iCalendar feed of the page's maintenance windows. Each window keeps its
+/// UID, so calendar apps move an event when its window is rescheduled.
+pub fn calendar(page: &PageInfo, data: &StatusPageData) -> String {
+    let host = page.url.split("://").nth(1).and_then(|rest| rest.split('/').next()).unwrap_or("localhost");
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//RustGenie//Status page//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{} maintenance", ics_text(&page.name)),
+    ];
+    for window in &data.maintenance {
+        let mut description = window.description.clone().unwrap_or_default();
+        let affected = affected_names(data, &window.affected_monitors);
+        if !affected.is_empty() {
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            description.push_str(&format!("Affects: {}", affected.join(", ")));
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:maintenance-{}@{}", window.id, host));
+        lines.push(format!("DTSTAMP:{}", ics_time(window.updated_at)));
+        lines.push(format!("LAST-MODIFIED:{}", ics_time(window.updated_at)));
+        lines.push(format!("DTSTART:{}", ics_time(window.starts_at)));
+        lines.push(format!("DTEND:{}", ics_time(window.ends_at)));
+        lines.push(format!("SUMMARY:{}", ics_text(&window.title)));
+        if !description.is_empty() {
+            lines.push(format!("DESCRIPTION:{}", ics_text(&description)));
+        }
+        lines.push(format!("URL:{}", page.url));
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+fn title(incident: &Incident) -> String {
+    let status = match incident_status(incident) {
+        "resolved" => "Resolved",
+        "identified" => "Identified",
+        _ => "Investigating",
+    };
+    format!("{} ({})", incident.title, status)
+}
+
+/// The entry's HTML: every update, newest first, then what it affects
+fn content(data: &StatusPageData, incident: &Incident) -> String {
+    let mut html = String::new();
+    for update in incident_updates(incident) {
+        html.push_str(&format!(
+            "<p><strong>{}</strong> ({}) - {}</p>",
+            update.status,
+            rfc3339(update.at),
+            xml(&update.body)
+        ));
+    }
+
+    let affected = affected_names(data, &incident.affected_monitors);
+    if !affected.is_empty() {
+        html.push_str(&format!("<p>Affects: {}</p>", xml(&affected.join(", "))));
+    }
+    html
+}
+
+/// Display names of the monitors on the page among `monitor_ids`
+fn affected_names<'a>(data: &'a StatusPageData, monitor_ids: &[i32]) -> Vec<&'a str> {
+    data.monitors
+        .iter()
+        .filter(|entry| monitor_ids.contains(&entry.monitor.id))
+        .map(|entry| entry.monitor.display_name.as_str())
+        .collect()
+}
+
+pub(crate) fn xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn rfc3339(time: OffsetDateTime) -> String {
+    time.format(&Rfc3339).unwrap_or_default()
+}
+
+fn rfc2822(time: OffsetDateTime) -> String {
+    time.format(&Rfc2822).unwrap_or_default()
+}
+
+/// UTC, as `20250704T120000Z`
+fn ics_time(time: OffsetDateTime) -> String {
+    time.to_offset(time::UtcOffset::UTC)
+        .format(format_description!("[year][month][day]T[hour][minute][second]Z"))
+        .unwrap_or_default()
+}
+
+/// Escape a TEXT value (RFC 5545 section 3.3.11)
+fn ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets, without splitting a character, and
+/// end it with CRLF (RFC 5545 section 3.1)
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db_core::models::maintenance_window::MaintenanceWindow;
+    use time::Duration;
+
+    const NOW: OffsetDateTime = OffsetDateTime::UNIX_EPOCH;
+
+    fn data(maintenance: Vec<MaintenanceWindow>) -> StatusPageData {
+        StatusPageData {
+            all_operational: true,
+            last_updated: NOW,
+            monitors: Vec::new(),
+            groups: Vec::new(),
+            incidents: Vec::new(),
+            maintenance,
+        }
+    }
+
+    fn page() -> PageInfo {
+        PageInfo::new("status", "Acme & Co", "https://status.example.com/status", NOW)
+    }
+
+    #[test]
+    fn feeds_escape_incident_text() {
+        let incident = Incident {
+            id: 9,
+            title: "Errors on <checkout>".to_string(),
+            message: "Payments fail & retry".to_string(),
+            severity: "critical".to_string(),
+            affected_monitors: Vec::new(),
+            created_at: NOW,
+            updated_at: NOW,
+            started_at: NOW,
+            resolved_at: Some(NOW + Duration::hours(1)),
+            is_resolved: true,
+            metadata: None,
+        };
+
+        let atom = atom(&page(), &data(Vec::new()), std::slice::from_ref(&incident));
+        assert!(atom.contains("<title>Acme &amp; Co incidents</title>"));
+        assert!(atom.contains("<title>Errors on &lt;checkout&gt; (Resolved)</title>"));
+        assert!(atom.contains("<id>https://status.example.com/status#incident-9</id>"));
+        assert!(atom.contains("Payments fail &amp;amp; retry"));
+
+        let rss = rss(&page(), &data(Vec::new()), &[incident]);
+        assert!(rss.contains("<pubDate>Thu, 01 Jan 1970 00:00:00 +0000</pubDate>"));
+        assert!(rss.contains("href=\"https://status.example.com/status/feed.rss\""));
+    }
+
+    #[test]
+    fn calendar_lists_maintenance_as_events() {
+        let window = MaintenanceWindow {
+            id: 4,
+            title: "Database upgrade; read-only".to_string(),
+            description: Some("Writes are paused, reads continue. ".repeat(3)),
+            affected_monitors: Vec::new(),
+            starts_at: NOW + Duration::hours(2),
+            ends_at: NOW + Duration::hours(3),
+            created_at: NOW,
+            updated_at: NOW,
+        };
+
+        let calendar = calendar(&page(), &data(vec![window]));
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.contains("UID:maintenance-4@status.example.com\r\n"));
+        assert!(calendar.contains("DTSTART:19700101T020000Z\r\n"));
+        assert!(calendar.contains("SUMMARY:Database upgrade\\; read-only\r\n"));
+        assert!(calendar.lines().all(|line| line.len() <= 76));
+        assert!(calendar.replace("\r\n ", "").contains("DESCRIPTION:Writes are paused\\, reads continue. Writes"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    }
+}
@@ -0,0 +1,232 @@
+//! The status page as a single HTML document with its styles inline and no
+//! scripts, so it can be served from any static host or bucket.
+
+use crate::{status_label, GroupWithStatus, MonitorWithStatus, StatusPageData, HISTORY_DAYS};
+use db_core::models::status_event::StatusDailyStat;
+use maud::{html, Markup, PreEscaped, DOCTYPE};
+use std::collections::HashMap;
+use time::{Date, Duration, OffsetDateTime, UtcOffset};
+
+/// Browsers reload the copy this often, in seconds, to pick up new exports
+const REFRESH_SECS: u32 = 60;
+
+const STYLES: &str = r#"
+* { box-sizing: border-box; }
+body { margin: 0; background: #f8fafc; color: #0f172a; font: 15px/1.5 system-ui, -apple-system, "Segoe UI", sans-serif; }
+main { max-width: 56rem; margin: 0 auto; padding: 2rem 1rem; }
+h1 { font-size: 1.75rem; margin: 0 0 1rem; }
+h2 { font-size: 1.125rem; margin: 2rem 0 .75rem; }
+h3 { font-size: 1.0625rem; margin: 0; }
+h4 { font-size: 1rem; margin: 0; }
+p { margin: .25rem 0; }
+.banner { padding: 1rem 1.25rem; border-radius: .5rem; color: #fff; font-weight: 600; }
+.banner.operational { background: #16a34a; }
+.banner.disrupted { background: #dc2626; }
+.card { background: #fff; border: 1px solid #e2e8f0; border-radius: .5rem; padding: 1rem 1.25rem; margin-bottom: .75rem; }
+.incident { border-left: 4px solid #3b82f6; }
+.incident.warning { border-left-color: #f59e0b; }
+.incident.critical { border-left-color: #dc2626; }
+.window { border-left: 4px solid #6366f1; }
+.meta { color: #64748b; font-size: .875rem; }
+.row { display: flex; justify-content: space-between; gap: 1rem; align-items: baseline; }
+.status { font-size: .875rem; font-weight: 600; }
+.group { margin-bottom: 1.5rem; }
+.group .group { margin: .75rem 0 0 1rem; }
+.group > .row { margin-bottom: .5rem; }
+.bars { display: flex; gap: 2px; height: 2rem; margin: .75rem 0 .25rem; }
+.bar { flex: 1; border-radius: 2px; }
+.operational { color: #16a34a; }
+.degraded { color: #ca8a04; }
+.partial_outage { color: #ea580c; }
+.major_outage { color: #dc2626; }
+.maintenance { color: #6366f1; }
+.unknown { color: #94a3b8; }
+.bar.operational { background: #22c55e; }
+.bar.degraded { background: #eab308; }
+.bar.partial_outage { background: #f97316; }
+.bar.major_outage { background: #ef4444; }
+.bar.unknown { background: #cbd5e1; }
+footer { margin-top: 2rem; color: #64748b; font-size: .875rem; }
+"#;
+
+/// One day of a monitor's uptime bar
+struct Day {
+    date: Date,
+    status: &'static str,
+    tooltip: String,
+}
+
+pub fn render(data: &StatusPageData) -> String {
+    let now = data.last_updated;
+    let monitors: HashMap<i32, &MonitorWithStatus> = data.monitors.iter().map(|m| (m.monitor.id, m)).collect();
+    let mut grouped = Vec::new();
+    collect_monitor_ids(&data.groups, &mut grouped);
+    let ungrouped: Vec<&MonitorWithStatus> =
+        data.monitors.iter().filter(|m| !grouped.contains(&m.monitor.id)).collect();
+    let (banner, headline) = if data.all_operational {
+        ("operational", "All systems operational")
+    } else {
+        ("disrupted", "Some systems are experiencing problems")
+    };
+
+    html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                meta name="viewport" content="width=device-width, initial-scale=1";
+                meta http-equiv="refresh" content=(REFRESH_SECS);
+                title { "System status" }
+                style { (PreEscaped(STYLES)) }
+            }
+            body {
+                main {
+                    h1 { "System status" }
+                    div.banner.(banner) { (headline) }
+
+                    @if !data.incidents.is_empty() {
+                        h2 { "Active incidents" }
+                        @for incident in &data.incidents {
+                            div.card.incident.(incident.severity) {
+                                h3 { (incident.title) }
+                                p { (incident.message) }
+                                p.meta { "Since " (timestamp(incident.started_at)) }
+                            }
+                        }
+                    }
+
+                    @if !data.maintenance.is_empty() {
+                        h2 { "Scheduled maintenance" }
+                        @for window in &data.maintenance {
+                            div.card.window {
+                                div.row {
+                                    h3 { (window.title) }
+                                    @if window.is_in_progress(now) {
+                                        span.status.maintenance { "In progress" }
+                                    }
+                                }
+                                @if let Some(description) = &window.description {
+                                    p { (description) }
+                                }
+                                p.meta { (timestamp(window.starts_at)) " to " (timestamp(window.ends_at)) }
+                            }
+                        }
+                    }
+
+                    h2 { "Services" }
+                    @for group in &data.groups {
+                        (group_section(group, &monitors, now))
+                    }
+                    @if !ungrouped.is_empty() {
+                        @if !data.groups.is_empty() {
+                            h3 { "Other services" }
+                        }
+                        @for monitor in &ungrouped {
+                            (monitor_card(monitor, now))
+                        }
+                    }
+
+                    footer {
+                        "Last updated " (timestamp(now)) ". This is a static copy of the status page, "
+                        "refreshed whenever something changes."
+                    }
+                }
+            }
+        }
+    }
+    .into_string()
+}
+
+fn group_section(group: &GroupWithStatus, monitors: &HashMap<i32, &MonitorWithStatus>, now: OffsetDateTime) -> Markup {
+    let status = group.overall_status.as_str();
+
+    html! {
+        section.group {
+            div.row {
+                h3 { (group.group.name) }
+                span.status.(status) { (status_label(status)) }
+            }
+            @if let Some(description) = &group.group.description {
+                p.meta { (description) }
+            }
+            @for id in &group.monitor_ids {
+                @if let Some(monitor) = monitors.get(id) {
+                    (monitor_card(monitor, now))
+                }
+            }
+            @for subgroup in &group.groups {
+                (group_section(subgroup, monitors, now))
+            }
+        }
+    }
+}
+
+fn collect_monitor_ids(groups: &[GroupWithStatus], ids: &mut Vec<i32>) {
+    for group in groups {
+        ids.extend(&group.monitor_ids);
+        collect_monitor_ids(&group.groups, ids);
+    }
+}
+
+fn monitor_card(monitor: &MonitorWithStatus, now: OffsetDateTime) -> Markup {
+    let status = monitor.current_status.as_str();
+
+    html! {
+        div.card {
+            div.row {
+                h4 { (monitor.monitor.display_name) }
+                span.status.(status) { (status_label(status)) }
+            }
+            div.bars {
+                @for day in history(&monitor.daily_stats, now) {
+                    div.bar.(day.status) title={ (day.date) ": " (day.tooltip) } {}
+                }
+            }
+            div.row.meta {
+                span { (HISTORY_DAYS) " days ago" }
+                span { (format!("{:.2}", monitor.uptime_percentage)) "% uptime" }
+                span { "Today" }
+            }
+        }
+    }
+}
+
+/// The last [`HISTORY_DAYS`] days, oldest first, with days lacking stats
+/// shown as unknown. Thresholds match the app's status tracker.
+fn history(stats: &[StatusDailyStat], now: OffsetDateTime) -> Vec<Day> {
+    let by_date: HashMap<Date, &StatusDailyStat> = stats
+        .iter()
+        .filter_map(|stat| Some((stat.bucket?.to_offset(UtcOffset::UTC).date(), stat)))
+        .collect();
+    let today = now.to_offset(UtcOffset::UTC).date();
+
+    (0..HISTORY_DAYS)
+        .rev()
+        .map(|days_ago| {
+            let date = today - Duration::days(days_ago.into());
+            let Some(stat) = by_date.get(&date) else {
+                return Day { date, status: "unknown", tooltip: "No data available".to_string() };
+            };
+
+            let uptime = stat.uptime_percentage.unwrap_or(0.0);
+            let status = if uptime < 50.0 {
+                "major_outage"
+            } else if uptime < 90.0 {
+                "partial_outage"
+            } else if uptime < 98.0 {
+                "degraded"
+            } else if stat.check_count.unwrap_or(0) == 0 {
+                "unknown"
+            } else {
+                "operational"
+            };
+            Day { date, status, tooltip: format!("{:.1}% uptime", uptime) }
+        })
+        .collect()
+}
+
+// Readers may be anywhere, so times are given in UTC
+fn timestamp(time: OffsetDateTime) -> String {
+    let time = time.to_offset(UtcOffset::UTC);
+    format!("{} {:02}:{:02} UTC", time.date(), time.hour(), time.minute())
+}
@@ -0,0 +1,651 @@
+//! The public status page's data, shared by the app, which renders it with
+//! Inertia, and the worker, which exports a static copy of it (see
+//! [`export`]).
+
+pub mod badge;
+pub mod export;
+pub mod feed;
+pub mod html;
+pub mod statuspage;
+
+use db_core::error::DbError;
+use db_core::models::incident::{Incident, IncidentFilter, IncidentSort};
+use db_core::models::maintenance_window::MaintenanceWindow;
+use db_core::models::monitor::Monitor;
+use db_core::models::service_group::{ServiceGroup, StatusRollup};
+use db_core::models::status_event::StatusDailyStat;
+use db_core::models::status_page::{StatusPage, StatusPageVisibility};
+use db_core::pagination::PageRequest;
+use db_core::repositories::{
+    IncidentRepository, MaintenanceWindowRepository, MonitorRepository, ServiceGroupRepository, StatusEventRepository,
+    StatusPageRepository,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+/// Days of history behind each monitor's uptime bar
+pub const HISTORY_DAYS: i32 = 90;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonitorWithStatus {
+    pub monitor: Monitor,
+    pub current_status: String,
+    #[serde(with = "db_core::time_serde::option")]
+    pub last_check_time: Option<OffsetDateTime>,
+    pub uptime_percentage: f64,
+    pub daily_stats: Vec<StatusDailyStat>,
+}
+
+/// A service group with the status rolled up from its members. Monitors are
+/// listed by ID; their details are in [`StatusPageData::monitors`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupWithStatus {
+    pub group: ServiceGroup,
+    /// Monitors directly in this group, in page order
+    pub monitor_ids: Vec<i32>,
+    /// Subgroups with monitors on the page, in display order
+    pub groups: Vec<GroupWithStatus>,
+    pub overall_status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusPageData {
+    pub all_operational: bool,
+    #[serde(with = "db_core::time_serde")]
+    pub last_updated: OffsetDateTime,
+    pub monitors: Vec<MonitorWithStatus>,
+    /// Top-level groups with monitors on the page. Monitors in none of them
+    /// are shown ungrouped.
+    pub groups: Vec<GroupWithStatus>,
+    pub incidents: Vec<Incident>,
+    /// Windows in progress or still to come, soonest first
+    pub maintenance: Vec<MaintenanceWindow>,
+}
+
+/// A step in an incident's history. Incidents keep only their opening
+/// message; acknowledgement and resolution are told from their metadata
+/// and `resolved_at`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncidentUpdate {
+    /// `investigating`, `identified` or `resolved`
+    pub status: &'static str,
+    pub body: String,
+    pub at: OffsetDateTime,
+}
+
+/// Monitor metadata that is safe to show on any page
+const PUBLIC_METADATA: &[&str] = &["service_category", "priority"];
+
+/// Which monitors a status page shows. The default shows every active one
+/// except those that only pages visitors must unlock list (see
+/// [`private_monitor_ids`]).
+#[derive(Debug, Clone, Default)]
+pub struct StatusPageFilter {
+    /// Shown first, in this order
+    pub monitor_ids: Vec<i32>,
+    /// Monitors in these groups or their subgroups follow, group by group
+    pub service_group_ids: Vec<i32>,
+    /// Whether a page listing neither shows private monitors too, as pages
+    /// that are not public do
+    pub include_private: bool,
+}
+
+impl StatusPageFilter {
+    pub fn is_empty(&self) -> bool {
+        self.monitor_ids.is_empty() && self.service_group_ids.is_empty()
+    }
+
+    /// Where `monitor` goes on the page, or `None` when it is not shown.
+    /// `parents` maps each group to the group it is nested in.
+    fn position(&self, monitor: &Monitor, parents: &HashMap<i32, Option<i32>>) -> Option<usize> {
+        if self.is_empty() {
+            return Some(0);
+        }
+        if let Some(position) = self.monitor_ids.iter().position(|id| *id == monitor.id) {
+            return Some(position);
+        }
+
+        // The first selected group among the monitor's group and its
+        // ancestors; the step limit guards against a cycle
+        let mut group = monitor.service_group_id;
+        let mut position = None;
+        for _ in 0..=parents.len() {
+            let Some(id) = group else { break };
+            if let Some(selected) = self.service_group_ids.iter().position(|g| *g == id) {
+                position = Some(position.map_or(selected, |p: usize| p.min(selected)));
+            }
+            group = parents.get(&id).copied().flatten();
+        }
+        Some(self.monitor_ids.len() + position?)
+    }
+
+    /// Whether the page shows every incident and maintenance window, even
+    /// those affecting only monitors it leaves out
+    fn shows_everything(&self) -> bool {
+        self.is_empty() && self.include_private
+    }
+
+    /// Whether an incident or maintenance window affecting `affected` is
+    /// shown on a page with `shown` monitors. Affecting no monitor in
+    /// particular means affecting every page.
+    fn shows(&self, shown: &[i32], affected: &[i32]) -> bool {
+        self.shows_everything() || affected.is_empty() || affected.iter().any(|id| shown.contains(id))
+    }
+}
+
+impl From<&StatusPage> for StatusPageFilter {
+    fn from(page: &StatusPage) -> Self {
+        Self {
+            monitor_ids: page.monitor_ids.clone(),
+            service_group_ids: page.service_group_ids.clone(),
+            include_private: StatusPageVisibility::from(page.visibility.clone()) != StatusPageVisibility::Public,
+        }
+    }
+}
+
+/// Monitors that pages visitors must unlock list and no public page does.
+/// Pages showing every monitor don't count, or none would be private.
+pub fn private_monitor_ids(
+    pages: &[StatusPage],
+    monitors: &[Monitor],
+    parents: &HashMap<i32, Option<i32>>,
+) -> HashSet<i32> {
+    let mut private = HashSet::new();
+    let mut public = HashSet::new();
+    for page in pages {
+        let filter = StatusPageFilter::from(page);
+        if filter.is_empty() {
+            continue;
+        }
+        let listed = if filter.include_private { &mut private } else { &mut public };
+        listed.extend(
+            monitors
+                .iter()
+                .filter(|monitor| filter.position(monitor, parents).is_some())
+                .map(|monitor| monitor.id),
+        );
+    }
+    &private - &public
+}
+
+/// Active monitors the default page shows: all but the private ones
+pub async fn public_monitor_ids(pool: &PgPool) -> Result<HashSet<i32>, DbError> {
+    let parents: HashMap<i32, Option<i32>> = ServiceGroupRepository::list_all(pool)
+        .await?
+        .iter()
+        .map(|group| (group.id, group.parent_id))
+        .collect();
+    let active = MonitorRepository::list_active(pool).await?;
+    let private = private_monitor_ids(&StatusPageRepository::list_all(pool).await?, &active, &parents);
+
+    Ok(active.iter().map(|monitor| monitor.id).filter(|id| !private.contains(id)).collect())
+}
+
+/// Everything a status page shows: its active monitors with their 90-day
+/// history, and the unresolved incidents and upcoming maintenance that
+/// affect them or everything
+pub async fn load(pool: &PgPool, filter: &StatusPageFilter) -> Result<StatusPageData, DbError> {
+    let groups = ServiceGroupRepository::list_all(pool).await?;
+    let parents: HashMap<i32, Option<i32>> = groups.iter().map(|group| (group.id, group.parent_id)).collect();
+
+    let active = MonitorRepository::list_active(pool).await?;
+    let hidden = if filter.is_empty() && !filter.include_private {
+        private_monitor_ids(&StatusPageRepository::list_all(pool).await?, &active, &parents)
+    } else {
+        HashSet::new()
+    };
+
+    let mut monitors: Vec<(usize, Monitor)> = active
+        .into_iter()
+        .filter(|monitor| !hidden.contains(&monitor.id))
+        .filter_map(|monitor| Some((filter.position(&monitor, &parents)?, monitor)))
+        .collect();
+    // Stable, so monitors in the same group keep their usual order
+    monitors.sort_by_key(|(position, _)| *position);
+    let monitors: Vec<Monitor> = monitors.into_iter().map(|(_, monitor)| monitor).collect();
+
+    let shown: Vec<i32> = monitors.iter().map(|monitor| monitor.id).collect();
+    let incidents: Vec<Incident> = IncidentRepository::list_active(pool)
+        .await?
+        .into_iter()
+        .filter(|incident| filter.shows(&shown, &incident.affected_monitors))
+        .collect();
+    let maintenance: Vec<MaintenanceWindow> = MaintenanceWindowRepository::list_upcoming(pool)
+        .await?
+        .into_iter()
+        .filter(|window| filter.shows(&shown, &window.affected_monitors))
+        .collect();
+    let mut monitors_with_status = Vec::new();
+    let mut all_operational = true;
+    let now = OffsetDateTime::now_utc();
+    let history_from = now - Duration::days(HISTORY_DAYS as i64);
+
+    for monitor in monitors {
+        let summary = StatusEventRepository::get_monitor_summary(pool, monitor.id).await?;
+        let daily_stats = StatusEventRepository::get_daily_stats(pool, monitor.id, history_from, now).await?;
+
+        if summary.current_status != "operational" {
+            all_operational = false;
+        }
+
+        monitors_with_status.push(MonitorWithStatus {
+            monitor,
+            current_status: summary.current_status,
+            last_check_time: Some(summary.last_check_time),
+            uptime_percentage: summary.uptime_90d,
+            daily_stats,
+        });
+    }
+
+    // If there are active incidents, we're not fully operational
+    if !incidents.is_empty() {
+        all_operational = false;
+    }
+
+    let mut visited = HashSet::new();
+    let groups = group_statuses(&groups, None, &monitors_with_status, &mut visited);
+
+    Ok(StatusPageData {
+        all_operational,
+        last_updated: now,
+        monitors: monitors_with_status,
+        groups,
+        incidents,
+        maintenance,
+    })
+}
+
+/// The latest `limit` incidents, resolved or not, newest first, of those
+/// the page `data` was loaded for shows
+pub async fn incident_history(
+    pool: &PgPool,
+    filter: &StatusPageFilter,
+    data: &StatusPageData,
+    limit: i64,
+) -> Result<Vec<Incident>, DbError> {
+    let incidents = IncidentFilter {
+        shown_with: (!filter.shows_everything()).then(|| data.monitors.iter().map(|entry| entry.monitor.id).collect()),
+        ..Default::default()
+    };
+    let page = PageRequest {
+        limit: Some(limit),
+        ..Default::default()
+    };
+
+    Ok(IncidentRepository::list(pool, &incidents, IncidentSort::StartedAt, &page).await?.data)
+}
+
+/// `investigating` until the incident is acknowledged, `identified` until
+/// it is resolved, then `resolved`
+pub fn incident_status(incident: &Incident) -> &'static str {
+    match (incident.is_resolved, acknowledged_at(incident)) {
+        (true, _) => "resolved",
+        (false, Some(_)) => "identified",
+        (false, None) => "investigating",
+    }
+}
+
+/// The incident's history, newest first
+pub fn incident_updates(incident: &Incident) -> Vec<IncidentUpdate> {
+    let mut updates = Vec::new();
+    if let (true, Some(at)) = (incident.is_resolved, incident.resolved_at) {
+        updates.push(IncidentUpdate {
+            status: "resolved",
+            body: "This incident has been resolved.".to_string(),
+            at,
+        });
+    }
+    if let Some(at) = acknowledged_at(incident) {
+        updates.push(IncidentUpdate {
+            status: "identified",
+            body: "The issue has been identified.".to_string(),
+            at,
+        });
+    }
+    updates.push(IncidentUpdate {
+        status: "investigating",
+        body: incident.message.clone(),
+        at: incident.started_at,
+    });
+    updates
+}
+
+/// Set in the incident's metadata when someone acknowledges it from the
+/// dashboard
+fn acknowledged_at(incident: &Incident) -> Option<OffsetDateTime> {
+    let at = incident.metadata.as_ref()?.get("acknowledged_at")?.as_str()?;
+    OffsetDateTime::parse(at, &Rfc3339).ok()
+}
+
+/// The groups nested in `parent` that have monitors on the page, with their
+/// statuses rolled up. `visited` stops a cycle from recursing forever.
+fn group_statuses(
+    groups: &[ServiceGroup],
+    parent: Option<i32>,
+    monitors: &[MonitorWithStatus],
+    visited: &mut HashSet<i32>,
+) -> Vec<GroupWithStatus> {
+    let mut statuses = Vec::new();
+
+    for group in groups.iter().filter(|group| group.parent_id == parent) {
+        if !visited.insert(group.id) {
+            continue;
+        }
+
+        let members: Vec<&MonitorWithStatus> = monitors
+            .iter()
+            .filter(|entry| entry.monitor.service_group_id == Some(group.id))
+            .collect();
+        let subgroups = group_statuses(groups, Some(group.id), monitors, visited);
+        if members.is_empty() && subgroups.is_empty() {
+            continue;
+        }
+
+        let weighted_statuses: Vec<(&str, i32)> = members
+            .iter()
+            .map(|entry| (entry.current_status.as_str(), entry.monitor.weight))
+            .chain(subgroups.iter().map(|sub| (sub.overall_status.as_str(), sub.group.weight)))
+            .collect();
+        let overall_status = rollup(StatusRollup::from(group.status_rollup.clone()), &weighted_statuses).to_string();
+
+        statuses.push(GroupWithStatus {
+            group: group.clone(),
+            monitor_ids: members.iter().map(|entry| entry.monitor.id).collect(),
+            groups: subgroups,
+            overall_status,
+        });
+    }
+
+    statuses
+}
+
+/// Combine member statuses, each with its weight, into a group status.
+/// Members with an `unknown` status are left out; a group with nothing else
+/// is `unknown`. Weighting treats maintenance as operational.
+pub fn rollup(rollup: StatusRollup, statuses: &[(&str, i32)]) -> &'static str {
+    const BY_SEVERITY: [&str; 5] = ["operational", "maintenance", "degraded", "partial_outage", "major_outage"];
+    let severity = |status: &str| BY_SEVERITY.iter().position(|s| *s == status);
+    let known: Vec<(usize, i32)> = statuses
+        .iter()
+        .filter_map(|(status, weight)| Some((severity(status)?, *weight)))
+        .collect();
+    if known.is_empty() {
+        return "unknown";
+    }
+
+    match rollup {
+        StatusRollup::Worst => BY_SEVERITY[known.iter().map(|(severity, _)| *severity).max().unwrap_or(0)],
+        StatusRollup::Weighted => {
+            // Operational and maintenance score 0, major outages 3
+            let total: i64 = known.iter().map(|(_, weight)| i64::from(*weight)).sum();
+            let score: i64 = known
+                .iter()
+                .map(|(severity, weight)| severity.saturating_sub(1) as i64 * i64::from(*weight))
+                .sum();
+            match (score as f64 / total.max(1) as f64).round() as usize {
+                0 => "operational",
+                level => BY_SEVERITY[(level + 1).min(BY_SEVERITY.len() - 1)],
+            }
+        }
+    }
+}
+
+impl StatusPageData {
+    /// Strip what only operators should see: monitor URLs, check settings
+    /// kept in metadata, and incident metadata
+    pub fn hide_internal_details(&mut self) {
+        for entry in &mut self.monitors {
+            entry.monitor.url = None;
+            if let Some(serde_json::Value::Object(metadata)) = &mut entry.monitor.metadata {
+                metadata.retain(|key, _| PUBLIC_METADATA.contains(&key.as_str()));
+            }
+        }
+        for incident in &mut self.incidents {
+            incident.metadata = None;
+        }
+    }
+}
+
+/// Human-readable name of a monitor status
+pub fn status_label(status: &str) -> &'static str {
+    match status {
+        "operational" => "Operational",
+        "degraded" => "Degraded Performance",
+        "partial_outage" => "Partial Outage",
+        "major_outage" => "Major Outage",
+        "maintenance" => "Maintenance",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn monitor(id: i32, group: Option<i32>) -> Monitor {
+        let now = OffsetDateTime::now_utc();
+        Monitor {
+            id,
+            name: format!("monitor-{}", id),
+            display_name: format!("Monitor {}", id),
+            description: None,
+            url: Some("https://internal.example.com/health".to_string()),
+            monitor_type: "http".to_string(),
+            check_interval: 60,
+            timeout: 10,
+            is_active: true,
+            metadata: Some(json!({ "service_category": "api", "expected_status": 200 })),
+            service_group_id: group,
+            weight: 1,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn group(id: i32, parent_id: Option<i32>, status_rollup: &str) -> ServiceGroup {
+        let now = OffsetDateTime::now_utc();
+        ServiceGroup {
+            id,
+            name: format!("Group {}", id),
+            description: None,
+            parent_id,
+            position: 0,
+            weight: 1,
+            status_rollup: status_rollup.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn with_status(monitor: Monitor, status: &str) -> MonitorWithStatus {
+        MonitorWithStatus {
+            monitor,
+            current_status: status.to_string(),
+            last_check_time: None,
+            uptime_percentage: 100.0,
+            daily_stats: vec![],
+        }
+    }
+
+    #[test]
+    fn filters_place_listed_monitors_before_their_groups() {
+        // Group 2 is nested in group 1
+        let parents = HashMap::from([(1, None), (2, Some(1)), (3, None)]);
+        let filter = StatusPageFilter {
+            monitor_ids: vec![9, 4],
+            service_group_ids: vec![3, 1],
+            ..Default::default()
+        };
+
+        assert_eq!(filter.position(&monitor(4, None), &parents), Some(1));
+        assert_eq!(filter.position(&monitor(1, Some(2)), &parents), Some(3));
+        assert_eq!(filter.position(&monitor(5, Some(3)), &parents), Some(2));
+        assert_eq!(filter.position(&monitor(3, None), &parents), None);
+        assert_eq!(StatusPageFilter::default().position(&monitor(3, None), &parents), Some(0));
+    }
+
+    fn page(visibility: &str, monitor_ids: Vec<i32>, service_group_ids: Vec<i32>) -> StatusPage {
+        let now = OffsetDateTime::now_utc();
+        StatusPage {
+            id: 1,
+            slug: "page".to_string(),
+            title: "Page".to_string(),
+            description: None,
+            logo_url: None,
+            custom_domain: None,
+            monitor_ids,
+            service_group_ids,
+            visibility: visibility.to_string(),
+            password_hash: None,
+            show_internal_details: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn monitors_only_on_restricted_pages_are_private() {
+        let parents = HashMap::from([(1, None), (2, Some(1))]);
+        let monitors = vec![monitor(1, None), monitor(2, Some(2)), monitor(3, Some(1)), monitor(4, None)];
+        let pages = vec![
+            page("sso", vec![1, 4], vec![]),
+            page("password", vec![], vec![1]),
+            page("public", vec![4], vec![2]),
+            // Shows everything, so says nothing about any one monitor
+            page("public", vec![], vec![]),
+        ];
+
+        let private = private_monitor_ids(&pages, &monitors, &parents);
+
+        assert_eq!(private, HashSet::from([1, 3]));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn the_default_page_leaves_out_private_monitors() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+        let private: i32 = sqlx::query_scalar(
+            "INSERT INTO monitors (name, display_name, is_active) VALUES ('private-monitor-test', 'Private', TRUE) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let page: i32 = sqlx::query_scalar(
+            "INSERT INTO status_pages (slug, title, monitor_ids, visibility) VALUES ('private-monitor-test', 'Private', $1, 'sso') RETURNING id",
+        )
+        .bind(vec![private])
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let incident: i32 = sqlx::query_scalar(
+            "INSERT INTO incidents (title, message, severity, affected_monitors) VALUES ('Private outage', 'Down', 'critical', $1) RETURNING id",
+        )
+        .bind(vec![private])
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let shown = |data: &StatusPageData| data.monitors.iter().any(|entry| entry.monitor.id == private);
+        let mentioned = |data: &StatusPageData| data.incidents.iter().any(|entry| entry.id == incident);
+        let default = load(&pool, &StatusPageFilter::default()).await.unwrap();
+        assert!(!shown(&default) && !mentioned(&default));
+        let restricted = StatusPageFilter { include_private: true, ..Default::default() };
+        let everything = load(&pool, &restricted).await.unwrap();
+        assert!(shown(&everything) && mentioned(&everything));
+
+        sqlx::query("DELETE FROM incidents WHERE id = $1").bind(incident).execute(&pool).await.unwrap();
+        sqlx::query("DELETE FROM status_pages WHERE id = $1").bind(page).execute(&pool).await.unwrap();
+        sqlx::query("DELETE FROM monitors WHERE id = $1").bind(private).execute(&pool).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn incident_history_is_limited_after_filtering_to_the_page() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+        let mut monitors = Vec::new();
+        for name in ["history-shown-test", "history-other-test"] {
+            let id: i32 = sqlx::query_scalar("INSERT INTO monitors (name, display_name, is_active) VALUES ($1, $1, TRUE) RETURNING id")
+                .bind(name)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            monitors.push(id);
+        }
+        // The page's incident is older than the two it does not show
+        let mut incidents = Vec::new();
+        for (monitor, age) in [(monitors[0], "1 day"), (monitors[1], "1 hour"), (monitors[1], "1 minute")] {
+            let id: i32 = sqlx::query_scalar(
+                "INSERT INTO incidents (title, message, severity, affected_monitors, started_at) VALUES ('History', 'Down', 'critical', $1, NOW() - $2::interval) RETURNING id",
+            )
+            .bind(vec![monitor])
+            .bind(age)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            incidents.push(id);
+        }
+
+        let filter = StatusPageFilter { monitor_ids: vec![monitors[0]], ..Default::default() };
+        let data = load(&pool, &filter).await.unwrap();
+        let history = incident_history(&pool, &filter, &data, 2).await.unwrap();
+
+        assert_eq!(history.iter().map(|incident| incident.id).collect::<Vec<_>>(), [incidents[0]]);
+
+        sqlx::query("DELETE FROM incidents WHERE id = ANY($1)").bind(&incidents).execute(&pool).await.unwrap();
+        sqlx::query("DELETE FROM monitors WHERE id = ANY($1)").bind(&monitors).execute(&pool).await.unwrap();
+    }
+
+    #[test]
+    fn groups_roll_up_their_monitors_and_subgroups() {
+        let groups = vec![group(1, None, "worst"), group(2, Some(1), "weighted"), group(3, None, "worst")];
+        let monitors = vec![
+            with_status(monitor(1, Some(1)), "operational"),
+            with_status(monitor(2, Some(2)), "major_outage"),
+            with_status(monitor(3, Some(2)), "operational"),
+            with_status(monitor(4, Some(2)), "operational"),
+            with_status(monitor(5, None), "operational"),
+        ];
+
+        let tree = group_statuses(&groups, None, &monitors, &mut HashSet::new());
+
+        // Group 3 has no monitors on the page
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].monitor_ids, [1]);
+        assert_eq!(tree[0].groups[0].monitor_ids, [2, 3, 4]);
+        assert_eq!(tree[0].groups[0].overall_status, "degraded");
+        assert_eq!(tree[0].overall_status, "degraded");
+    }
+
+    #[test]
+    fn rollups_skip_unknown_members() {
+        let statuses = [("major_outage", 1), ("operational", 4), ("unknown", 10)];
+
+        assert_eq!(rollup(StatusRollup::Worst, &statuses), "major_outage");
+        assert_eq!(rollup(StatusRollup::Weighted, &statuses), "degraded");
+        assert_eq!(rollup(StatusRollup::Weighted, &[("major_outage", 1), ("operational", 1)]), "partial_outage");
+        assert_eq!(rollup(StatusRollup::Worst, &[("maintenance", 1), ("operational", 1)]), "maintenance");
+        assert_eq!(rollup(StatusRollup::Worst, &[("unknown", 1)]), "unknown");
+    }
+
+    #[test]
+    fn hiding_internal_details_keeps_public_metadata() {
+        let mut data = StatusPageData {
+            all_operational: true,
+            last_updated: OffsetDateTime::now_utc(),
+            monitors: vec![with_status(monitor(1, Some(1)), "operational")],
+            groups: vec![],
+            incidents: vec![],
+            maintenance: vec![],
+        };
+
+        data.hide_internal_details();
+
+        let monitor = &data.monitors[0].monitor;
+        assert_eq!(monitor.url, None);
+        assert_eq!(monitor.metadata, Some(json!({ "service_category": "api" })));
+    }
+}
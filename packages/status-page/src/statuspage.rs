@@ -0,0 +1,539 @@
+//! The status page in the JSON shapes of Atlassian Statuspage's public API
+//! (`summary.json`, `status.json` and `incidents.json`), so chat bots and
+//! widgets written for it work unchanged.
+//!
+//! Monitors become components, and each service group becomes a component
+//! group. Statuspage groups cannot nest, so a subgroup is listed as a group
+//! of its own, named after its parents. IDs are prefixed by kind, since
+//! monitors, groups and incidents are numbered separately.
+
+use crate::{GroupWithStatus, StatusPageData};
+use db_core::models::incident::Incident;
+use db_core::models::maintenance_window::MaintenanceWindow;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+/// The page every document starts with
+#[derive(Debug, Clone, Serialize)]
+pub struct PageInfo {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub time_zone: &'static str,
+    #[serde(with = "db_core::time_serde")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl PageInfo {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, url: impl Into<String>, updated_at: OffsetDateTime) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            url: url.into(),
+            time_zone: "Etc/UTC",
+            updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Status {
+    /// `none`, `minor`, `major`, `critical` or `maintenance`
+    pub indicator: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Component {
+    pub id: String,
+    pub name: String,
+    /// `operational`, `degraded_performance`, `partial_outage`,
+    /// `major_outage` or `under_maintenance`
+    pub status: &'static str,
+    #[serde(with = "db_core::time_serde")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "db_core::time_serde")]
+    pub updated_at: OffsetDateTime,
+    pub position: usize,
+    pub description: Option<String>,
+    pub showcase: bool,
+    pub start_date: Option<String>,
+    pub group_id: Option<String>,
+    pub page_id: String,
+    pub group: bool,
+    pub only_show_if_degraded: bool,
+    /// The IDs of a group's components
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentUpdate {
+    pub id: String,
+    pub status: &'static str,
+    pub body: String,
+    pub incident_id: String,
+    #[serde(with = "db_core::time_serde")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "db_core::time_serde")]
+    pub updated_at: OffsetDateTime,
+    #[serde(with = "db_core::time_serde")]
+    pub display_at: OffsetDateTime,
+    pub affected_components: Option<Vec<String>>,
+    pub deliver_notifications: bool,
+    pub custom_tweet: Option<String>,
+    pub tweet_id: Option<String>,
+}
+
+/// An incident, or a scheduled maintenance when `scheduled_for` is set
+#[derive(Debug, Clone, Serialize)]
+pub struct StatuspageIncident {
+    pub id: String,
+    pub name: String,
+    /// `investigating`, `identified` or `resolved` for incidents;
+    /// `scheduled` or `in_progress` for maintenance
+    pub status: &'static str,
+    #[serde(with = "db_core::time_serde")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "db_core::time_serde")]
+    pub updated_at: OffsetDateTime,
+    #[serde(with = "db_core::time_serde::option")]
+    pub monitoring_at: Option<OffsetDateTime>,
+    #[serde(with = "db_core::time_serde::option")]
+    pub resolved_at: Option<OffsetDateTime>,
+    pub impact: &'static str,
+    pub shortlink: String,
+    #[serde(with = "db_core::time_serde")]
+    pub started_at: OffsetDateTime,
+    pub page_id: String,
+    /// Newest first
+    pub incident_updates: Vec<IncidentUpdate>,
+    pub components: Vec<Component>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "db_core::time_serde::option")]
+    pub scheduled_for: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "db_core::time_serde::option")]
+    pub scheduled_until: Option<OffsetDateTime>,
+}
+
+/// `summary.json`
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub page: PageInfo,
+    pub components: Vec<Component>,
+    /// Unresolved only
+    pub incidents: Vec<StatuspageIncident>,
+    /// In progress or upcoming
+    pub scheduled_maintenances: Vec<StatuspageIncident>,
+    pub status: Status,
+}
+
+/// `status.json`
+#[derive(Debug, Clone, Serialize)]
+pub struct PageStatus {
+    pub page: PageInfo,
+    pub status: Status,
+}
+
+/// `incidents.json`
+#[derive(Debug, Clone, Serialize)]
+pub struct Incidents {
+    pub page: PageInfo,
+    pub incidents: Vec<StatuspageIncident>,
+}
+
+pub fn summary(page: PageInfo, data: &StatusPageData) -> Summary {
+    let components = components(&page, data);
+    let incidents = data.incidents.iter().map(|incident| to_incident(&page, &components, incident)).collect();
+    let scheduled_maintenances = data
+        .maintenance
+        .iter()
+        .map(|window| to_maintenance(&page, &components, window, data.last_updated))
+        .collect();
+    let status = status(data);
+
+    Summary {
+        page,
+        components,
+        incidents,
+        scheduled_maintenances,
+        status,
+    }
+}
+
+pub fn page_status(page: PageInfo, data: &StatusPageData) -> PageStatus {
+    PageStatus {
+        status: status(data),
+        page,
+    }
+}
+
+/// `history` is the page's recent incidents, resolved or not, newest first
+pub fn incidents(page: PageInfo, data: &StatusPageData, history: &[Incident]) -> Incidents {
+    let components = components(&page, data);
+    let incidents = history.iter().map(|incident| to_incident(&page, &components, incident)).collect();
+
+    Incidents { page, incidents }
+}
+
+/// The page's overall status: its worst component or unresolved incident,
+/// or maintenance when that is all that is going on
+fn status(data: &StatusPageData) -> Status {
+    const BY_IMPACT: [&str; 4] = ["none", "minor", "major", "critical"];
+
+    let impact = data
+        .monitors
+        .iter()
+        .map(|entry| component_impact(&entry.current_status))
+        .chain(data.incidents.iter().map(|incident| incident_impact(&incident.severity)))
+        .filter_map(|impact| BY_IMPACT.iter().position(|i| *i == impact))
+        .max()
+        .unwrap_or(0);
+    let in_maintenance = data.monitors.iter().any(|entry| entry.current_status == "maintenance")
+        || data.maintenance.iter().any(|window| window.is_in_progress(data.last_updated));
+
+    let indicator = match BY_IMPACT[impact] {
+        "none" if in_maintenance => "maintenance",
+        indicator => indicator,
+    };
+    let description = match indicator {
+        "none" => "All Systems Operational",
+        "minor" => "Minor Service Outage",
+        "major" => "Partial System Outage",
+        "critical" => "Major System Outage",
+        _ => "Service Under Maintenance",
+    };
+    Status { indicator, description }
+}
+
+/// Groups first, each followed by its monitors, then ungrouped monitors
+fn components(page: &PageInfo, data: &StatusPageData) -> Vec<Component> {
+    let mut components = Vec::new();
+    for group in &data.groups {
+        group_components(page, data, group, "", &mut components);
+    }
+
+    let grouped: Vec<i32> = components
+        .iter()
+        .filter(|component| !component.group)
+        .filter_map(|component| component.id.strip_prefix("monitor-")?.parse().ok())
+        .collect();
+    for entry in data.monitors.iter().filter(|entry| !grouped.contains(&entry.monitor.id)) {
+        components.push(monitor_component(page, entry, None, components.len() + 1));
+    }
+
+    components
+}
+
+fn group_components(
+    page: &PageInfo,
+    data: &StatusPageData,
+    group: &GroupWithStatus,
+    prefix: &str,
+    components: &mut Vec<Component>,
+) {
+    let name = if prefix.is_empty() {
+        group.group.name.clone()
+    } else {
+        format!("{} / {}", prefix, group.group.name)
+    };
+    let group_id = format!("group-{}", group.group.id);
+    let members: Vec<_> = group
+        .monitor_ids
+        .iter()
+        .filter_map(|id| data.monitors.iter().find(|entry| entry.monitor.id == *id))
+        .collect();
+
+    if !members.is_empty() {
+        components.push(Component {
+            id: group_id.clone(),
+            name: name.clone(),
+            status: component_status(&group.overall_status),
+            created_at: group.group.created_at,
+            updated_at: group.group.updated_at,
+            position: components.len() + 1,
+            description: group.group.description.clone(),
+            showcase: false,
+            start_date: None,
+            group_id: None,
+            page_id: page.id.clone(),
+            group: true,
+            only_show_if_degraded: false,
+            components: Some(members.iter().map(|entry| format!("monitor-{}", entry.monitor.id)).collect()),
+        });
+        for entry in members {
+            components.push(monitor_component(page, entry, Some(group_id.clone()), components.len() + 1));
+        }
+    }
+
+    for subgroup in &group.groups {
+        group_components(page, data, subgroup, &name, components);
+    }
+}
+
+fn monitor_component(
+    page: &PageInfo,
+    entry: &crate::MonitorWithStatus,
+    group_id: Option<String>,
+    position: usize,
+) -> Component {
+    Component {
+        id: format!("monitor-{}", entry.monitor.id),
+        name: entry.monitor.display_name.clone(),
+        status: component_status(&entry.current_status),
+        created_at: entry.monitor.created_at,
+        updated_at: entry.last_check_time.unwrap_or(entry.monitor.updated_at),
+        position,
+        description: entry.monitor.description.clone(),
+        showcase: true,
+        start_date: Some(entry.monitor.created_at.date().to_string()),
+        group_id,
+        page_id: page.id.clone(),
+        group: false,
+        only_show_if_degraded: false,
+        components: None,
+    }
+}
+
+fn to_incident(page: &PageInfo, components: &[Component], incident: &Incident) -> StatuspageIncident {
+    let id = format!("incident-{}", incident.id);
+    let updates = crate::incident_updates(incident)
+        .into_iter()
+        .map(|update| IncidentUpdate {
+            id: format!("{}-{}", id, update.status),
+            status: update.status,
+            body: update.body,
+            incident_id: id.clone(),
+            created_at: update.at,
+            updated_at: update.at,
+            display_at: update.at,
+            affected_components: None,
+            deliver_notifications: false,
+            custom_tweet: None,
+            tweet_id: None,
+        })
+        .collect();
+
+    StatuspageIncident {
+        name: incident.title.clone(),
+        status: crate::incident_status(incident),
+        created_at: incident.created_at,
+        updated_at: incident.updated_at,
+        monitoring_at: None,
+        resolved_at: incident.resolved_at,
+        impact: incident_impact(&incident.severity),
+        shortlink: page.url.clone(),
+        started_at: incident.started_at,
+        page_id: page.id.clone(),
+        incident_updates: updates,
+        components: affected(components, &incident.affected_monitors),
+        scheduled_for: None,
+        scheduled_until: None,
+        id,
+    }
+}
+
+fn to_maintenance(
+    page: &PageInfo,
+    components: &[Component],
+    window: &MaintenanceWindow,
+    now: OffsetDateTime,
+) -> StatuspageIncident {
+    let id = format!("maintenance-{}", window.id);
+    let status = if window.is_in_progress(now) { "in_progress" } else { "scheduled" };
+
+    StatuspageIncident {
+        name: window.title.clone(),
+        status,
+        created_at: window.created_at,
+        updated_at: window.updated_at,
+        monitoring_at: None,
+        resolved_at: None,
+        impact: "maintenance",
+        shortlink: page.url.clone(),
+        started_at: window.starts_at,
+        page_id: page.id.clone(),
+        incident_updates: vec![IncidentUpdate {
+            id: format!("{}-{}", id, status),
+            status,
+            body: window.description.clone().unwrap_or_default(),
+            incident_id: id.clone(),
+            created_at: window.created_at,
+            updated_at: window.updated_at,
+            display_at: window.created_at,
+            affected_components: None,
+            deliver_notifications: false,
+            custom_tweet: None,
+            tweet_id: None,
+        }],
+        components: affected(components, &window.affected_monitors),
+        scheduled_for: Some(window.starts_at),
+        scheduled_until: Some(window.ends_at),
+        id,
+    }
+}
+
+/// The page's components for `monitor_ids`. Affecting no monitor in
+/// particular means affecting none on the page.
+fn affected(components: &[Component], monitor_ids: &[i32]) -> Vec<Component> {
+    components
+        .iter()
+        .filter(|component| !component.group)
+        .filter(|component| monitor_ids.iter().any(|id| component.id == format!("monitor-{}", id)))
+        .cloned()
+        .collect()
+}
+
+/// Statuspage has no `unknown`; a monitor not checked yet is not an outage
+fn component_status(status: &str) -> &'static str {
+    match status {
+        "degraded" => "degraded_performance",
+        "partial_outage" => "partial_outage",
+        "major_outage" => "major_outage",
+        "maintenance" => "under_maintenance",
+        _ => "operational",
+    }
+}
+
+fn component_impact(status: &str) -> &'static str {
+    match status {
+        "degraded" => "minor",
+        "partial_outage" => "major",
+        "major_outage" => "critical",
+        _ => "none",
+    }
+}
+
+fn incident_impact(severity: &str) -> &'static str {
+    match severity {
+        "critical" => "critical",
+        "warning" => "minor",
+        _ => "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MonitorWithStatus;
+    use db_core::models::monitor::Monitor;
+    use db_core::models::service_group::ServiceGroup;
+    use time::Duration;
+
+    const NOW: OffsetDateTime = OffsetDateTime::UNIX_EPOCH;
+
+    fn monitor(id: i32, status: &str, service_group_id: Option<i32>) -> MonitorWithStatus {
+        MonitorWithStatus {
+            monitor: Monitor {
+                id,
+                name: format!("monitor-{}", id),
+                display_name: format!("Monitor {}", id),
+                description: None,
+                url: None,
+                monitor_type: "http".to_string(),
+                check_interval: 60,
+                timeout: 30,
+                is_active: true,
+                metadata: None,
+                service_group_id,
+                weight: 1,
+                created_at: NOW,
+                updated_at: NOW,
+            },
+            current_status: status.to_string(),
+            last_check_time: Some(NOW),
+            uptime_percentage: 100.0,
+            daily_stats: Vec::new(),
+        }
+    }
+
+    fn incident(id: i32, severity: &str, affected_monitors: Vec<i32>) -> Incident {
+        Incident {
+            id,
+            title: "Slow logins".to_string(),
+            message: "Logins are taking longer than usual.".to_string(),
+            severity: severity.to_string(),
+            affected_monitors,
+            created_at: NOW,
+            updated_at: NOW,
+            started_at: NOW,
+            resolved_at: None,
+            is_resolved: false,
+            metadata: None,
+        }
+    }
+
+    fn data(incidents: Vec<Incident>) -> StatusPageData {
+        StatusPageData {
+            all_operational: false,
+            last_updated: NOW,
+            monitors: vec![monitor(1, "operational", Some(7)), monitor(2, "degraded", None)],
+            groups: vec![GroupWithStatus {
+                group: ServiceGroup {
+                    id: 7,
+                    name: "API".to_string(),
+                    description: None,
+                    parent_id: None,
+                    position: 0,
+                    weight: 1,
+                    status_rollup: "worst".to_string(),
+                    created_at: NOW,
+                    updated_at: NOW,
+                },
+                monitor_ids: vec![1],
+                groups: Vec::new(),
+                overall_status: "operational".to_string(),
+            }],
+            incidents,
+            maintenance: Vec::new(),
+        }
+    }
+
+    fn page() -> PageInfo {
+        PageInfo::new("status", "Status", "https://status.example.com/status", NOW)
+    }
+
+    #[test]
+    fn summary_lists_groups_before_their_monitors() {
+        let summary = summary(page(), &data(Vec::new()));
+
+        let components: Vec<(&str, &str, Option<&str>)> = summary
+            .components
+            .iter()
+            .map(|c| (c.id.as_str(), c.status, c.group_id.as_deref()))
+            .collect();
+        assert_eq!(components, [
+            ("group-7", "operational", None),
+            ("monitor-1", "operational", Some("group-7")),
+            ("monitor-2", "degraded_performance", None),
+        ]);
+        assert_eq!(summary.components[0].components, Some(vec!["monitor-1".to_string()]));
+        assert_eq!(summary.status.indicator, "minor");
+    }
+
+    #[test]
+    fn incidents_carry_their_updates_newest_first() {
+        let mut resolved = incident(3, "critical", vec![1]);
+        resolved.is_resolved = true;
+        resolved.resolved_at = Some(NOW + Duration::hours(1));
+        let data = data(Vec::new());
+
+        let incidents = incidents(page(), &data, &[resolved]);
+        let json = serde_json::to_value(&incidents).unwrap();
+
+        assert_eq!(json["incidents"][0]["id"], "incident-3");
+        assert_eq!(json["incidents"][0]["status"], "resolved");
+        assert_eq!(json["incidents"][0]["impact"], "critical");
+        assert_eq!(json["incidents"][0]["incident_updates"][0]["status"], "resolved");
+        assert_eq!(json["incidents"][0]["incident_updates"][1]["body"], "Logins are taking longer than usual.");
+        assert_eq!(json["incidents"][0]["components"][0]["id"], "monitor-1");
+        assert!(json["incidents"][0].get("scheduled_for").is_none());
+    }
+
+    #[test]
+    fn unresolved_incidents_raise_the_page_indicator() {
+        let status = page_status(page(), &data(vec![incident(4, "critical", Vec::new())])).status;
+
+        assert_eq!(status.indicator, "critical");
+        assert_eq!(status.description, "Major System Outage");
+    }
+}
@@ -0,0 +1,174 @@
+//! Tracing setup shared by the app and the worker.
+//!
+//! Logs go to stdout, filtered by `RUST_LOG`. When `OTEL_EXPORTER_OTLP_ENDPOINT`
+//! (or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`) is set, spans are also exported to
+//! that collector over OTLP/HTTP. Trace context crosses process boundaries as
+//! W3C `traceparent`/`tracestate` fields: in HTTP headers, and in job payloads
+//! under [`PAYLOAD_KEY`].
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use std::collections::HashMap;
+use std::env;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Job payload field holding the trace context of the request that queued it
+pub const PAYLOAD_KEY: &str = "trace_context";
+
+const ENDPOINT_VARS: &[&str] = &["OTEL_EXPORTER_OTLP_ENDPOINT", "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT"];
+
+/// Owns the span exporter. Call [`Telemetry::shutdown`] before the process
+/// exits so buffered spans are sent.
+#[must_use]
+pub struct Telemetry {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    /// Flush and stop the exporter. Blocks until the collector has answered.
+    pub fn shutdown(self) {
+        if let Some(provider) = self.provider {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush traces: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global subscriber for a binary. `service_name` becomes the
+/// `service.name` of every exported span.
+pub fn init(service_name: &'static str) -> Telemetry {
+    let provider = if ENDPOINT_VARS.iter().any(|var| env::var_os(var).is_some()) {
+        // The exporter reads the endpoint and headers from the OTEL_* variables
+        match SpanExporter::builder().with_http().build() {
+            Ok(exporter) => Some(tracer_provider(service_name, exporter)),
+            Err(e) => {
+                eprintln!("Failed to set up OTLP trace export: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let otel = provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer(service_name)));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(otel)
+        .init();
+
+    if provider.is_some() {
+        tracing::info!("Exporting traces over OTLP");
+    }
+    Telemetry { provider }
+}
+
+fn tracer_provider(service_name: &'static str, exporter: SpanExporter) -> SdkTracerProvider {
+    SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build()
+}
+
+/// The trace context of `span` as `traceparent`/`tracestate` fields. Empty
+/// when traces are not being exported.
+pub fn inject(span: &Span) -> HashMap<String, String> {
+    let mut carrier = HashMap::new();
+    TraceContextPropagator::new().inject_context(&span.context(), &mut carrier);
+    carrier
+}
+
+/// Make `span` a child of the remote span described by `carrier`, as
+/// produced by [`inject`] or sent in HTTP headers. Field names are matched
+/// case-insensitively; without a valid `traceparent` the span stays a root.
+pub fn set_parent(span: &Span, carrier: &HashMap<String, String>) {
+    let carrier: HashMap<String, String> = carrier
+        .iter()
+        .map(|(name, value)| (name.to_lowercase(), value.clone()))
+        .collect();
+    let context = TraceContextPropagator::new().extract(&carrier);
+
+    // Only fails when the span is disabled, in which case there is nothing to link
+    let _ = span.set_parent(context);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, http::StatusCode, routing::post, Router};
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+    use prost::Message;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use tracing::info_span;
+
+    /// Stands in for an OTLP collector: decodes each export and passes it on
+    async fn collector() -> (String, mpsc::UnboundedReceiver<ExportTraceServiceRequest>) {
+        let (exports, received) = mpsc::unbounded_channel();
+        let app = Router::new().route(
+            "/v1/traces",
+            post(move |body: Bytes| {
+                let exports = exports.clone();
+                async move {
+                    exports.send(ExportTraceServiceRequest::decode(body).unwrap()).unwrap();
+                    StatusCode::OK
+                }
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/v1/traces", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (endpoint, received)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn jobs_continue_the_trace_of_the_request_that_queued_them() {
+        let (endpoint, mut received) = collector().await;
+        let exporter = SpanExporter::builder().with_http().with_endpoint(endpoint).build().unwrap();
+        let provider = tracer_provider("telemetry-test", exporter);
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        let carrier = tracing::subscriber::with_default(subscriber, || {
+            let carrier = inject(&info_span!("request"));
+
+            // As the worker would, from the payload of the queued job
+            let job = info_span!("job");
+            set_parent(&job, &carrier);
+            job.in_scope(|| info_span!("query").in_scope(|| {}));
+            carrier
+        });
+        assert!(carrier.contains_key("traceparent"));
+        tokio::task::spawn_blocking(move || provider.shutdown()).await.unwrap().unwrap();
+
+        let mut spans = HashMap::new();
+        while let Ok(export) = received.try_recv() {
+            for scope in export.resource_spans.into_iter().flat_map(|r| r.scope_spans) {
+                for span in scope.spans {
+                    spans.insert(span.name.clone(), span);
+                }
+            }
+        }
+
+        let (request, job, query) = (&spans["request"], &spans["job"], &spans["query"]);
+        assert_eq!(job.trace_id, request.trace_id);
+        assert_eq!(job.parent_span_id, request.span_id);
+        assert_eq!(query.trace_id, request.trace_id);
+        assert_eq!(query.parent_span_id, job.span_id);
+    }
+}
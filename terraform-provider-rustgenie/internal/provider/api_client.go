@@ -7,6 +7,7 @@ import (
 	"fmt"
 	"io"
 	"net/http"
+	"net/url"
 	"time"
 )
 
@@ -26,6 +27,13 @@ type Monitor struct {
 	UpdatedAt     *time.Time             `json:"updated_at,omitempty"`
 }
 
+// MonitorPage is one page of the paginated monitor list
+type MonitorPage struct {
+	Data       []Monitor `json:"data"`
+	NextCursor *string   `json:"next_cursor"`
+	HasMore    bool      `json:"has_more"`
+}
+
 // CreateMonitorRequest represents the request body for creating a monitor
 type CreateMonitorRequest struct {
 	Name          string                 `json:"name"`
@@ -84,7 +92,7 @@ func (c *APIClient) doRequest(ctx context.Context, method, path string, body int
 
 // CreateMonitor creates a new monitor
 func (c *APIClient) CreateMonitor(ctx context.Context, monitor CreateMonitorRequest) (*Monitor, error) {
-	resp, err := c.doRequest(ctx, "POST", "/api/monitors", monitor)
+	resp, err := c.doRequest(ctx, "POST", "/api/v1/monitors", monitor)
 	if err != nil {
 		return nil, err
 	}
@@ -105,7 +113,7 @@ func (c *APIClient) CreateMonitor(ctx context.Context, monitor CreateMonitorRequ
 
 // GetMonitor retrieves a monitor by ID
 func (c *APIClient) GetMonitor(ctx context.Context, id int) (*Monitor, error) {
-	resp, err := c.doRequest(ctx, "GET", fmt.Sprintf("/api/monitors/%d", id), nil)
+	resp, err := c.doRequest(ctx, "GET", fmt.Sprintf("/api/v1/monitors/%d", id), nil)
 	if err != nil {
 		return nil, err
 	}
@@ -130,8 +138,7 @@ func (c *APIClient) GetMonitor(ctx context.Context, id int) (*Monitor, error) {
 
 // GetMonitorByName retrieves a monitor by name
 func (c *APIClient) GetMonitorByName(ctx context.Context, name string) (*Monitor, error) {
-	// First, get all monitors
-	resp, err := c.doRequest(ctx, "GET", "/api/monitors", nil)
+	resp, err := c.doRequest(ctx, "GET", "/api/v1/monitors?name="+url.QueryEscape(name), nil)
 	if err != nil {
 		return nil, err
 	}
@@ -142,13 +149,13 @@ func (c *APIClient) GetMonitorByName(ctx context.Context, name string) (*Monitor
 		return nil, fmt.Errorf("failed to list monitors: %s (status %d)", string(body), resp.StatusCode)
 	}
 
-	var monitors []Monitor
-	if err := json.NewDecoder(resp.Body).Decode(&monitors); err != nil {
+	var page MonitorPage
+	if err := json.NewDecoder(resp.Body).Decode(&page); err != nil {
 		return nil, fmt.Errorf("failed to decode response: %w", err)
 	}
 
-	// Find monitor by name
-	for _, m := range monitors {
+	// Names are unique, so the filtered list holds at most one monitor
+	for _, m := range page.Data {
 		if m.Name == name {
 			return &m, nil
 		}
@@ -159,7 +166,7 @@ func (c *APIClient) GetMonitorByName(ctx context.Context, name string) (*Monitor
 
 // UpdateMonitor updates an existing monitor
 func (c *APIClient) UpdateMonitor(ctx context.Context, id int, monitor UpdateMonitorRequest) (*Monitor, error) {
-	resp, err := c.doRequest(ctx, "PUT", fmt.Sprintf("/api/monitors/%d", id), monitor)
+	resp, err := c.doRequest(ctx, "PUT", fmt.Sprintf("/api/v1/monitors/%d", id), monitor)
 	if err != nil {
 		return nil, err
 	}
@@ -180,7 +187,7 @@ func (c *APIClient) UpdateMonitor(ctx context.Context, id int, monitor UpdateMon
 
 // DeleteMonitor deletes a monitor
 func (c *APIClient) DeleteMonitor(ctx context.Context, id int) error {
-	resp, err := c.doRequest(ctx, "DELETE", fmt.Sprintf("/api/monitors/%d", id), nil)
+	resp, err := c.doRequest(ctx, "DELETE", fmt.Sprintf("/api/v1/monitors/%d", id), nil)
 	if err != nil {
 		return err
 	}
@@ -3,7 +3,7 @@
 # Test script for RustGenie API
 
 echo "1. Creating a monitor..."
-MONITOR_RESPONSE=$(curl -s -X POST http://localhost:8000/api/monitors \
+MONITOR_RESPONSE=$(curl -s -X POST http://localhost:8000/api/v1/monitors \
   -H "Content-Type: application/json" \
   -d '{
     "name": "api-health",
@@ -22,7 +22,7 @@ MONITOR_ID=$(echo $MONITOR_RESPONSE | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
 echo "Created monitor with ID: $MONITOR_ID"
 
 echo -e "\n2. Creating another monitor..."
-curl -s -X POST http://localhost:8000/api/monitors \
+curl -s -X POST http://localhost:8000/api/v1/monitors \
   -H "Content-Type: application/json" \
   -d '{
     "name": "database-health",
@@ -37,12 +37,12 @@ curl -s -X POST http://localhost:8000/api/monitors \
   }' | jq .
 
 echo -e "\n3. Listing all monitors..."
-curl -s http://localhost:8000/api/monitors | jq .
+curl -s http://localhost:8000/api/v1/monitors | jq .
 
 echo -e "\n4. Recording some status events for monitor $MONITOR_ID..."
 
 # Add operational status
-curl -s -X POST http://localhost:8000/api/monitors/$MONITOR_ID/events \
+curl -s -X POST http://localhost:8000/api/v1/monitors/$MONITOR_ID/events \
   -H "Content-Type: application/json" \
   -d '{
     "monitor_id": '$MONITOR_ID',
@@ -54,7 +54,7 @@ curl -s -X POST http://localhost:8000/api/monitors/$MONITOR_ID/events \
   }' | jq .
 
 # Add degraded status
-curl -s -X POST http://localhost:8000/api/monitors/$MONITOR_ID/events \
+curl -s -X POST http://localhost:8000/api/v1/monitors/$MONITOR_ID/events \
   -H "Content-Type: application/json" \
   -d '{
     "monitor_id": '$MONITOR_ID',
@@ -66,7 +66,7 @@ curl -s -X POST http://localhost:8000/api/monitors/$MONITOR_ID/events \
   }' | jq .
 
 # Add partial outage
-curl -s -X POST http://localhost:8000/api/monitors/$MONITOR_ID/events \
+curl -s -X POST http://localhost:8000/api/v1/monitors/$MONITOR_ID/events \
   -H "Content-Type: application/json" \
   -d '{
     "monitor_id": '$MONITOR_ID',
@@ -78,7 +78,7 @@ curl -s -X POST http://localhost:8000/api/monitors/$MONITOR_ID/events \
   }' | jq .
 
 # Back to operational
-curl -s -X POST http://localhost:8000/api/monitors/$MONITOR_ID/events \
+curl -s -X POST http://localhost:8000/api/v1/monitors/$MONITOR_ID/events \
   -H "Content-Type: application/json" \
   -d '{
     "monitor_id": '$MONITOR_ID',
@@ -90,10 +90,10 @@ curl -s -X POST http://localhost:8000/api/monitors/$MONITOR_ID/events \
   }' | jq .
 
 echo -e "\n5. Getting monitor details..."
-curl -s http://localhost:8000/api/monitors/$MONITOR_ID | jq .
+curl -s http://localhost:8000/api/v1/monitors/$MONITOR_ID | jq .
 
 echo -e "\n6. Updating monitor..."
-curl -s -X PUT http://localhost:8000/api/monitors/$MONITOR_ID \
+curl -s -X PUT http://localhost:8000/api/v1/monitors/$MONITOR_ID \
   -H "Content-Type: application/json" \
   -d '{
     "display_name": "Main API Health Check (Updated)",
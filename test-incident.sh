@@ -1,7 +1,7 @@
 #!/bin/bash
 
 # Create a test incident
-curl -X POST http://localhost:3000/api/incidents \
+curl -X POST http://localhost:3000/api/v1/incidents \
   -H "Content-Type: application/json" \
   -d '{
     "title": "Database Connection Issues",
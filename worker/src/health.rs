@@ -0,0 +1,93 @@
+//! Small HTTP server for orchestration probes: liveness, readiness and
+//! job metrics in the Prometheus text format.
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use prometheus_client::encoding::text::encode;
+use prometheus_client::registry::Registry;
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+use crate::metrics::JobMetrics;
+
+const DATABASE_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
+pub struct HealthState {
+    pool: PgPool,
+    ready: Arc<AtomicBool>,
+    registry: Arc<Registry>,
+}
+
+impl HealthState {
+    /// Starts out not ready; call [`HealthState::set_ready`] once the worker
+    /// is taking jobs.
+    pub fn new(pool: PgPool, metrics: &JobMetrics) -> Self {
+        let mut registry = Registry::default();
+        metrics.register(&mut registry);
+
+        Self {
+            pool,
+            ready: Arc::new(AtomicBool::new(false)),
+            registry: Arc::new(registry),
+        }
+    }
+
+    pub fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::SeqCst);
+    }
+}
+
+pub fn router(state: HealthState) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/metrics", get(metrics))
+        .with_state(state)
+}
+
+pub async fn serve(addr: SocketAddr, state: HealthState) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, router(state)).await
+}
+
+/// The process is up
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+/// The worker is taking jobs and can reach the database. Turns unavailable
+/// as soon as shutdown starts, while running jobs finish.
+async fn readyz(State(state): State<HealthState>) -> (StatusCode, &'static str) {
+    if !state.ready.load(Ordering::SeqCst) {
+        return (StatusCode::SERVICE_UNAVAILABLE, "not ready");
+    }
+
+    let ping = sqlx::query("SELECT 1").execute(&state.pool);
+    match tokio::time::timeout(DATABASE_CHECK_TIMEOUT, ping).await {
+        Ok(Ok(_)) => (StatusCode::OK, "ok"),
+        _ => (StatusCode::SERVICE_UNAVAILABLE, "database unavailable"),
+    }
+}
+
+async fn metrics(State(state): State<HealthState>) -> impl IntoResponse {
+    let mut body = String::new();
+    if encode(&mut body, &state.registry).is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    (
+        [(header::CONTENT_TYPE, "application/openmetrics-text; version=1.0.0; charset=utf-8")],
+        body,
+    )
+        .into_response()
+}
@@ -1,48 +1,100 @@
-use dotenv::dotenv;
 use graphile_worker::WorkerOptions;
-use sqlx::postgres::PgPoolOptions;
-use std::env;
+use status_page::export::Exporter;
 use std::error::Error;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tracing::info;
-use tracing_subscriber::fmt::format::FmtSpan;
+use tracing::{error, info};
 
+mod health;
+mod metrics;
 mod tasks;
 
+use health::HealthState;
+use metrics::JobMetrics;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    // Initialize environment variables
-    dotenv().ok();
+    // `worker config check` prints the effective configuration and exits
+    if let Some(code) = config_core::run_command(std::env::args().skip(1)) {
+        std::process::exit(code);
+    }
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_span_events(FmtSpan::CLOSE)
-        .init();
+    // Read config.toml and the environment, refusing to start if invalid
+    let config = config_core::Config::load_or_exit();
 
-    info!("Starting worker...");
+    // Log to stdout and, when configured, export traces over OTLP
+    let telemetry = telemetry::init("rustgenie-worker");
 
-    // Get database connection string from environment
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    info!("Starting worker...");
 
     // Create database pool
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
-        .await?;
+    let db_pool = db_core::init_db_pool(&config.database).await?;
+    let pool = (*db_pool).clone();
+
+    // The static status page copy, when export.dir or export.bucket is set
+    let exporter = Exporter::from_config(&config.export)?.map(Arc::new);
+
+    // `worker export-status` writes the copy once and exits
+    if std::env::args().nth(1).as_deref() == Some("export-status") {
+        let code = match &exporter {
+            Some(exporter) => match exporter.export_current(&db_pool).await {
+                Ok(()) => 0,
+                Err(e) => {
+                    error!("Status page export failed: {}", e);
+                    1
+                }
+            },
+            None => {
+                error!("Status page export is not configured; set export.dir or export.bucket");
+                2
+            }
+        };
+        telemetry.shutdown();
+        std::process::exit(code);
+    }
 
     // Initialize the worker with options
     let mut options = WorkerOptions::default()
-        .concurrency(5)
+        .concurrency(config.worker.concurrency)
         .schema("graphile_worker")
         .pg_pool(pool.clone());
         
     // Register all tasks
     options = tasks::register_tasks(options);
+
+    // Repair the check schedule every few minutes
+    options = options.with_crontab(tasks::schedule_monitors::RECONCILE_CRONTAB)?;
+
+    // Raise and clear SLO burn-rate alerts
+    options = options.with_crontab(tasks::evaluate_slos::EVALUATE_CRONTAB)?;
     
-    // Convert sqlx pool to db_core pool and add to worker context
-    let db_pool: db_core::DbPool = Arc::new(pool.clone());
-    options = options.add_extension(db_pool);
-    
+    // Share the db_core pool with tasks through the worker context
+    options = options.add_extension(db_pool.clone());
+
+    // Keep the static status page copy fresh between changes
+    if let Some(exporter) = &exporter {
+        options = options
+            .with_crontab(tasks::export_status_page::EXPORT_CRONTAB)?
+            .add_extension(exporter.clone());
+    }
+
+    // Count jobs for the health server
+    let metrics = JobMetrics::default();
+    options = options.add_extension(metrics.clone());
+
+    // Serve health probes and metrics while the worker starts, runs and stops
+    let health = HealthState::new(pool.clone(), &metrics);
+    let health_addr = SocketAddr::from(([0, 0, 0, 0], config.worker.health_port));
+    tokio::spawn({
+        let health = health.clone();
+        async move {
+            if let Err(e) = health::serve(health_addr, health).await {
+                error!("Health server on {} stopped: {}", health_addr, e);
+            }
+        }
+    });
+    info!("Health server listening on {}", health_addr);
+
     // Initialize the worker
     let worker = options.init().await?;
 
@@ -50,26 +102,81 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let utils = worker.create_utils();
 
     // Add a test job for demonstration
-    utils.add_job(
-        tasks::send_email::SendEmail {
-            to: "test@example.com".to_string(),
-            subject: "Test Email".to_string(),
-            body: "This is a test email from the worker".to_string(),
-        },
-        Default::default(),
-    )
-    .await?;
-
-    info!("Added test email job");
-
-    // Schedule initial monitor checks
-    let db_pool: db_core::DbPool = Arc::new(pool);
-    if let Err(e) = tasks::schedule_monitors::schedule_monitors_periodically(db_pool, &utils).await {
-        tracing::error!("Failed to schedule initial monitor checks: {}", e);
+    if config.worker.demo_job {
+        utils.add_job(
+            tasks::send_email::SendEmail {
+                to: "test@example.com".to_string(),
+                subject: "Test Email".to_string(),
+                body: "This is a test email from the worker".to_string(),
+            },
+            Default::default(),
+        )
+        .await?;
+
+        info!("Added test email job");
+    }
+
+    // Make sure every active monitor has a pending check before starting
+    if let Err(e) = tasks::schedule_monitors::reconcile(&db_pool, &utils).await {
+        error!("Failed to reconcile monitor check schedule: {}", e);
     }
 
+    // Export now, then after every change the status page shows
+    if exporter.is_some() {
+        tasks::export_status_page::queue(&utils, config.export.debounce()).await;
+        tokio::spawn(tasks::export_status_page::watch(db_pool.clone(), config.export.debounce()));
+    }
+
+    health.set_ready(true);
+
+    // graphile_worker stops taking jobs on SIGINT/SIGTERM by itself and
+    // interrupts running ones five seconds later; report not ready right
+    // away so no new work is routed here
+    tokio::spawn({
+        let health = health.clone();
+        async move {
+            shutdown_signal().await;
+            info!("Shutdown signal received, finishing running jobs");
+            health.set_ready(false);
+        }
+    });
+
     // Run the worker
     worker.run().await?;
 
+    info!("Worker stopped");
+
+    telemetry.shutdown();
     Ok(())
 }
+
+/// Resolves on SIGINT or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
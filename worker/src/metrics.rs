@@ -0,0 +1,172 @@
+//! Job throughput and failure counters, served by the health server.
+
+use graphile_worker::{IntoTaskHandlerResult, TaskHandler, WorkerContext};
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::{counter::Counter, family::Family, gauge::Gauge};
+use prometheus_client::registry::Registry;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use tracing::{info_span, Instrument};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct TaskLabels {
+    task: String,
+}
+
+/// Per-task job counters. Cheap to clone; clones share the same counts.
+#[derive(Clone, Debug, Default)]
+pub struct JobMetrics {
+    completed: Family<TaskLabels, Counter>,
+    failed: Family<TaskLabels, Counter>,
+    in_flight: Gauge,
+}
+
+impl JobMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "worker_jobs_completed",
+            "Jobs that finished successfully",
+            self.completed.clone(),
+        );
+        registry.register(
+            "worker_jobs_failed",
+            "Jobs that returned an error, panicked or were interrupted",
+            self.failed.clone(),
+        );
+        registry.register("worker_jobs_in_flight", "Jobs currently running", self.in_flight.clone());
+    }
+
+    /// Run a job and count its outcome. The job runs in place, so when
+    /// graphile_worker interrupts it at shutdown it stops and counts as
+    /// failed, rather than running on beside the retry.
+    pub async fn track<F, R>(&self, task: &'static str, job: F) -> Result<(), String>
+    where
+        F: Future<Output = R>,
+        R: IntoTaskHandlerResult,
+    {
+        let running = Running::start(self.clone(), task);
+        let result = job.await.into_task_handler_result().map_err(|e| format!("{:?}", e));
+        running.finish(result.is_ok());
+        result
+    }
+}
+
+/// Counts a running job. Dropped without [`Running::finish`], e.g. by a
+/// panic or an interrupted job, it records the job as failed.
+struct Running {
+    metrics: JobMetrics,
+    task: &'static str,
+    succeeded: bool,
+}
+
+impl Running {
+    fn start(metrics: JobMetrics, task: &'static str) -> Self {
+        metrics.in_flight.inc();
+        Self { metrics, task, succeeded: false }
+    }
+
+    fn finish(mut self, succeeded: bool) {
+        self.succeeded = succeeded;
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        let labels = TaskLabels { task: self.task.to_string() };
+        let outcome = if self.succeeded { &self.metrics.completed } else { &self.metrics.failed };
+        outcome.get_or_create(&labels).inc();
+        self.metrics.in_flight.dec();
+    }
+}
+
+/// Runs task `T` in a `job` span under the [`JobMetrics`] in the worker
+/// context. It has the same identifier and payload as `T`, so jobs are queued
+/// as plain `T`; the app adds the trace context of the request that queued
+/// the job, which the span continues.
+#[derive(Deserialize, Serialize)]
+pub struct Tracked<T> {
+    #[serde(flatten)]
+    pub task: T,
+    // Must match telemetry::PAYLOAD_KEY
+    #[serde(default, rename = "trace_context", skip_serializing_if = "HashMap::is_empty")]
+    pub trace_context: HashMap<String, String>,
+}
+
+impl<T: TaskHandler> TaskHandler for Tracked<T> {
+    const IDENTIFIER: &'static str = T::IDENTIFIER;
+
+    async fn run(self, ctx: WorkerContext) -> impl IntoTaskHandlerResult {
+        let metrics = ctx.get_ext::<JobMetrics>().cloned().unwrap_or_default();
+
+        let span = info_span!(
+            "job",
+            otel.name = T::IDENTIFIER,
+            otel.kind = "consumer",
+            job.id = *ctx.job().id(),
+            job.task = T::IDENTIFIER,
+            job.attempt = *ctx.job().attempts(),
+        );
+        telemetry::set_parent(&span, &self.trace_context);
+
+        metrics.track(T::IDENTIFIER, self.task.run(ctx).instrument(span)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus_client::encoding::text::encode;
+
+    #[tokio::test]
+    async fn jobs_are_counted_by_outcome() {
+        let metrics = JobMetrics::default();
+        let mut registry = Registry::default();
+        metrics.register(&mut registry);
+
+        assert!(metrics.track("send_email", async { Ok::<(), String>(()) }).await.is_ok());
+        assert!(metrics.track("check_monitor", async { Err::<(), _>("timed out") }).await.is_err());
+        let panicked = tokio::spawn({
+            let metrics = metrics.clone();
+            async move { metrics.track::<_, ()>("check_monitor", async { panic!("boom") }).await }
+        });
+        assert!(panicked.await.is_err());
+
+        // graphile_worker aborts jobs still running a while after shutdown
+        // starts; an interrupted job stops there and counts as failed
+        let (_release, released) = tokio::sync::oneshot::channel::<()>();
+        let interrupted = tokio::spawn({
+            let metrics = metrics.clone();
+            async move { metrics.track("check_monitor", async { released.await.map_err(|e| e.to_string()) }).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(metrics.in_flight.get(), 1);
+        interrupted.abort();
+        assert!(interrupted.await.unwrap_err().is_cancelled());
+        assert_eq!(metrics.in_flight.get(), 0);
+
+        let mut text = String::new();
+        encode(&mut text, &registry).unwrap();
+        assert!(text.contains(r#"worker_jobs_completed_total{task="send_email"} 1"#));
+        assert!(!text.contains(r#"worker_jobs_completed_total{task="check_monitor"}"#));
+        assert!(text.contains(r#"worker_jobs_failed_total{task="check_monitor"} 3"#));
+        assert!(text.contains("worker_jobs_in_flight 0"));
+    }
+
+    #[test]
+    fn payloads_carry_an_optional_trace_context() {
+        use crate::tasks::check_monitor::CheckMonitor;
+
+        let payload = serde_json::json!({
+            "monitor_id": 7,
+            (telemetry::PAYLOAD_KEY): { "traceparent": "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01" },
+        });
+        let job: Tracked<CheckMonitor> = serde_json::from_value(payload).unwrap();
+        assert_eq!(job.task.monitor_id, 7);
+        assert!(job.trace_context.contains_key("traceparent"));
+
+        let job: Tracked<CheckMonitor> = serde_json::from_value(serde_json::json!({ "monitor_id": 7 })).unwrap();
+        assert!(job.trace_context.is_empty());
+        assert_eq!(serde_json::to_value(&job).unwrap(), serde_json::json!({ "monitor_id": 7 }));
+    }
+}
@@ -0,0 +1,142 @@
+use db_core::models::slo::{SloAlert, SloStatus};
+use db_core::{repositories::SloRepository, DbPool};
+use crate::tasks::send_email::SendEmail;
+use graphile_worker::{IntoTaskHandlerResult, JobSpec, TaskHandler, WorkerContext, WorkerUtils};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+/// How often SLOs are evaluated, as a crontab entry. The hourly aggregate
+/// refreshes every minute, so this is as fresh as the burn rates get.
+pub const EVALUATE_CRONTAB: &str = "*/5 * * * * evaluate_slos";
+
+/// Compares each SLO's burn rates with the alert it last raised, records
+/// the change and emails the SLO's alert address about it
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct EvaluateSlos {}
+
+impl TaskHandler for EvaluateSlos {
+    const IDENTIFIER: &'static str = "evaluate_slos";
+
+    async fn run(self, ctx: WorkerContext) -> impl IntoTaskHandlerResult {
+        let pool = ctx.get_ext::<DbPool>()
+            .ok_or_else(|| "Database pool not found in context".to_string())?;
+        let utils = WorkerUtils::new(pool.as_ref().clone(), "graphile_worker".to_string());
+
+        evaluate(pool, &utils).await.map_err(|e| e.to_string())
+    }
+}
+
+pub async fn evaluate(
+    pool: &DbPool,
+    utils: &WorkerUtils,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let slos = SloRepository::list_all(pool).await?;
+    let (mut changed, mut failed) = (0, 0);
+
+    for slo in slos {
+        let previous = SloAlert::from(slo.alert_status.clone());
+        let id = slo.id;
+        // One SLO failing to evaluate should not hold up the others
+        let status = match SloRepository::get_status(pool, slo).await {
+            Ok(status) => status,
+            Err(e) => {
+                error!("Failed to evaluate SLO {}: {}", id, e);
+                failed += 1;
+                continue;
+            }
+        };
+        if status.alert == previous {
+            continue;
+        }
+
+        if let Err(e) = SloRepository::set_alert_status(pool, status.slo.id, status.alert).await {
+            warn!("Failed to record the alert status of SLO {}: {}", status.slo.id, e);
+            failed += 1;
+            continue;
+        }
+        changed += 1;
+        if status.alert > previous {
+            warn!("SLO {} ({}) is now {:?}", status.slo.id, status.slo.name, status.alert);
+        } else {
+            info!("SLO {} ({}) is back to {:?}", status.slo.id, status.slo.name, status.alert);
+        }
+
+        if let Some(to) = &status.slo.alert_email {
+            let email = SendEmail {
+                to: to.clone(),
+                subject: subject(&status, previous),
+                body: body(&status),
+            };
+            if let Err(e) = utils.add_job(email, JobSpec::default()).await {
+                error!("Failed to queue alert email for SLO {}: {}", status.slo.id, e);
+            }
+        }
+    }
+
+    info!("Evaluated SLOs; {} changed alert status, {} failed", changed, failed);
+    Ok(())
+}
+
+fn subject(status: &SloStatus, previous: SloAlert) -> String {
+    let name = &status.slo.name;
+    match status.alert {
+        SloAlert::Critical => format!("[critical] SLO \"{}\" is burning its error budget fast", name),
+        SloAlert::Warning if previous < SloAlert::Warning => format!("[warning] SLO \"{}\" is burning its error budget", name),
+        SloAlert::Warning => format!("[warning] SLO \"{}\" has eased to a warning", name),
+        SloAlert::Ok => format!("[resolved] SLO \"{}\" is back within budget", name),
+    }
+}
+
+fn body(status: &SloStatus) -> String {
+    let rate = |rate: Option<f64>| rate.map_or_else(|| "no checks".to_string(), |rate| format!("{:.1}x", rate));
+    let sli = status.sli.map_or_else(|| "no checks".to_string(), |sli| format!("{:.3}%", sli));
+
+    format!(
+        "Objective: {}% over {} days\nSLI: {}\nError budget left: {:.1}%\nBurn rate, last hour: {}\nBurn rate, last 6 hours: {}",
+        status.slo.target,
+        status.slo.window_days,
+        sli,
+        status.error_budget_remaining * 100.0,
+        rate(status.burn_rate_1h),
+        rate(status.burn_rate_6h),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db_core::models::slo::{SliCounts, Slo};
+    use time::OffsetDateTime;
+
+    fn status(hour: SliCounts, six_hours: SliCounts) -> SloStatus {
+        let slo = Slo {
+            id: 1,
+            name: "Checkout".to_string(),
+            description: None,
+            monitor_id: Some(1),
+            service_group_id: None,
+            target: 99.0,
+            window_days: 30,
+            latency_p95_ms: None,
+            alert_email: Some("oncall@example.com".to_string()),
+            alert_status: "ok".to_string(),
+            alert_changed_at: None,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: OffsetDateTime::UNIX_EPOCH,
+        };
+        SloStatus::new(slo, vec![1], SliCounts { total: 1000, good: 995 }, hour, six_hours)
+    }
+
+    #[test]
+    fn emails_say_which_way_the_alert_moved() {
+        // 20% of checks failing is 20x a 1% budget
+        let burning = status(SliCounts { total: 60, good: 48 }, SliCounts { total: 360, good: 300 });
+        assert_eq!(burning.alert, SloAlert::Critical);
+        assert_eq!(subject(&burning, SloAlert::Ok), "[critical] SLO \"Checkout\" is burning its error budget fast");
+        assert!(body(&burning).contains("Burn rate, last hour: 20.0x"));
+
+        let recovered = status(SliCounts { total: 60, good: 60 }, SliCounts { total: 360, good: 360 });
+        assert_eq!(recovered.alert, SloAlert::Ok);
+        assert_eq!(subject(&recovered, SloAlert::Critical), "[resolved] SLO \"Checkout\" is back within budget");
+    }
+}
@@ -0,0 +1,83 @@
+use db_core::notifications::{self, Change};
+use db_core::DbPool;
+use graphile_worker::{IntoTaskHandlerResult, JobKeyMode, JobSpec, TaskHandler, WorkerContext, WorkerUtils};
+use serde::{Deserialize, Serialize};
+use status_page::export::Exporter;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::error;
+
+/// Refresh the copy even when nothing changes, so its uptime bars and
+/// "last updated" time keep moving
+pub const EXPORT_CRONTAB: &str = "*/10 * * * * export_status_page";
+
+/// Pending exports share one key, so a burst of changes is exported once
+const JOB_KEY: &str = "export_status_page";
+
+/// Writes the static copy of the status page to the configured directory or
+/// bucket. Queued by [`watch`] after every change the page shows.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ExportStatusPage {}
+
+impl TaskHandler for ExportStatusPage {
+    const IDENTIFIER: &'static str = "export_status_page";
+
+    async fn run(self, ctx: WorkerContext) -> impl IntoTaskHandlerResult {
+        let pool = ctx.get_ext::<DbPool>()
+            .ok_or_else(|| "Database pool not found in context".to_string())?;
+        let exporter = ctx.get_ext::<Arc<Exporter>>()
+            .ok_or_else(|| "Status page export is not configured".to_string())?;
+
+        exporter.export_current(pool).await.map_err(|e| e.to_string())
+    }
+}
+
+/// Queue an export `debounce` from now, unless one is already pending
+pub async fn queue(utils: &WorkerUtils, debounce: Duration) {
+    let job_spec = JobSpec {
+        job_key: Some(JOB_KEY.to_string()),
+        // Later changes join the pending export rather than postponing it
+        job_key_mode: Some(JobKeyMode::PreserveRunAt),
+        run_at: Some(chrono::Utc::now() + chrono::Duration::seconds(debounce.as_secs() as i64)),
+        ..Default::default()
+    };
+    if let Err(e) = utils.add_job(ExportStatusPage {}, job_spec).await {
+        error!("Failed to queue status page export: {}", e);
+    }
+}
+
+/// Listen for changes and queue an export for each one the status page
+/// shows. Runs for the life of the worker; with several workers, the shared
+/// job key still yields one export per burst.
+pub async fn watch(pool: DbPool, debounce: Duration) {
+    let utils = WorkerUtils::new(pool.as_ref().clone(), "graphile_worker".to_string());
+    let utils = &utils;
+
+    notifications::listen(&pool, "Status page export", |change| async move {
+        if shows_on_status_page(&change) {
+            queue(utils, debounce).await;
+        }
+    })
+    .await
+}
+
+fn shows_on_status_page(change: &Change) -> bool {
+    match change {
+        // Every check records an event; only a new status changes the page
+        Change::StatusEvent { event, previous_status } => previous_status.as_deref() != Some(event.status.as_str()),
+        Change::Incident { .. } | Change::Maintenance { .. } => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db_core::notifications::status_change;
+
+    #[test]
+    fn only_status_transitions_trigger_an_export() {
+        assert!(!shows_on_status_page(&status_change(1, "operational", Some("operational"))));
+        assert!(shows_on_status_page(&status_change(1, "major_outage", Some("operational"))));
+        assert!(shows_on_status_page(&status_change(1, "operational", None)));
+    }
+}
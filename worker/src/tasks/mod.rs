@@ -1,13 +1,22 @@
 pub mod send_email;
 pub mod check_monitor;
 pub mod schedule_monitors;
+pub mod export_status_page;
+pub mod evaluate_slos;
 
+use crate::metrics::Tracked;
 use graphile_worker::WorkerOptions;
 use send_email::SendEmail;
 use check_monitor::CheckMonitor;
+use schedule_monitors::ReconcileSchedules;
+use export_status_page::ExportStatusPage;
+use evaluate_slos::EvaluateSlos;
 
 pub fn register_tasks(options: WorkerOptions) -> WorkerOptions {
     options
-        .define_job::<SendEmail>()
-        .define_job::<CheckMonitor>()
+        .define_job::<Tracked<SendEmail>>()
+        .define_job::<Tracked<CheckMonitor>>()
+        .define_job::<Tracked<ReconcileSchedules>>()
+        .define_job::<Tracked<ExportStatusPage>>()
+        .define_job::<Tracked<EvaluateSlos>>()
 }
@@ -1,39 +1,195 @@
+use db_core::models::monitor::Monitor;
 use db_core::{repositories::MonitorRepository, DbPool};
 use crate::tasks::check_monitor::CheckMonitor;
-use graphile_worker::JobSpec;
-use tracing::info;
+use graphile_worker::{IntoTaskHandlerResult, JobKeyMode, JobSpec, TaskHandler, WorkerContext, WorkerUtils};
+use monitor_checks::{schedule, SUPPORTED_TYPES};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use time::{Duration, OffsetDateTime};
+use tracing::{error, info, warn};
 
-/// Schedule recurring checks for all active monitors
-pub async fn schedule_monitors_periodically(
-    pool: DbPool,
-    utils: &graphile_worker::WorkerUtils,
+/// How often the reconciler runs, as a crontab entry
+pub const RECONCILE_CRONTAB: &str = "*/5 * * * * reconcile_schedules";
+
+/// Periodically repairs the check schedule. The app schedules and cancels
+/// checks as monitors change, but a failed call, a crashed check or a
+/// monitor written straight to the database can leave an active monitor
+/// without a pending check, or a pending check for a monitor that is gone.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ReconcileSchedules {}
+
+impl TaskHandler for ReconcileSchedules {
+    const IDENTIFIER: &'static str = "reconcile_schedules";
+
+    async fn run(self, ctx: WorkerContext) -> impl IntoTaskHandlerResult {
+        let pool = ctx.get_ext::<DbPool>()
+            .ok_or_else(|| "Database pool not found in context".to_string())?;
+        let utils = WorkerUtils::new(pool.as_ref().clone(), "graphile_worker".to_string());
+
+        reconcile(pool, &utils).await.map_err(|e| e.to_string())
+    }
+}
+
+/// A pending check job, as stored by graphile_worker
+#[derive(Debug, sqlx::FromRow)]
+struct ScheduledCheck {
+    key: String,
+    run_at: OffsetDateTime,
+    attempts: i16,
+    max_attempts: i16,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Plan {
+    /// Monitors whose check should be (re)scheduled to run now
+    schedule: Vec<i32>,
+    /// Keys of check jobs to remove
+    cancel: Vec<String>,
+}
+
+/// Schedule a check for every active monitor that has no usable pending
+/// check and cancel pending checks of monitors that are no longer checked
+pub async fn reconcile(
+    pool: &DbPool,
+    utils: &WorkerUtils,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-    info!("Scheduling initial monitor checks");
-    
-    let monitors = MonitorRepository::list_active(&pool).await?;
-    let monitor_count = monitors.len();
-    
-    for monitor in monitors {
-        info!("Scheduling initial check for monitor {}: {} (every {} seconds)", 
-            monitor.id, monitor.display_name, monitor.check_interval);
-        
-        // Schedule immediate check with a unique key
+    let monitors = MonitorRepository::list_active(pool).await?;
+    let checks = scheduled_checks(pool).await?;
+    let plan = plan(&monitors, &checks, OffsetDateTime::now_utc());
+
+    for monitor_id in &plan.schedule {
         let job_spec = JobSpec {
-            job_key: Some(format!("monitor_{}_initial", monitor.id)),
-            job_key_mode: Some(graphile_worker::JobKeyMode::Replace),
+            job_key: Some(schedule::job_key(*monitor_id)),
+            job_key_mode: Some(JobKeyMode::Replace),
             ..Default::default()
         };
-        
-        utils.add_job(
-            CheckMonitor {
-                monitor_id: monitor.id,
-            },
-            job_spec,
-        ).await?;
-    }
-    
-    info!("Scheduled initial checks for {} monitors", monitor_count);
-    
+        if let Err(e) = utils.add_job(CheckMonitor { monitor_id: *monitor_id }, job_spec).await {
+            error!("Failed to schedule check for monitor {}: {}", monitor_id, e);
+        }
+    }
+    for key in &plan.cancel {
+        if let Err(e) = utils.remove_job(key).await {
+            error!("Failed to remove check job {}: {}", key, e);
+        }
+    }
+
+    if plan == Plan::default() {
+        info!("Check schedule is up to date for {} monitors", monitors.len());
+    } else {
+        warn!(
+            "Repaired check schedule: scheduled {:?}, removed {:?}",
+            plan.schedule, plan.cancel
+        );
+    }
+
     Ok(())
 }
 
+async fn scheduled_checks(pool: &PgPool) -> Result<Vec<ScheduledCheck>, sqlx::Error> {
+    sqlx::query_as::<_, ScheduledCheck>(
+        r#"
+        SELECT key, run_at, attempts, max_attempts
+        FROM graphile_worker.jobs
+        WHERE task_identifier = $1 AND key IS NOT NULL
+        "#,
+    )
+    .bind(schedule::TASK)
+    .fetch_all(pool)
+    .await
+}
+
+fn plan(monitors: &[Monitor], checks: &[ScheduledCheck], now: OffsetDateTime) -> Plan {
+    let checked: Vec<&Monitor> = monitors
+        .iter()
+        .filter(|m| m.is_active && SUPPORTED_TYPES.contains(&m.monitor_type.as_str()))
+        .collect();
+
+    let mut plan = Plan::default();
+
+    for monitor in &checked {
+        let key = schedule::job_key(monitor.id);
+        // A check that will never run, or not before the interval is up,
+        // counts as missing
+        let usable = checks.iter().any(|check| {
+            check.key == key
+                && check.attempts < check.max_attempts
+                && check.run_at <= now + Duration::seconds(monitor.check_interval as i64)
+        });
+        if !usable {
+            plan.schedule.push(monitor.id);
+        }
+    }
+
+    for check in checks {
+        if let Some(monitor_id) = schedule::monitor_id(&check.key) {
+            if !checked.iter().any(|m| m.id == monitor_id) {
+                plan.cancel.push(check.key.clone());
+            }
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(id: i32, monitor_type: &str, is_active: bool) -> Monitor {
+        Monitor {
+            id,
+            name: format!("monitor-{}", id),
+            display_name: format!("Monitor {}", id),
+            description: None,
+            url: Some("https://example.com".to_string()),
+            monitor_type: monitor_type.to_string(),
+            check_interval: 60,
+            timeout: 10,
+            is_active,
+            metadata: None,
+            service_group_id: None,
+            weight: 1,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn check(monitor_id: i32, run_at: OffsetDateTime, attempts: i16) -> ScheduledCheck {
+        ScheduledCheck {
+            key: schedule::job_key(monitor_id),
+            run_at,
+            attempts,
+            max_attempts: 25,
+        }
+    }
+
+    #[test]
+    fn plan_repairs_missing_stale_and_orphaned_checks() {
+        let now = OffsetDateTime::now_utc();
+        let monitors = [
+            monitor(1, "http", true),
+            monitor(2, "http", true),
+            monitor(3, "tcp", true),
+            monitor(4, "http", true),
+            monitor(5, "custom", true),
+        ];
+        let checks = [
+            // 1 is fine
+            check(1, now + Duration::seconds(30), 0),
+            // 2 has no check; 3's check failed for good
+            check(3, now, 25),
+            // 4's check is further out than its interval
+            check(4, now + Duration::hours(1), 0),
+            // 9 no longer exists or is paused
+            check(9, now, 0),
+        ];
+
+        assert_eq!(
+            plan(&monitors, &checks, now),
+            Plan {
+                schedule: vec![2, 3, 4],
+                cancel: vec![schedule::job_key(9)],
+            }
+        );
+    }
+}